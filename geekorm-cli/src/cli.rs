@@ -41,11 +41,22 @@ pub enum ArgumentCommands {
     Migrate {
         #[clap(short, long, default_value_t = false)]
         data: bool,
+        /// Allow destructive changes (dropping tables or columns) without confirmation
+        #[clap(long, default_value_t = false)]
+        force: bool,
+        /// Only migrate the named database from the configuration
+        #[clap(long)]
+        database: Option<String>,
     },
     /// Update
     Update,
     /// Test the migrations
-    Test,
+    Test {
+        /// Apply the migrations to a temporary SQLite database and check the
+        /// resulting schema matches the latest definition exactly
+        #[clap(long, default_value_t = false)]
+        live: bool,
+    },
     /// Read and display the database schema generated by GeekORM
     Display,
 }
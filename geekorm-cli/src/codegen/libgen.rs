@@ -1,6 +1,7 @@
 use anyhow::Result;
 use quote::{format_ident, quote};
 
+use super::code_format;
 use crate::utils::Config;
 
 pub async fn lib_generation(config: &Config) -> Result<()> {
@@ -30,6 +31,14 @@ pub async fn lib_generation(config: &Config) -> Result<()> {
         });
     });
 
+    let queries = if config.queries {
+        quote! {
+            pub mod queries;
+        }
+    } else {
+        quote! {}
+    };
+
     let ast = if !imports.is_empty() {
         quote! {
             //! GeekORM Database Migrations
@@ -38,6 +47,8 @@ pub async fn lib_generation(config: &Config) -> Result<()> {
 
             #( #imports )*
 
+            #queries
+
             pub use #latest::{Database, Migration as LatestMigration};
 
             pub async fn init<'a, T>(connection: &'a T) -> Result<(), geekorm::Error>
@@ -77,7 +88,7 @@ pub async fn lib_generation(config: &Config) -> Result<()> {
     };
 
     log::debug!("Writing the lib/mod file...");
-    tokio::fs::write(&lib_file, ast.to_string().as_bytes()).await?;
+    tokio::fs::write(&lib_file, code_format(ast)?.as_bytes()).await?;
 
     if config.module_mode() {
         log::warn!("The module must be manually added to our lib.rs/main.rs file.");
@@ -3,6 +3,7 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use std::path::PathBuf;
 
+use super::code_format;
 use crate::utils::database::Database;
 use crate::utils::Config;
 
@@ -126,14 +127,14 @@ pub async fn create_mod(config: &Config, path: &PathBuf) -> Result<()> {
 
     };
 
-    tokio::fs::write(path, ast.to_string().as_bytes()).await?;
+    tokio::fs::write(path, code_format(ast)?.as_bytes()).await?;
 
     if config.data_migrations {
         let data_path = config.migrations_data_path()?;
         if !data_path.exists() {
             log::debug!("Creating data migrations: {}", data_path.display());
 
-            tokio::fs::write(data_path, data_migrations.to_string().as_bytes()).await?;
+            tokio::fs::write(data_path, code_format(data_migrations)?.as_bytes()).await?;
         } else {
             log::warn!("Data migrations already exist: {}", data_path.display());
         }
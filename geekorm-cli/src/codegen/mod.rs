@@ -1,7 +1,53 @@
 pub(crate) mod libgen;
 pub(crate) mod migration_mod;
+pub(crate) mod queries;
 pub(crate) mod sqlgen;
 
+use anyhow::Result;
+use proc_macro2::TokenStream;
+
 pub(crate) use libgen::lib_generation;
 pub(crate) use migration_mod::create_mod;
+pub(crate) use queries::queries_generation;
 pub(crate) use sqlgen::generate_create_sql;
+
+/// Format a generated token stream as a Rust source file
+///
+/// The output is deterministic so re-running `geekorm-cli update` does not
+/// produce noisy diffs in version control.
+pub(crate) fn code_format(ast: TokenStream) -> Result<String> {
+    let file: syn::File = syn::parse2(ast)?;
+    Ok(prettyplease::unparse(&file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn test_code_format() -> Result<()> {
+        let ast = || {
+            quote! {
+                #![doc = "GeekORM Database Migrations"]
+                pub struct Migration;
+                impl geekorm::Migration for Migration {
+                    fn version() -> &'static str { "0.1.0" }
+                }
+                geekorm::lazy_static! {
+                    pub static ref Database: Box<geekorm::Database> = Box::new(
+                        geekorm::Database { tables: Vec::from([]) }
+                    );
+                }
+            }
+        };
+
+        let code = code_format(ast())?;
+        assert!(code.lines().count() > 1);
+        assert!(code.contains("pub struct Migration;\n"));
+        assert!(code.contains("    fn version() -> &'static str {\n"));
+        // Formatting the same tokens twice must produce the same output
+        assert_eq!(code, code_format(ast())?);
+        Ok(())
+    }
+}
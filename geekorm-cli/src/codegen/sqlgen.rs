@@ -16,6 +16,10 @@ pub async fn generate_create_sql(database: &Database, path: &PathBuf) -> Result<
 
         data.push_str(&comment);
         data.push_str(&query);
+        for index in table.on_create_indexes() {
+            data.push('\n');
+            data.push_str(&index);
+        }
         data += "\n\n";
     }
 
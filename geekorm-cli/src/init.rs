@@ -105,6 +105,10 @@ pub async fn initalise(config: &Config) -> Result<()> {
         }
         _ => {}
     });
+    // The typed queries deserialize rows with serde
+    if config.queries {
+        crates.push("serde");
+    }
     log::debug!("Features: {:?}", features);
 
     let geekorm_lib: Vec<String> = if let Some(gpath) = &config.geekorm {
@@ -36,8 +36,16 @@ async fn main() -> Result<()> {
             init::init(&mut config).await?;
             config.save(&arguments.config)?;
         }
-        Some(ArgumentCommands::Migrate { data }) => {
+        Some(ArgumentCommands::Migrate {
+            data,
+            force,
+            database,
+        }) => {
             config.data_migrations = data;
+            config.force_migrations = force;
+            if let Some(database) = database {
+                config.select_database(&database).await?;
+            }
             migrations::create_migrations(&mut config).await?;
         }
         Some(ArgumentCommands::Update) => {
@@ -46,9 +54,14 @@ async fn main() -> Result<()> {
             }
 
             codegen::lib_generation(&config).await?;
+            codegen::queries_generation(&config).await?;
         }
-        Some(ArgumentCommands::Test) => {
-            let results = migrations::test_migrations(&config).await?;
+        Some(ArgumentCommands::Test { live }) => {
+            let results = if live {
+                migrations::test_migrations_live(&config).await?
+            } else {
+                migrations::test_migrations(&config).await?
+            };
             if results.errors.is_empty() {
                 log::info!("All migrations passed");
             } else {
@@ -87,6 +100,7 @@ async fn main() -> Result<()> {
                     }
 
                     codegen::lib_generation(&config).await?;
+                    codegen::queries_generation(&config).await?;
                 }
                 "Display" => {
                     log::info!("Displaying the database...");
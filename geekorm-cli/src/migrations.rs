@@ -8,7 +8,7 @@ use std::path::PathBuf;
 
 use crate::codegen;
 use crate::utils::database::Database;
-use crate::utils::{prompt_select, prompt_select_with_default, Config};
+use crate::utils::{prompt_confirm, prompt_select, prompt_select_with_default, Config};
 
 pub async fn create_migrations(config: &mut Config) -> Result<()> {
     log::info!("Initializing a version migration...");
@@ -64,6 +64,7 @@ pub async fn create_migrations(config: &mut Config) -> Result<()> {
 
         codegen::lib_generation(config).await?;
         codegen::create_mod(config, &mod_path).await?;
+        codegen::queries_generation(config).await?;
 
         log::debug!("Formatting the lib/mod file...");
         let fmtdir = if config.crate_mode() {
@@ -105,6 +106,13 @@ async fn create_schema_migration(config: &Config, path: &PathBuf) -> Result<bool
     if !validator.errors.is_empty() {
         log::info!("Errors found, creating a schema migration...");
 
+        let destructive = destructive_changes(&database, &validator.errors);
+        confirm_destructive(
+            &destructive,
+            config.force_migrations,
+            console::Term::stdout().is_term(),
+        )?;
+
         let mut data = "-- This migration will update the schema\n\n".to_string();
 
         for verror in validator.errors.iter() {
@@ -134,8 +142,68 @@ async fn create_schema_migration(config: &Config, path: &PathBuf) -> Result<bool
     }
 }
 
+/// Find the changes which would drop a table or column (and the data in it)
+///
+/// Tables and columns which exist after applying the migrations, but are no
+/// longer in the latest database definition, are dropped.
+fn destructive_changes<'a>(
+    database: &Database,
+    errors: &'a [MigrationError],
+) -> Vec<&'a MigrationError> {
+    errors
+        .iter()
+        .filter(|error| match error {
+            MigrationError::MissingTable(table) => database.get_table(table).is_none(),
+            MigrationError::MissingColumn { table, column } => {
+                database.get_table(table).is_some()
+                    && database.get_table_column(table, column).is_none()
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// Require `--force` or an interactive confirmation before generating destructive changes
+fn confirm_destructive(
+    destructive: &[&MigrationError],
+    force: bool,
+    interactive: bool,
+) -> Result<()> {
+    if destructive.is_empty() {
+        return Ok(());
+    }
+
+    log::warn!("The migration contains destructive changes:");
+    for change in destructive {
+        log::warn!(" > {}", change);
+    }
+
+    if force {
+        log::warn!("Destructive changes allowed (--force)");
+        Ok(())
+    } else if interactive && prompt_confirm("Drop the tables / columns and their data?")? {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(
+            "Destructive migration aborted, use `--force` to allow dropping tables or columns"
+        ))
+    }
+}
+
 fn prompt_table_alter(database: &Database, migrations: &MigrationError) -> Result<String> {
     match migrations {
+        // Destructive changes (confirmed before prompting)
+        MigrationError::MissingTable(table) if database.get_table(table).is_none() => {
+            let alt = AlterQuery::new(AlterMode::DropTable, table, "");
+            Ok(geekorm::prelude::BuilderTable::default().on_alter(&alt)?)
+        }
+        MigrationError::MissingColumn { table, column }
+            if database.get_table(table).is_some()
+                && database.get_table_column(table, column).is_none() =>
+        {
+            let alt = AlterQuery::new(AlterMode::DropColumn, table, column);
+            Ok(geekorm::Column::default().on_alter(&alt)?)
+        }
         MigrationError::MissingTable(table) => {
             if let Some(dbtable) = database.get_table(table) {
                 log::info!("Prompting for missing table: `{:?}`", migrations);
@@ -198,6 +266,74 @@ fn prompt_table_alter(database: &Database, migrations: &MigrationError) -> Resul
                 ))
             }
         }
+        MigrationError::NewTable { table } => {
+            if let Some(dbtable) = database.get_table(table) {
+                log::info!("Creating new table: `{}`", table);
+                let alt = AlterQuery::new(AlterMode::AddTable, table, "");
+                Ok(dbtable.on_alter(&alt)?)
+            } else {
+                Err(anyhow::anyhow!(
+                    "Table not found (this should never happen): {}",
+                    table
+                ))
+            }
+        }
+        MigrationError::RenamedTable { from, to } => {
+            if let Some(dbtable) = database.get_table(to) {
+                log::info!("Prompting for renamed table: `{:?}`", migrations);
+                let prompt = format!("Rename table `{}` to `{}`:", from, to);
+                let (choice, _) =
+                    prompt_select_with_default(prompt.as_str(), &vec!["Rename", "Skip"], 0)?;
+
+                if choice == "Rename" {
+                    let mut alt = AlterQuery::new(AlterMode::RenameTable, from, "");
+                    alt.rename(to);
+                    Ok(dbtable.on_alter(&alt)?)
+                } else {
+                    Ok("".to_string())
+                }
+            } else {
+                Err(anyhow::anyhow!(
+                    "Table not found (this should never happen): {}",
+                    to
+                ))
+            }
+        }
+        MigrationError::RenamedColumn { table, from, to } => {
+            if let Some(dbcolumn) = database.get_table_column(table, to) {
+                log::info!("Prompting for renamed column: `{:?}`", migrations);
+                let prompt = format!("Rename column `{}.{}` to `{}.{}`:", table, from, table, to);
+                let (choice, _) =
+                    prompt_select_with_default(prompt.as_str(), &vec!["Rename", "Skip"], 0)?;
+
+                if choice == "Rename" {
+                    let mut alt = AlterQuery::new(AlterMode::RenameColumn, table, from);
+                    alt.rename(to);
+                    Ok(dbcolumn.on_alter(&alt)?)
+                } else {
+                    Ok("".to_string())
+                }
+            } else {
+                Err(anyhow::anyhow!(
+                    "Column not found (this should never happen): {}.{}",
+                    table,
+                    to
+                ))
+            }
+        }
+        MigrationError::MissingIndex { table, column } => {
+            if let Some(dbcolumn) = database.get_table_column(table, column) {
+                log::info!("Creating missing index: `{}.{}`", table, column);
+                let alt = AlterQuery::new(AlterMode::AddIndex, table, column);
+                Ok(dbcolumn.on_alter(&alt)?)
+            } else {
+                Err(anyhow::anyhow!(
+                    "Column not found (this should never happen): {}.{}",
+                    table,
+                    column
+                ))
+            }
+        }
         _ => {
             todo!("Prompt for other types of migrations")
         }
@@ -210,9 +346,55 @@ pub async fn test_migrations(config: &Config) -> Result<Validator> {
     let connection = rusqlite::Connection::open_in_memory()?;
     log::info!("Created an in-memory database to test the migrations against");
 
+    apply_and_test(&connection, config).await
+}
+
+/// Apply the full migration chain to a temporary SQLite database file and
+/// validate the resulting schema matches the latest database definition
+pub async fn test_migrations_live(config: &Config) -> Result<Validator> {
+    log::info!("Testing the migrations against a temporary database...");
+
+    let db_path = std::env::temp_dir().join(format!(
+        "geekorm-test-{}-{}.db",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    log::info!("Temporary database: {}", db_path.display());
+
+    let connection = rusqlite::Connection::open(&db_path)?;
+    let result = apply_and_test(&connection, config).await;
+    drop(connection);
+
+    if db_path.exists() {
+        tokio::fs::remove_file(&db_path).await?;
+    }
+    result
+}
+
+/// Apply the migration chain to the connection and validate it against the
+/// latest database definition
+async fn apply_and_test(connection: &rusqlite::Connection, config: &Config) -> Result<Validator> {
+    apply_migrations(connection, &migration_paths(config)?).await?;
+
+    let database = latest_database(config)?;
+    test_database(connection, &database).await
+}
+
+fn migration_paths(config: &Config) -> Result<Vec<PathBuf>> {
     let path = config.migrations_src_path()?;
-    let migrations: Vec<PathBuf> = config.versions.iter().map(|v| path.join(v)).collect();
+    Ok(config.versions.iter().map(|v| path.join(v)).collect())
+}
+
+fn latest_database(config: &Config) -> Result<geekorm::Database> {
+    let current_database = Database::find_database(config)?;
+    Ok(geekorm::Database {
+        tables: current_database.tables.clone(),
+    })
+}
 
+/// Apply the migrations in order (`create.sql` for the first version and
+/// `upgrade.sql` for every version after that)
+async fn apply_migrations(connection: &rusqlite::Connection, migrations: &[PathBuf]) -> Result<()> {
     for (index, migration) in migrations.iter().enumerate() {
         let query_path = if index == 0 {
             migration.join("create.sql")
@@ -230,14 +412,7 @@ pub async fn test_migrations(config: &Config) -> Result<Validator> {
             log::warn!("Migration does not exist: {:?}", query_path);
         }
     }
-
-    let current_database = Database::find_database(config)?;
-    let database = geekorm::Database {
-        tables: current_database.tables.clone(),
-    };
-    let validator = test_database(&connection, &database).await?;
-
-    Ok(validator)
+    Ok(())
 }
 
 async fn test_database<'a, C>(connection: &'a C, database: &geekorm::Database) -> Result<Validator>
@@ -245,8 +420,13 @@ where
     C: GeekConnection<Connection = C> + 'a,
 {
     let mut tables = Vec::new();
+    let mut indexes = Vec::new();
     for table_name in C::table_names(connection).await? {
         let table = C::pragma_info(connection, &table_name).await?;
+        indexes.push((
+            table_name.clone(),
+            C::index_info(connection, &table_name).await?,
+        ));
         tables.push((table_name, table));
     }
 
@@ -255,7 +435,20 @@ where
         quick: false,
     };
 
-    match geekorm_core::migrations::validate::validate_database(&tables, database, &mut validator) {
+    let state =
+        geekorm_core::migrations::validate::validate_database(&tables, database, &mut validator)
+            .and_then(|state| {
+                let indexes = geekorm_core::migrations::validate::validate_indexes(
+                    &indexes,
+                    database,
+                    &mut validator,
+                )?;
+                Ok(match state {
+                    MigrationState::OutOfDate(_) => state,
+                    _ => indexes,
+                })
+            });
+    match state {
         Ok(MigrationState::UpToDate) | Ok(MigrationState::Initialized) => {
             log::info!("Database is up to date");
         }
@@ -270,3 +463,152 @@ where
 
     Ok(validator)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geekorm_core::{Column, ColumnType, ColumnTypeOptions, Table};
+
+    fn users_table(columns: Vec<Column>) -> Table {
+        let mut all = vec![
+            Column::new(
+                "id".to_string(),
+                ColumnType::Identifier(ColumnTypeOptions {
+                    primary_key: true,
+                    auto_increment: true,
+                    ..Default::default()
+                }),
+            ),
+            Column::new(
+                "username".to_string(),
+                ColumnType::Text(ColumnTypeOptions {
+                    not_null: true,
+                    ..Default::default()
+                }),
+            ),
+        ];
+        all.extend(columns);
+        Table {
+            name: "Users".to_string(),
+            columns: all.into(),
+        }
+    }
+
+    #[test]
+    fn test_destructive_without_force() {
+        let database = Database {
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            tables: vec![users_table(vec![])],
+        };
+        // `Users.email` and `Posts` exist after the migrations, but were removed
+        let errors = vec![
+            MigrationError::MissingColumn {
+                table: "Users".to_string(),
+                column: "email".to_string(),
+            },
+            MigrationError::MissingTable("Posts".to_string()),
+            MigrationError::MissingColumn {
+                table: "Users".to_string(),
+                column: "username".to_string(),
+            },
+        ];
+
+        let destructive = destructive_changes(&database, &errors);
+        assert_eq!(destructive.len(), 2);
+
+        // Non-interactive without `--force` aborts
+        assert!(confirm_destructive(&destructive, false, false).is_err());
+        assert!(confirm_destructive(&destructive, true, false).is_ok());
+        // Nothing destructive, nothing to confirm
+        assert!(confirm_destructive(&[], false, false).is_ok());
+    }
+
+    /// Write a two-version migration chain to a temporary directory
+    async fn migration_chain(name: &str) -> Result<(PathBuf, Vec<PathBuf>)> {
+        let root =
+            std::env::temp_dir().join(format!("geekorm-cli-{}-{}", name, std::process::id()));
+        let v1 = root.join("v0_1_0");
+        let v2 = root.join("v0_2_0");
+        tokio::fs::create_dir_all(&v1).await?;
+        tokio::fs::create_dir_all(&v2).await?;
+
+        tokio::fs::write(
+            v1.join("create.sql"),
+            "CREATE TABLE IF NOT EXISTS Users (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT NOT NULL);",
+        )
+        .await?;
+        tokio::fs::write(
+            v2.join("upgrade.sql"),
+            "ALTER TABLE Users ADD COLUMN email TEXT;",
+        )
+        .await?;
+
+        Ok((root, vec![v1, v2]))
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() -> Result<()> {
+        let (root, migrations) = migration_chain("round-trip").await?;
+        let db_path = root.join("test.db");
+
+        let database = geekorm::Database {
+            tables: vec![users_table(vec![Column::new(
+                "email".to_string(),
+                ColumnType::Text(ColumnTypeOptions::default()),
+            )])],
+        };
+        let connection = rusqlite::Connection::open(&db_path)?;
+        apply_migrations(&connection, &migrations).await?;
+        let validator = test_database(&connection, &database).await?;
+        assert!(validator.errors.is_empty(), "{:?}", validator.errors);
+
+        tokio::fs::remove_dir_all(&root).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_schema_mismatch() -> Result<()> {
+        let (root, migrations) = migration_chain("mismatch").await?;
+        let db_path = root.join("test.db");
+
+        // The latest definition has a column and a table no migration creates
+        let database = geekorm::Database {
+            tables: vec![
+                users_table(vec![
+                    Column::new(
+                        "email".to_string(),
+                        ColumnType::Text(ColumnTypeOptions::default()),
+                    ),
+                    Column::new(
+                        "age".to_string(),
+                        ColumnType::Integer(ColumnTypeOptions::default()),
+                    ),
+                ]),
+                Table {
+                    name: "Posts".to_string(),
+                    columns: vec![Column::new(
+                        "title".to_string(),
+                        ColumnType::Text(ColumnTypeOptions::default()),
+                    )]
+                    .into(),
+                },
+            ],
+        };
+        let connection = rusqlite::Connection::open(&db_path)?;
+        apply_migrations(&connection, &migrations).await?;
+        let validator = test_database(&connection, &database).await?;
+
+        assert!(validator.errors.iter().any(|e| matches!(
+            e,
+            MigrationError::MissingColumn { table, column } if table == "Users" && column == "age"
+        )));
+        assert!(validator
+            .errors
+            .iter()
+            .any(|e| matches!(e, MigrationError::NewTable { table } if table == "Posts")));
+
+        tokio::fs::remove_dir_all(&root).await?;
+        Ok(())
+    }
+}
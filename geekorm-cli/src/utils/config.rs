@@ -1,9 +1,21 @@
 //! # Utils Configuration
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use crate::utils::cargo::Cargo;
 
+/// Configuration for a named database
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct DatabaseConfig {
+    /// Crate/Module name for the migrations (defaults to the database name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) name: Option<String>,
+    /// Tables in the database
+    #[serde(default)]
+    pub(crate) tables: Vec<String>,
+}
+
 /// Configuration struct
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Config {
@@ -26,6 +38,12 @@ pub struct Config {
     /// Database Driver
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub(crate) drivers: Vec<String>,
+    /// Named databases, each with their own set of tables and migrations
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) databases: BTreeMap<String, DatabaseConfig>,
+    /// The named database selected (`--database`)
+    #[serde(skip)]
+    pub(crate) selected_database: Option<String>,
 
     /// Build command (if any)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -40,9 +58,17 @@ pub struct Config {
     #[serde(skip)]
     pub(crate) versions: Vec<String>,
 
+    /// Generate a `queries` module with typed query helpers for each table
+    #[serde(default)]
+    pub(crate) queries: bool,
+
     /// Data migrations
     #[serde(skip)]
     pub(crate) data_migrations: bool,
+
+    /// Allow destructive migrations (dropping tables or columns)
+    #[serde(skip)]
+    pub(crate) force_migrations: bool,
 }
 
 impl Config {
@@ -123,9 +149,38 @@ impl Config {
         self.mode == "module"
     }
     pub fn name(&self) -> String {
+        if let Some(selected) = &self.selected_database {
+            return self
+                .databases
+                .get(selected)
+                .and_then(|database| database.name.clone())
+                .unwrap_or_else(|| selected.clone());
+        }
         self.name.clone().unwrap_or("db".to_string())
     }
 
+    /// Select a named database, scoping the tables and migrations to it
+    pub async fn select_database(&mut self, name: &str) -> Result<()> {
+        if !self.databases.contains_key(name) {
+            let names: Vec<&str> = self.databases.keys().map(|n| n.as_str()).collect();
+            return Err(anyhow::anyhow!(
+                "Database `{}` is not configured (available: {})",
+                name,
+                names.join(", ")
+            ));
+        }
+        self.selected_database = Some(name.to_string());
+        // Each database has its own migrations directory (which might not exist yet)
+        self.versions = self.get_versions().await.unwrap_or_default();
+        log::debug!("Versions: {:?}", self.versions);
+        Ok(())
+    }
+
+    /// The configuration of the selected database (if any)
+    pub fn database_config(&self) -> Option<&DatabaseConfig> {
+        self.databases.get(self.selected_database.as_ref()?)
+    }
+
     pub fn version(&self) -> String {
         self.version.replace(".", "_")
     }
@@ -227,11 +282,15 @@ impl Default for Config {
             name: None,
             database: "".to_string(),
             drivers: Vec::new(),
+            databases: BTreeMap::new(),
+            selected_database: None,
             build: Vec::new(),
             geekorm: None,
             version: "0.1.0".to_string(),
             versions: Vec::new(),
+            queries: false,
             data_migrations: false,
+            force_migrations: false,
         }
     }
 }
@@ -31,7 +31,7 @@ impl Database {
         })?;
 
         // Find the latest database file based on the creation date
-        glob(path_str)?
+        let mut database = glob(path_str)?
             .filter_map(|entry| entry.ok())
             .fold(None, |acc, entry| {
                 let database = Self::load_database(entry).ok()?;
@@ -43,7 +43,28 @@ impl Database {
                     }
                 }))
             })
-            .ok_or_else(|| anyhow::anyhow!("Database not found"))
+            .ok_or_else(|| anyhow::anyhow!("Database not found"))?;
+
+        database.scope(config)?;
+        Ok(database)
+    }
+
+    /// Only keep the tables of the selected named database (if any)
+    pub fn scope(&mut self, config: &Config) -> Result<()> {
+        if let Some(dbconfig) = config.database_config() {
+            for name in dbconfig.tables.iter() {
+                if self.get_table(name).is_none() {
+                    return Err(anyhow::anyhow!(
+                        "Table `{}` in database `{}` was not found",
+                        name,
+                        config.name()
+                    ));
+                }
+            }
+            self.tables
+                .retain(|table| dbconfig.tables.contains(&table.name));
+        }
+        Ok(())
     }
 
     /// Load the database from the file
@@ -92,8 +113,7 @@ impl Database {
     }
 
     pub fn get_table_column(&self, table: &str, column: &str) -> Option<&Column> {
-        self.get_table(table)
-            .unwrap()
+        self.get_table(table)?
             .columns
             .columns
             .iter()
@@ -114,3 +134,59 @@ impl Database {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geekorm_core::{ColumnType, ColumnTypeOptions};
+
+    fn table(name: &str) -> BuilderTable {
+        BuilderTable {
+            name: name.to_string(),
+            columns: vec![Column::new(
+                "id".to_string(),
+                ColumnType::Identifier(ColumnTypeOptions {
+                    primary_key: true,
+                    ..Default::default()
+                }),
+            )]
+            .into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scope_named_database() -> Result<()> {
+        let mut config: Config = serde_yaml::from_str(
+            r#"
+mode: module
+database: sqlite
+databases:
+  main:
+    tables: [Users]
+  analytics:
+    name: analytics_db
+    tables: [Events]
+"#,
+        )?;
+        assert!(config.select_database("missing").await.is_err());
+        config.select_database("analytics").await?;
+        assert_eq!(config.name(), "analytics_db");
+
+        let mut database = Database {
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            tables: vec![table("Users"), table("Events")],
+        };
+        database.scope(&config)?;
+        assert_eq!(database.get_table_names(), vec!["Events"]);
+
+        // Only the selected database's tables end up in the migration
+        let path = std::env::temp_dir().join(format!("geekorm-create-{}.sql", std::process::id()));
+        crate::codegen::generate_create_sql(&database, &path).await?;
+        let sql = tokio::fs::read_to_string(&path).await?;
+        tokio::fs::remove_file(&path).await?;
+        assert!(sql.contains("CREATE TABLE IF NOT EXISTS Events"));
+        assert!(!sql.contains("Users"));
+        Ok(())
+    }
+}
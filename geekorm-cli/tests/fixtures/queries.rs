@@ -0,0 +1,71 @@
+//! GeekORM Typed Queries
+#![allow(dead_code)]
+use super::Database;
+/// Get a table from the latest database schema
+fn table(name: &str) -> Result<geekorm::Table, geekorm::Error> {
+    Database
+        .get_table(name)
+        .cloned()
+        .ok_or_else(|| geekorm::Error::SchemaError(format!("Table `{}` not found", name)))
+}
+///Find a row in `UserSessions` by the unique `id` column
+pub async fn user_sessions_find_by_id<C, T>(
+    connection: &C::Connection,
+    id: impl Into<geekorm::Value>,
+) -> Result<T, geekorm::Error>
+where
+    C: geekorm::GeekConnection,
+    T: serde::de::DeserializeOwned,
+{
+    let query = geekorm::QueryBuilder::select()
+        .table(table("UserSessions")?)
+        .where_eq("id", id)
+        .limit(1)
+        .build()?;
+    C::query_first::<T>(connection, query).await
+}
+///Find a row in `UserSessions` by the unique `token` column
+pub async fn user_sessions_find_by_token<C, T>(
+    connection: &C::Connection,
+    token: impl Into<geekorm::Value>,
+) -> Result<T, geekorm::Error>
+where
+    C: geekorm::GeekConnection,
+    T: serde::de::DeserializeOwned,
+{
+    let query = geekorm::QueryBuilder::select()
+        .table(table("UserSessions")?)
+        .where_eq("token", token)
+        .limit(1)
+        .build()?;
+    C::query_first::<T>(connection, query).await
+}
+///List the rows in `UserSessions` ordered by `id` (`limit` rows starting at `offset`)
+pub async fn user_sessions_list<C, T>(
+    connection: &C::Connection,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<T>, geekorm::Error>
+where
+    C: geekorm::GeekConnection,
+    T: serde::de::DeserializeOwned,
+{
+    let query = geekorm::QueryBuilder::select()
+        .table(table("UserSessions")?)
+        .order_by("id", geekorm::QueryOrder::Asc)
+        .limit(limit)
+        .offset(offset)
+        .build()?;
+    C::query::<T>(connection, query).await
+}
+///Count the rows in `UserSessions`
+pub async fn user_sessions_count<C>(connection: &C::Connection) -> Result<i64, geekorm::Error>
+where
+    C: geekorm::GeekConnection,
+{
+    let query = geekorm::QueryBuilder::select()
+        .table(table("UserSessions")?)
+        .count()
+        .build()?;
+    C::row_count(connection, query).await
+}
@@ -0,0 +1,61 @@
+//! Compile and run the typed queries module generated by `geekorm-cli`
+//!
+//! `fixtures/queries.rs` is the output of `generate_queries` for the `UserSessions`
+//! table (kept in sync by the `test_generate_queries` unit test).
+use geekorm::prelude::*;
+
+#[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct UserSessions {
+    #[geekorm(primary_key, auto_increment)]
+    pub id: PrimaryKeyInteger,
+    #[geekorm(unique)]
+    pub token: String,
+    pub name: Option<String>,
+}
+
+geekorm::lazy_static! {
+    pub static ref Database: Box<geekorm::Database> = Box::new(
+        geekorm::Database { tables: Vec::from([UserSessions::table()]) }
+    );
+}
+
+#[path = "fixtures/queries.rs"]
+mod queries;
+
+#[tokio::test]
+async fn test_generated_queries() -> Result<(), geekorm::Error> {
+    let connection = rusqlite::Connection::open_in_memory()
+        .map_err(|error| geekorm::Error::ConnectionError(error.to_string()))?;
+    UserSessions::create_table(&connection).await?;
+
+    for token in ["abc", "def", "ghi"] {
+        UserSessions::new(token).save(&connection).await?;
+    }
+
+    let session: UserSessions =
+        queries::user_sessions_find_by_token::<rusqlite::Connection, _>(&connection, "def").await?;
+    assert_eq!(session.token, "def");
+
+    let session: UserSessions =
+        queries::user_sessions_find_by_id::<rusqlite::Connection, _>(&connection, 1).await?;
+    assert_eq!(session.token, "abc");
+
+    let sessions: Vec<UserSessions> =
+        queries::user_sessions_list::<rusqlite::Connection, _>(&connection, 2, 1).await?;
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0].token, "def");
+    assert_eq!(sessions[1].token, "ghi");
+
+    assert_eq!(
+        queries::user_sessions_count::<rusqlite::Connection>(&connection).await?,
+        3
+    );
+
+    // Wrapped connections can be used as the backend
+    let connection = ReadOnly::new(connection);
+    let sessions: Vec<UserSessions> =
+        queries::user_sessions_list::<ReadOnly<rusqlite::Connection>, _>(&connection, 10, 0)
+            .await?;
+    assert_eq!(sessions.len(), 3);
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Benchmarks for building select queries
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use geekorm_core::builder::models::QueryOrder;
+use geekorm_core::{Column, ColumnType, ColumnTypeOptions, QueryBuilder, Table};
+
+fn table() -> Table {
+    Table {
+        name: String::from("Users"),
+        columns: vec![
+            Column::new(
+                String::from("id"),
+                ColumnType::Identifier(ColumnTypeOptions {
+                    primary_key: true,
+                    auto_increment: true,
+                    ..Default::default()
+                }),
+            ),
+            Column::new(
+                String::from("username"),
+                ColumnType::Text(ColumnTypeOptions::default()),
+            ),
+            Column::new(
+                String::from("email"),
+                ColumnType::Text(ColumnTypeOptions::default()),
+            ),
+            Column::new(
+                String::from("age"),
+                ColumnType::Integer(ColumnTypeOptions::default()),
+            ),
+            Column::new(
+                String::from("order"),
+                ColumnType::Integer(ColumnTypeOptions::default()),
+            ),
+        ]
+        .into(),
+    }
+}
+
+fn bench_select(c: &mut Criterion) {
+    let table = table();
+
+    c.bench_function("select_all", |b| {
+        b.iter(|| {
+            QueryBuilder::select()
+                .table(black_box(table.clone()))
+                .build()
+                .unwrap()
+        })
+    });
+
+    c.bench_function("select_where_order_limit", |b| {
+        b.iter(|| {
+            QueryBuilder::select()
+                .table(black_box(table.clone()))
+                .where_eq("username", "geekmasher")
+                .or()
+                .where_gt("age", 18)
+                .order_by("order", QueryOrder::Desc)
+                .order_by("id", QueryOrder::Asc)
+                .limit(10)
+                .offset(20)
+                .build()
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_select);
+criterion_main!(benches);
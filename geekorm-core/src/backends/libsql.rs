@@ -43,7 +43,8 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::collections::HashMap;
 
 use crate::{
-    builder::models::QueryType, GeekConnection, QueryBuilderTrait, TableBuilder, Value, Values,
+    builder::models::QueryType, GeekConnection, GeekQueryMap, QueryBuilderTrait, TableBuilder,
+    Value, Values,
 };
 
 #[cfg(feature = "backends-tokio")]
@@ -56,6 +57,8 @@ impl GeekConnection for libsql::Connection {
     where
         T: TableBuilder + QueryBuilderTrait + Sized + Serialize + DeserializeOwned,
     {
+        T::table().try_primary_key()?;
+
         let query = T::query_create().build()?;
         #[cfg(feature = "log")]
         {
@@ -64,9 +67,22 @@ impl GeekConnection for libsql::Connection {
         connection.execute(query.to_str(), ()).await.map_err(|e| {
             crate::Error::QuerySyntaxError {
                 error: e.to_string(),
-                query: query.to_string(),
+                query: query.query.clone(),
             }
         })?;
+
+        for index in T::table().on_create_indexes() {
+            #[cfg(feature = "log")]
+            {
+                debug!("Create Index Query :: {:?}", index);
+            }
+            connection.execute(index.as_str(), ()).await.map_err(|e| {
+                crate::Error::QuerySyntaxError {
+                    error: e.to_string(),
+                    query: index.clone(),
+                }
+            })?;
+        }
         Ok(())
     }
 
@@ -81,25 +97,18 @@ impl GeekConnection for libsql::Connection {
         let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
             crate::Error::QuerySyntaxError {
                 error: e.to_string(),
-                query: query.to_string(),
+                query: query.query.clone(),
             }
         })?;
 
-        let parameters: Vec<libsql::Value> = convert_values(&query)?;
+        let parameters = convert_values(&query)?;
 
-        let mut rows =
-            statement
-                .query(parameters)
-                .await
-                .map_err(|e| crate::Error::LibSQLError {
-                    error: e.to_string(),
-                    query: query.to_string(),
-                })?;
+        let mut rows = statement
+            .query(parameters)
+            .await
+            .map_err(|e| query_error(e, &query))?;
 
-        let row = match rows.next().await.map_err(|e| crate::Error::LibSQLError {
-            error: e.to_string(),
-            query: query.to_string(),
-        })? {
+        let row = match rows.next().await.map_err(|e| query_error(e, &query))? {
             Some(row) => row,
             None => {
                 #[cfg(feature = "log")]
@@ -108,15 +117,99 @@ impl GeekConnection for libsql::Connection {
                 }
                 return Err(crate::Error::LibSQLError {
                     error: "Error fetching row count".to_string(),
-                    query: query.to_string(),
+                    query: query.query.clone(),
                 });
             }
         };
         // Get the first row
-        Ok(row.get(0).map_err(|e| crate::Error::LibSQLError {
-            error: e.to_string(),
-            query: query.to_string(),
-        })?)
+        Ok(row.get(0).map_err(|e| query_error(e, &query))?)
+    }
+
+    async fn query_scalar<V>(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> Result<Option<V>, crate::Error>
+    where
+        V: TryFrom<Value>,
+        V::Error: std::fmt::Display,
+    {
+        #[cfg(feature = "log")]
+        {
+            debug!("Query Scalar :: {:?}", query.to_str());
+        }
+        let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
+            crate::Error::QuerySyntaxError {
+                error: e.to_string(),
+                query: query.query.clone(),
+            }
+        })?;
+
+        let parameters = convert_values(&query)?;
+
+        let mut rows = statement
+            .query(parameters)
+            .await
+            .map_err(|e| query_error(e, &query))?;
+
+        let row = match rows.next().await.map_err(|e| query_error(e, &query))? {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let value = match row.get_value(0).map_err(|e| query_error(e, &query))? {
+            libsql::Value::Null => return Ok(None),
+            libsql::Value::Real(value) => Value::Text(value.to_string()),
+            value => Value::from(value),
+        };
+        V::try_from(value)
+            .map(Some)
+            .map_err(|e| crate::Error::SerdeError(e.to_string()))
+    }
+
+    async fn query_tuple<T>(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        #[cfg(feature = "log")]
+        {
+            debug!("Query Tuple :: {:?}", query.to_str());
+        }
+        let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
+            crate::Error::QuerySyntaxError {
+                error: e.to_string(),
+                query: query.query.clone(),
+            }
+        })?;
+
+        let parameters = convert_values(&query)?;
+
+        let mut rows = statement
+            .query(parameters)
+            .await
+            .map_err(|e| query_error(e, &query))?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| query_error(e, &query))? {
+            // `de::from_row` is name based, so build the row positionally
+            let mut columns = Vec::new();
+            for index in 0..row.column_count() {
+                let value = row.get_value(index).map_err(|e| query_error(e, &query))?;
+                columns.push(match value {
+                    libsql::Value::Null => serde_json::Value::Null,
+                    libsql::Value::Integer(value) => serde_json::Value::from(value),
+                    libsql::Value::Real(value) => serde_json::Value::from(value),
+                    libsql::Value::Text(value) => serde_json::Value::from(value),
+                    libsql::Value::Blob(value) => serde_json::Value::from(value),
+                });
+            }
+            results.push(
+                serde_json::from_value::<T>(serde_json::Value::Array(columns))
+                    .map_err(|e| crate::Error::SerdeError(e.to_string()))?,
+            );
+        }
+        Ok(results)
     }
 
     async fn query<T>(
@@ -134,11 +227,11 @@ impl GeekConnection for libsql::Connection {
         let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
             crate::Error::QuerySyntaxError {
                 error: e.to_string(),
-                query: query.to_string(),
+                query: query.query.clone(),
             }
         })?;
 
-        let parameters: Vec<libsql::Value> = convert_values(&query)?;
+        let parameters = convert_values(&query)?;
 
         #[cfg(feature = "log")]
         {
@@ -146,21 +239,14 @@ impl GeekConnection for libsql::Connection {
         }
 
         // Execute the query
-        let mut rows =
-            statement
-                .query(parameters)
-                .await
-                .map_err(|e| crate::Error::LibSQLError {
-                    error: e.to_string(),
-                    query: query.to_string(),
-                })?;
+        let mut rows = statement
+            .query(parameters)
+            .await
+            .map_err(|e| query_error(e, &query))?;
 
         let mut results = Vec::new();
 
-        while let Some(row) = rows.next().await.map_err(|e| crate::Error::LibSQLError {
-            error: e.to_string(),
-            query: query.to_string(),
-        })? {
+        while let Some(row) = rows.next().await.map_err(|e| query_error(e, &query))? {
             results.push(de::from_row::<T>(&row).map_err(|e| {
                 #[cfg(feature = "log")]
                 {
@@ -181,7 +267,7 @@ impl GeekConnection for libsql::Connection {
         T: serde::de::DeserializeOwned,
     {
         // TODO: Should we always make sure the query limit is set to 1?
-        if query.query_type == QueryType::Update {
+        if query.query_type == QueryType::Update && !query.returns_rows() {
             #[cfg(feature = "log")]
             {
                 error!(
@@ -190,19 +276,19 @@ impl GeekConnection for libsql::Connection {
             }
             return Err(crate::Error::LibSQLError {
                 error: "Query type is an `update`".to_string(),
-                query: query.to_string(),
+                query: query.query.clone(),
             });
         }
 
         let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
             crate::Error::QuerySyntaxError {
                 error: e.to_string(),
-                query: query.to_string(),
+                query: query.query.clone(),
             }
         })?;
 
         // Convert the values to libsql::Value
-        let parameters: Vec<libsql::Value> = convert_values(&query)?;
+        let parameters = convert_values(&query)?;
 
         #[cfg(feature = "log")]
         {
@@ -210,17 +296,13 @@ impl GeekConnection for libsql::Connection {
             debug!("Parameters :: {:?}", parameters.clone());
         }
 
-        // Execute the query
-        let mut rows =
-            statement
-                .query(parameters)
-                .await
-                .map_err(|e| crate::Error::LibSQLError {
-                    error: e.to_string(),
-                    query: query.to_string(),
-                })?;
+        // Execute the query (writes with `RETURNING` can violate constraints)
+        let mut rows = statement
+            .query(parameters)
+            .await
+            .map_err(|e| query_error(e, &query))?;
 
-        let row: libsql::Row = match rows.next().await? {
+        let row: libsql::Row = match rows.next().await.map_err(|e| query_error(e, &query))? {
             Some(row) => row,
             None => {
                 #[cfg(feature = "log")]
@@ -228,7 +310,7 @@ impl GeekConnection for libsql::Connection {
                     error!("No rows found for query: `{}`", query.to_str());
                 }
                 return Err(crate::Error::NoRowsFound {
-                    query: query.to_string(),
+                    query: query.query.clone(),
                 });
             }
         };
@@ -246,26 +328,40 @@ impl GeekConnection for libsql::Connection {
         connection: &Self::Connection,
         query: crate::Query,
     ) -> Result<(), crate::Error> {
+        Self::execute_affected(connection, query).await?;
+        Ok(())
+    }
+
+    async fn execute_affected(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> Result<u64, crate::Error> {
+        super::ensure_not_select(&query)?;
         // Convert the values to libsql::Value
-        let parameters: Vec<libsql::Value> = convert_values(&query)?;
+        let parameters = convert_values(&query)?;
 
         connection
             .execute(query.to_str(), parameters)
             .await
-            .map_err(|e| crate::Error::QuerySyntaxError {
-                error: e.to_string(),
-                query: query.to_string(),
-            })?;
-        Ok(())
+            .map_err(|e| {
+                let error = e.to_string();
+                crate::Error::unique_violation(&error).unwrap_or(crate::Error::QuerySyntaxError {
+                    error,
+                    query: query.query.clone(),
+                })
+            })
     }
 
     async fn batch(connection: &Self::Connection, query: crate::Query) -> Result<(), crate::Error> {
         connection
             .execute_batch(query.to_str())
             .await
-            .map_err(|e| crate::Error::QuerySyntaxError {
-                error: e.to_string(),
-                query: query.to_string(),
+            .map_err(|e| {
+                let error = e.to_string();
+                crate::Error::unique_violation(&error).unwrap_or(crate::Error::QuerySyntaxError {
+                    error,
+                    query: query.query.clone(),
+                })
             })?;
         Ok(())
     }
@@ -279,7 +375,7 @@ impl GeekConnection for libsql::Connection {
         let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
             crate::Error::QuerySyntaxError {
                 error: e.to_string(),
-                query: query.to_string(),
+                query: query.query.clone(),
             }
         })?;
 
@@ -292,14 +388,11 @@ impl GeekConnection for libsql::Connection {
         let mut rows = statement
             .query(params)
             .await
-            .map_err(|e| crate::Error::LibSQLError {
-                error: e.to_string(),
-                query: query.to_string(),
-            })?;
+            .map_err(|e| query_error(e, &query))?;
 
         let mut results: Vec<HashMap<String, Value>> = Vec::new();
 
-        while let Some(row) = rows.next().await? {
+        while let Some(row) = rows.next().await.map_err(|e| query_error(e, &query))? {
             let mut values: HashMap<String, Value> = HashMap::new();
 
             for (index, column_name) in query.columns.iter().enumerate() {
@@ -313,7 +406,51 @@ impl GeekConnection for libsql::Connection {
     }
 }
 
-fn convert_values(query: &crate::Query) -> Result<Vec<libsql::Value>, crate::Error> {
+impl GeekQueryMap for libsql::Connection {
+    type Row<'r> = libsql::Row;
+
+    async fn query_map<T, F>(
+        connection: &Self::Connection,
+        query: crate::Query,
+        f: F,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        F: for<'r> Fn(&Self::Row<'r>) -> Result<T, crate::Error>,
+    {
+        #[cfg(feature = "log")]
+        {
+            debug!("Query Map :: {:?}", query.to_str());
+        }
+        let mut statement = connection.prepare(query.to_str()).await.map_err(|e| {
+            crate::Error::QuerySyntaxError {
+                error: e.to_string(),
+                query: query.query.clone(),
+            }
+        })?;
+
+        let parameters = convert_values(&query)?;
+
+        let mut rows = statement
+            .query(parameters)
+            .await
+            .map_err(|e| query_error(e, &query))?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().await.map_err(|e| query_error(e, &query))? {
+            results.push(f(&row)?);
+        }
+        Ok(results)
+    }
+}
+
+/// Convert the query values to libsql parameters
+///
+/// Queries using named placeholders (`:name`, `@name` or `$name`) are bound by
+/// name, everything else is bound by position.
+fn convert_values(query: &crate::Query) -> Result<libsql::params::Params, crate::Error> {
+    if query.is_named() {
+        return convert_named_values(query);
+    }
     let mut parameters: Vec<libsql::Value> = Vec::new();
 
     // TODO(geekmasher): This is awful, need to refactor this
@@ -345,11 +482,49 @@ fn convert_values(query: &crate::Query) -> Result<Vec<libsql::Value>, crate::Err
                 .into_value()
                 .map_err(|e| crate::Error::LibSQLError {
                     error: format!("Error converting value - {}", e),
-                    query: query.to_string(),
+                    query: query.query.clone(),
                 })?,
         );
     }
-    Ok(parameters)
+    Ok(libsql::params::Params::Positional(parameters))
+}
+
+fn convert_named_values(query: &crate::Query) -> Result<libsql::params::Params, crate::Error> {
+    let values: &Values = match query.query_type {
+        QueryType::Insert | QueryType::Update => &query.parameters,
+        _ => &query.values,
+    };
+
+    let mut parameters: Vec<(String, libsql::Value)> = Vec::new();
+    for (name, value) in &values.values {
+        #[cfg(feature = "log")]
+        {
+            log::trace!("LIBSQL - Named('{}', '{}')", name, value);
+        }
+
+        parameters.push((
+            name.clone(),
+            value
+                .clone()
+                .into_value()
+                .map_err(|e| crate::Error::LibSQLError {
+                    error: format!("Error converting value - {}", e),
+                    query: query.query.clone(),
+                })?,
+        ));
+    }
+    Ok(libsql::params::Params::Named(parameters))
+}
+
+/// Convert a LibSQL error of a query to a GeekORM error
+///
+/// Unique constraint failures are reported as `Error::UniqueViolation`.
+fn query_error(error: libsql::Error, query: &crate::Query) -> crate::Error {
+    let error = error.to_string();
+    crate::Error::unique_violation(&error).unwrap_or(crate::Error::LibSQLError {
+        error,
+        query: query.query.clone(),
+    })
 }
 
 /// Convert LibSQL Error to GeekORM Error
@@ -6,7 +6,7 @@ use std::sync::Mutex;
 #[cfg(feature = "backends-tokio")]
 use tokio::sync::Mutex;
 
-use crate::{GeekConnection, QueryBuilderTrait, TableBuilder};
+use crate::{GeekConnection, GeekQueryMap, QueryBuilderTrait, TableBuilder};
 
 const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
 
@@ -118,4 +118,134 @@ where
             query: "N/A".to_string(),
         })
     }
+
+    async fn execute_affected(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> Result<u64, crate::Error> {
+        let start = std::time::Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match connection.try_lock() {
+                Ok(conn) => return C::execute_affected(&conn, query).await,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        Err(crate::Error::LibSQLError {
+            error: "Error getting write lock on connection in execute_affected".to_string(),
+            query: "N/A".to_string(),
+        })
+    }
+
+    async fn query_scalar<V>(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> Result<Option<V>, crate::Error>
+    where
+        V: TryFrom<crate::Value>,
+        V::Error: std::fmt::Display,
+    {
+        let start = std::time::Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match connection.try_lock() {
+                Ok(conn) => return C::query_scalar(&conn, query).await,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        Err(crate::Error::LibSQLError {
+            error: "Error getting lock on connection in query_scalar".to_string(),
+            query: "N/A".to_string(),
+        })
+    }
+
+    async fn query_tuple<T>(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let start = std::time::Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match connection.try_lock() {
+                Ok(conn) => return C::query_tuple(&conn, query).await,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        Err(crate::Error::LibSQLError {
+            error: "Error getting lock on connection in query_tuple".to_string(),
+            query: "N/A".to_string(),
+        })
+    }
+
+    async fn batch(connection: &Self::Connection, query: crate::Query) -> Result<(), crate::Error> {
+        let start = std::time::Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match connection.try_lock() {
+                Ok(conn) => return C::batch(&conn, query).await,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        Err(crate::Error::LibSQLError {
+            error: "Error getting write lock on connection in batch".to_string(),
+            query: "N/A".to_string(),
+        })
+    }
+
+    async fn execute_all(
+        connection: &Self::Connection,
+        queries: Vec<crate::Query>,
+    ) -> Result<(), crate::Error> {
+        // Hold the lock for the whole transaction so no other statements are interleaved
+        let start = std::time::Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match connection.try_lock() {
+                Ok(conn) => return C::execute_all(&conn, queries).await,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        Err(crate::Error::LibSQLError {
+            error: "Error getting write lock on connection in execute_all".to_string(),
+            query: "N/A".to_string(),
+        })
+    }
+}
+
+impl<C> GeekQueryMap for Arc<Mutex<C>>
+where
+    C: GeekQueryMap<Connection = libsql::Connection>,
+{
+    type Row<'r> = C::Row<'r>;
+
+    async fn query_map<T, F>(
+        connection: &Self::Connection,
+        query: crate::Query,
+        f: F,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        F: for<'r> Fn(&Self::Row<'r>) -> Result<T, crate::Error>,
+    {
+        let start = std::time::Instant::now();
+        while start.elapsed() < TIMEOUT {
+            match connection.try_lock() {
+                Ok(conn) => return C::query_map(&conn, query, f).await,
+                Err(_) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+            }
+        }
+        Err(crate::Error::LibSQLError {
+            error: "Error getting lock on connection in query_map".to_string(),
+            query: "N/A".to_string(),
+        })
+    }
 }
@@ -63,13 +63,32 @@
 
 use std::collections::HashMap;
 
+#[cfg(feature = "stream")]
+use futures::{Stream, StreamExt};
+
+use crate::builder::models::QueryType;
+#[cfg(feature = "migrations")]
+use crate::builder::quote_identifier;
 use crate::{Query, QueryBuilder, QueryBuilderTrait, TableBuilder, TablePrimaryKey, Value};
 
 #[cfg(feature = "libsql")]
 pub mod libsql;
+pub mod readonly;
 #[cfg(feature = "rusqlite")]
 pub mod rusqlite;
 
+/// The pragmas applied to a connection by [`GeekConnection::configure`]
+///
+/// - `foreign_keys = ON` enforces foreign key constraints (off by default in SQLite)
+/// - `journal_mode = WAL` lets readers run alongside a writer (in-memory
+///   databases keep the `memory` journal mode)
+/// - `synchronous = NORMAL` is safe in WAL mode and avoids a sync on every commit
+pub const DEFAULT_PRAGMAS: &[(&str, &str)] = &[
+    ("foreign_keys", "ON"),
+    ("journal_mode", "WAL"),
+    ("synchronous", "NORMAL"),
+];
+
 /// GeekConnection is the trait used for models to interact with the database.
 ///
 /// This trait is used to define the methods that are used to interact with the database.
@@ -118,7 +137,96 @@ where
         .await
     }
 
+    /// Count the rows in the table grouped by the value of a column
+    ///
+    /// Returns each distinct value of the column with the number of rows
+    /// that have that value, ordered by the value.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Tasks {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub status: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Tasks::create_table(&connection).await?;
+    ///
+    ///     for status in ["open", "closed", "open", "pending", "open", "closed"] {
+    ///         Tasks::new(status).save(&connection).await?;
+    ///     }
+    ///
+    ///     let groups = Tasks::count_by_group(&connection, "status").await?;
+    ///     # assert_eq!(
+    ///     #     groups,
+    ///     #     vec![
+    ///     #         (Value::from("closed"), 2),
+    ///     #         (Value::from("open"), 3),
+    ///     #         (Value::from("pending"), 1),
+    ///     #     ]
+    ///     # );
+    ///     # assert!(Tasks::count_by_group(&connection, "unknown").await.is_err());
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn count_by_group(
+        connection: &'a C,
+        group_column: &str,
+    ) -> Result<Vec<(Value, i64)>, crate::Error> {
+        #[derive(serde::Deserialize)]
+        struct GroupCount {
+            value: Value,
+            count: i64,
+        }
+
+        let table = Self::table();
+        let column = match table.columns.get(group_column) {
+            Some(column) => column,
+            None => {
+                return Err(crate::Error::QueryBuilderError(
+                    format!(
+                        "Column `{}` does not exist in table `{}`",
+                        group_column, table.name
+                    ),
+                    String::from("count_by_group"),
+                ))
+            }
+        };
+        let column_name = if column.alias.is_empty() {
+            &column.name
+        } else {
+            &column.alias
+        };
+
+        let query = Query::raw(
+            QueryType::Select,
+            format!(
+                "SELECT {column} AS value, COUNT(1) AS count FROM {table} GROUP BY {column} ORDER BY {column};",
+                column = crate::builder::quote_identifier(column_name),
+                table = crate::builder::quote_identifier(&table.name)
+            ),
+        );
+
+        Ok(C::query::<GroupCount>(connection, query)
+            .await?
+            .into_iter()
+            .map(|group| (group.value, group.count))
+            .collect())
+    }
+
     /// Fetch all rows from the table
+    ///
+    /// **Note:** This query is unbounded and unordered, every row in the table is
+    /// loaded into memory in the order SQLite returns them. For large tables use
+    /// `page` / `paginate`, and use `all_ordered` if the order matters.
     #[allow(async_fn_in_trait, unused_variables)]
     async fn all(connection: &'a C) -> Result<Vec<Self>, crate::Error> {
         C::query::<Self>(
@@ -128,6 +236,53 @@ where
         .await
     }
 
+    /// Fetch all rows from the table ordered by a column
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     for name in ["mallory", "alice", "trent"] {
+    ///         Users::new(name).save(&connection).await?;
+    ///     }
+    ///
+    ///     let users = Users::all_ordered(&connection, "name", QueryOrder::Asc).await?;
+    ///     # let names: Vec<&str> = users.iter().map(|u| u.name.as_str()).collect();
+    ///     # assert_eq!(names, vec!["alice", "mallory", "trent"]);
+    ///
+    ///     // Unknown columns are an error
+    ///     # assert!(Users::all_ordered(&connection, "age", QueryOrder::Asc).await.is_err());
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn all_ordered(
+        connection: &'a C,
+        column: &str,
+        order: crate::builder::models::QueryOrder,
+    ) -> Result<Vec<Self>, crate::Error> {
+        C::query::<Self>(
+            connection,
+            Self::query_select()
+                .table(Self::table())
+                .order_by(column, order)
+                .build()?,
+        )
+        .await
+    }
+
     /// Fetch by Page
     #[cfg(feature = "pagination")]
     #[allow(async_fn_in_trait, unused_variables)]
@@ -142,6 +297,61 @@ where
         .await
     }
 
+    /// Fetch a page of rows together with the total number of rows in the table
+    ///
+    /// Pages start at `0` and the page size is capped at the default page limit.
+    /// Both queries run inside a savepoint so the rows and the total are read
+    /// from the same snapshot of the table.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     for name in ["alice", "bob", "charlie", "dave", "eve"] {
+    ///         Users::new(name).save(&connection).await?;
+    ///     }
+    ///
+    ///     let (users, total) = Users::fetch_page_with_total(&connection, 0, 2).await?;
+    ///     # assert_eq!(users.len(), 2);
+    ///     # assert_eq!(total, 5);
+    ///     # assert_eq!(users[0].username, "alice");
+    ///
+    ///     // The last page only has the remaining rows
+    ///     let (users, total) = Users::fetch_page_with_total(&connection, 2, 2).await?;
+    ///     # assert_eq!(users.len(), 1);
+    ///     # assert_eq!(total, 5);
+    ///     # assert_eq!(users[0].username, "eve");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "pagination")]
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn fetch_page_with_total(
+        connection: &'a C,
+        page: u32,
+        page_size: u32,
+    ) -> Result<(Vec<Self>, i64), crate::Error> {
+        let page = crate::Page::from((page, page_size));
+        C::with_savepoint(connection, "geekorm_page", || async {
+            let rows = Self::page(connection, &page).await?;
+            let total = Self::total(connection).await?;
+            Ok((rows, total))
+        })
+        .await
+    }
+
     /// Create a new Pagination instance with the current table and fetch
     /// total number of rows
     #[cfg(feature = "pagination")]
@@ -153,22 +363,739 @@ where
     }
 
     /// Update the current object in the database
+    ///
+    /// If the table has a `#[geekorm(version)]` column, the version is incremented
+    /// and the update only applies if the row still has the version that was loaded.
+    /// A stale update returns `Error::StaleUpdate` and leaves the row (and the
+    /// `on_update` fields of the current object) untouched.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     #[geekorm(on_update = "self.edits + 1")]
+    ///     pub edits: i64,
+    ///     #[geekorm(version, rename = "row_version")]
+    ///     pub version: i64,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher", 0);
+    ///     user.save(&connection).await?;
+    ///
+    ///     // Two copies of the same row
+    ///     let mut first = Users::fetch_by_primary_key(&connection, user.id).await?;
+    ///     let mut stale = first.clone();
+    ///
+    ///     first.username = String::from("fresh");
+    ///     first.update(&connection).await?;
+    ///     # assert_eq!(first.version, 1);
+    ///
+    ///     // The row changed underneath, so this update is rejected
+    ///     stale.username = String::from("stale");
+    ///     let result = stale.update(&connection).await;
+    ///     # assert!(matches!(result, Err(geekorm::Error::StaleUpdate { version: 0, .. })));
+    ///     // The version and `on_update` fields are left as they were
+    ///     # assert_eq!(stale.version, 0);
+    ///     # assert_eq!(stale.edits, 0);
+    ///
+    ///     // Touching the row also bumps the version
+    ///     first.touch(&connection).await?;
+    ///     # assert_eq!(first.version, 2);
+    ///     # assert!(matches!(stale.touch(&connection).await, Err(geekorm::Error::StaleUpdate { .. })));
+    ///
+    ///     let user = Users::fetch_by_primary_key(&connection, user.id).await?;
+    ///     # assert_eq!(user.username, "fresh");
+    ///     # assert_eq!(user.edits, 2);
+    ///     # assert_eq!(user.version, 2);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
     #[allow(async_fn_in_trait, unused_variables)]
     async fn update(&mut self, connection: &'a C) -> Result<(), crate::Error> {
         C::execute(connection, Self::query_update(self)).await
     }
 
+    /// Update only the `on_update` columns (e.g. `updated_at`) of the current object
+    ///
+    /// The `on_update` expressions are run and only those columns are written
+    /// (by primary key), so other fields in the row are never overwritten.
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "rusqlite", feature = "chrono"))] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     #[geekorm(new = "chrono::Utc::now()", on_update = "chrono::Utc::now()")]
+    ///     pub last_seen: chrono::DateTime<chrono::Utc>,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///     let first_seen = user.last_seen;
+    ///
+    ///     // Only `last_seen` is written, the username change is not saved
+    ///     user.username = String::from("changed");
+    ///     user.touch(&connection).await?;
+    ///
+    ///     let user = Users::fetch_by_primary_key(&connection, user.id).await?;
+    ///     # assert_eq!(user.username, "geekmasher");
+    ///     # assert!(user.last_seen > first_seen);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// The generated query only sets the `on_update` columns (mock connection):
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    /// use std::sync::Mutex;
+    ///
+    /// #[derive(Default)]
+    /// struct Connection(Mutex<Vec<String>>);
+    ///
+    /// impl GeekConnection for Connection {
+    ///     type Connection = Self;
+    ///
+    ///     async fn execute(connection: &Self, query: Query) -> Result<(), geekorm::Error> {
+    ///         connection.0.lock().unwrap().push(query.query);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     #[geekorm(on_update = "self.updated_at + 1")]
+    ///     pub updated_at: i32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = Connection::default();
+    ///
+    ///     let mut user = Users::new("geekmasher", 0);
+    ///     user.id = PrimaryKeyInteger::from(1);
+    ///     user.touch(&connection).await?;
+    ///     # assert_eq!(user.updated_at, 1);
+    ///
+    ///     let queries = connection.0.lock().unwrap();
+    ///     # assert_eq!(queries.len(), 1);
+    ///     # assert_eq!(queries[0], "UPDATE Users SET updated_at = 1 WHERE id = 1;");
+    ///     Ok(())
+    /// }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn touch(&mut self, connection: &'a C) -> Result<(), crate::Error> {
+        Err(crate::Error::NotImplemented)
+    }
+
+    /// Update the current object in the database and return the number of rows affected
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn update_affected(&mut self, connection: &'a C) -> Result<u64, crate::Error> {
+        C::execute_affected(connection, Self::query_update(self)).await
+    }
+
     /// Save the current object to the database
+    ///
+    /// Fields with an `on_save` attribute are set before the row is inserted
+    /// and the `before_save` table hook (if any) is awaited first. Both can fail
+    /// using `?`, which stops the save before anything is written.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// #[geekorm(before_save = "check_title")]
+    /// pub struct Posts {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub title: String,
+    ///     #[geekorm(on_save = "self.slug = slugify(&self.title)?")]
+    ///     pub slug: String,
+    /// }
+    ///
+    /// fn slugify(title: &str) -> Result<String, geekorm::Error> {
+    ///     if title.trim().is_empty() {
+    ///         return Err(geekorm::Error::ValidationError(String::from("Empty title")));
+    ///     }
+    ///     Ok(title.to_lowercase().replace(' ', "-"))
+    /// }
+    ///
+    /// async fn check_title<'a, C>(post: &mut Posts, connection: &'a C) -> Result<(), geekorm::Error>
+    /// where
+    ///     C: GeekConnection<Connection = C> + 'a,
+    /// {
+    ///     // Hooks can query the database before the row is written
+    ///     if Posts::total(connection).await? >= 2 {
+    ///         return Err(geekorm::Error::ValidationError(String::from("Too many posts")));
+    ///     }
+    ///     post.title = post.title.trim().to_string();
+    ///     Ok(())
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Posts::create_table(&connection).await?;
+    ///
+    ///     let mut post = Posts::new("  Hello World ", "");
+    ///     post.save(&connection).await?;
+    ///     # assert_eq!(post.title, "Hello World");
+    ///     # assert_eq!(post.slug, "hello-world");
+    ///
+    ///     // Fallible `on_save` expressions stop the save
+    ///     let mut post = Posts::new("   ", "");
+    ///     # assert!(post.save(&connection).await.is_err());
+    ///     # assert_eq!(Posts::total(&connection).await?, 1);
+    ///
+    ///     // The async hook stops the save
+    ///     Posts::new("Second", "").save(&connection).await?;
+    ///     let mut post = Posts::new("Third", "");
+    ///     # assert!(post.save(&connection).await.is_err());
+    ///     # assert_eq!(Posts::total(&connection).await?, 2);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Inserting a duplicate value into a unique column returns `Error::UniqueViolation`:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     Users::new("geekmasher").save(&connection).await?;
+    ///
+    ///     let result = Users::new("geekmasher").save(&connection).await;
+    ///     if let Err(geekorm::Error::UniqueViolation { column }) = &result {
+    ///         println!("{} already exists", column.as_deref().unwrap_or("value"));
+    ///     }
+    ///     # assert!(matches!(
+    ///     #     result,
+    ///     #     Err(geekorm::Error::UniqueViolation { column: Some(ref column) }) if column == "username"
+    ///     # ));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// The row is inserted with a `RETURNING` clause, so columns set by the
+    /// database (auto increment keys, defaults, etc.) are updated in one round trip:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     #[geekorm(readonly)]
+    ///     pub role: Option<String>,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     // The database sets the default role
+    ///     connection.execute_batch(
+    ///         "CREATE TABLE Users (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT NOT NULL, role TEXT DEFAULT 'member');",
+    ///     )?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///     # assert_eq!(user.id, PrimaryKeyInteger::from(1));
+    ///     # assert_eq!(user.role.as_deref(), Some("member"));
+    ///     # assert_eq!(
+    ///     #     Users::query_insert_returning(&user).to_str(),
+    ///     #     "INSERT INTO Users (username) VALUES (?) RETURNING id, username, role;"
+    ///     # );
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
     #[allow(async_fn_in_trait, unused_variables)]
     async fn save(&mut self, connection: &'a C) -> Result<(), crate::Error>;
 
+    /// Save a new item unless it conflicts with an existing row (`INSERT OR IGNORE`)
+    ///
+    /// Returns `true` if the row was inserted (and the current object updated like
+    /// [`GeekConnector::save`]), or `false` if a conflicting row already exists.
+    /// Unlike [`GeekConnector::persist`], existing rows are never updated, which
+    /// makes this useful for idempotent inserts (e.g. seeding reference data).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Roles {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub name: String,
+    ///     pub description: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Roles::create_table(&connection).await?;
+    ///
+    ///     let mut admin = Roles::new("admin", "Administrator");
+    ///     # assert!(admin.save_ignore(&connection).await?);
+    ///     # assert_eq!(Roles::total(&connection).await?, 1);
+    ///
+    ///     // Seeding again does not error or change the existing row
+    ///     let mut duplicate = Roles::new("admin", "Changed");
+    ///     let inserted = duplicate.save_ignore(&connection).await?;
+    ///     # assert!(!inserted);
+    ///     # assert_eq!(Roles::total(&connection).await?, 1);
+    ///     # let admin = Roles::fetch_by_name(&connection, "admin").await?;
+    ///     # assert_eq!(admin.description, "Administrator");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn save_ignore(&mut self, connection: &'a C) -> Result<bool, crate::Error> {
+        // An ignored insert returns no rows
+        let query = Self::query_insert_or_ignore(self).returning_all();
+        match C::query::<Self>(connection, query)
+            .await?
+            .into_iter()
+            .next()
+        {
+            Some(item) => {
+                *self = item;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Insert or update the current object based on its primary key
+    ///
+    /// If the primary key is unset (`0`, empty or `NULL`) the row is inserted
+    /// using [`GeekConnector::save`], otherwise the existing row is updated
+    /// using [`GeekConnector::update`].
+    ///
+    /// Primary keys generated on creation (e.g. `PrimaryKeyUuid`) are always
+    /// set, so use `save` explicitly for new rows with those keys.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     // New row, inserted
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.persist(&connection).await?;
+    ///     # assert_eq!(user.id, PrimaryKeyInteger::from(1));
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///
+    ///     // Existing row, updated
+    ///     user.username = String::from("geek");
+    ///     user.persist(&connection).await?;
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///     # let user = Users::fetch_by_primary_key(&connection, user.id).await?;
+    ///     # assert_eq!(user.username, "geek");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn persist(&mut self, connection: &'a C) -> Result<(), crate::Error>
+    where
+        Self: TablePrimaryKey,
+    {
+        if self.primary_key_is_set() {
+            self.update(connection).await
+        } else {
+            self.save(connection).await
+        }
+    }
+
+    /// Insert a batch of rows into the database within a single transaction
+    ///
+    /// Every row is validated first ([`TableBuilder::validate`]).
+    /// If any of the inserts fail, the transaction is rolled back and the error is returned.
+    /// Returns the number of rows inserted (as reported by the database).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let users: Vec<Users> = (0..250).map(|i| Users::new(format!("user{}", i))).collect();
+    ///
+    ///     let inserted = Users::save_batch(&connection, &users).await?;
+    ///     assert_eq!(inserted, users.len() as u64);
+    ///     # assert_eq!(Users::total(&connection).await?, 250);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn save_batch(connection: &'a C, items: &[Self]) -> Result<u64, crate::Error> {
+        // Validate every row before starting the transaction
+        for item in items {
+            <Self as TableBuilder>::validate(item)?;
+        }
+
+        C::batch(
+            connection,
+            Query::raw(QueryType::Update, "BEGIN TRANSACTION;"),
+        )
+        .await?;
+
+        let mut inserted = 0;
+        for item in items {
+            match C::execute_affected(connection, Self::query_insert(item)).await {
+                Ok(affected) => inserted += affected,
+                Err(err) => {
+                    // Return the insert error, not a (possible) rollback error
+                    if let Err(rollback) =
+                        C::batch(connection, Query::raw(QueryType::Update, "ROLLBACK;")).await
+                    {
+                        #[cfg(feature = "log")]
+                        {
+                            log::error!("Failed to rollback the batch insert: {}", rollback);
+                        }
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        C::batch(connection, Query::raw(QueryType::Update, "COMMIT;")).await?;
+        Ok(inserted)
+    }
+
+    /// Insert rows from a stream in batches of `batch_size` rows
+    ///
+    /// Rows are only pulled from the stream once the previous batch has been
+    /// written, so the whole stream is never held in memory.
+    /// Each batch is inserted with [`GeekConnector::save_batch`] and the total
+    /// number of rows inserted across all the batches is returned.
+    ///
+    /// Requires the `stream` feature.
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "rusqlite", feature = "stream"))] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let users = futures::stream::iter((0..1000).map(|i| Users::new(format!("user{}", i))));
+    ///
+    ///     let inserted = Users::save_stream(&connection, users, 100).await?;
+    ///     # assert_eq!(inserted, 1000);
+    ///     # assert_eq!(Users::total(&connection).await?, 1000);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "stream")]
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn save_stream(
+        connection: &'a C,
+        stream: impl Stream<Item = Self>,
+        batch_size: usize,
+    ) -> Result<u64, crate::Error> {
+        let batch_size = batch_size.max(1);
+        let mut stream = std::pin::pin!(stream);
+
+        let mut total = 0;
+        let mut batch: Vec<Self> = Vec::with_capacity(batch_size);
+
+        while let Some(item) = stream.next().await {
+            batch.push(item);
+
+            if batch.len() >= batch_size {
+                total += Self::save_batch(connection, &batch).await?;
+                batch.clear();
+            }
+        }
+        if !batch.is_empty() {
+            total += Self::save_batch(connection, &batch).await?;
+        }
+
+        Ok(total)
+    }
+
     /// Delete the current object from the database
     #[allow(async_fn_in_trait, unused_variables)]
     async fn delete(&self, connection: &'a C) -> Result<(), crate::Error> {
         C::execute(connection, Self::query_delete(self)).await
     }
 
+    /// Delete the current object from the database and return the number of rows affected
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///
+    ///     // Delete the existing row
+    ///     let affected = user.delete_affected(&connection).await?;
+    ///     # assert_eq!(affected, 1);
+    ///
+    ///     // The row no longer exists
+    ///     let affected = user.delete_affected(&connection).await?;
+    ///     # assert_eq!(affected, 0);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn delete_affected(&self, connection: &'a C) -> Result<u64, crate::Error> {
+        C::execute_affected(connection, Self::query_delete(self)).await
+    }
+
+    /// Delete the current object from the database and return the deleted row
+    ///
+    /// The row is deleted and returned in a single query (`RETURNING`), so there is
+    /// no race between reading and deleting the row (e.g. for audit logs or undo).
+    /// An error is returned if the row does not exist.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub active: bool,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher", true);
+    ///     user.save(&connection).await?;
+    ///
+    ///     let deleted = user.delete_returning(&connection).await?;
+    ///     # assert_eq!(deleted, user);
+    ///     # assert_eq!(Users::total(&connection).await?, 0);
+    ///
+    ///     // The row no longer exists
+    ///     # assert!(user.delete_returning(&connection).await.is_err());
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn delete_returning(&self, connection: &'a C) -> Result<Self, crate::Error>
+    where
+        Self: TablePrimaryKey,
+    {
+        let query = QueryBuilder::delete()
+            .table(Self::table())
+            .where_eq(Self::primary_key().as_str(), self.primary_key_value())
+            .returning_all()
+            .build()?;
+        C::query_first::<Self>(connection, query).await
+    }
+
+    /// Delete all the rows in the table matching the filter criteria passed as a tuple of (&str, Value).
+    ///
+    /// The same prefix operators as [`GeekConnector::filter`] are supported.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Sessions {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub token: String,
+    ///     pub expired: bool,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Sessions::create_table(&connection).await?;
+    ///
+    ///     Sessions::new("a", true).save(&connection).await?;
+    ///     Sessions::new("b", false).save(&connection).await?;
+    ///     Sessions::new("c", true).save(&connection).await?;
+    ///
+    ///     // Delete all the expired sessions
+    ///     Sessions::delete_where(&connection, vec![("=expired", true)]).await?;
+    ///
+    ///     let sessions = Sessions::all(&connection).await?;
+    ///     # assert_eq!(sessions.len(), 1);
+    ///     # assert_eq!(sessions[0].token, "b");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn delete_where(
+        connection: &'a C,
+        fields: Vec<(&str, impl Into<Value>)>,
+    ) -> Result<(), crate::Error> {
+        C::execute(
+            connection,
+            QueryBuilder::delete()
+                .table(Self::table())
+                .filter(fields)
+                .build()?,
+        )
+        .await
+    }
+
     /// Fetches all of the foreign key values for the current object
+    ///
+    /// Loaded relations (e.g. the `Vec<Child>` of a one-to-many relation) should be
+    /// modelled as `#[geekorm(transient)]` fields. They are not columns, are never
+    /// read from or written to the table, and are only populated by your fetch logic.
+    /// Serde has to skip them too, so they need `#[serde(skip)]` (or `#[serde(default)]`).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub username: String,
+    ///
+    ///     /// Posts written by the user (loaded on demand)
+    ///     #[geekorm(transient)]
+    ///     #[serde(skip)]
+    ///     pub posts: Vec<Posts>,
+    /// }
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Posts {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub user_id: i32,
+    ///     pub title: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     Posts::create_table(&connection).await?;
+    ///     # assert!(!Users::query_create().build()?.to_str().contains("posts"));
+    ///
+    ///     // Transient fields are not constructor parameters
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///     for title in ["Hello", "World"] {
+    ///         Posts::new(user.id, title).save(&connection).await?;
+    ///     }
+    ///
+    ///     let mut user = Users::fetch_by_username(&connection, "geekmasher").await?;
+    ///     # assert!(user.posts.is_empty());
+    ///     user.fetch(&connection).await?;
+    ///     user.posts = Posts::filter(&connection, vec![("user_id", user.id)]).await?;
+    ///     # assert_eq!(user.posts.len(), 2);
+    ///
+    ///     // Saving the user does not write the relation
+    ///     user.username = String::from("geek");
+    ///     user.update(&connection).await?;
+    ///     # assert_eq!(Users::fetch_by_username(&connection, "geek").await?.posts.len(), 0);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
     #[allow(async_fn_in_trait, unused_variables)]
     async fn fetch(&mut self, connection: &'a C) -> Result<(), crate::Error>;
 
@@ -233,6 +1160,43 @@ where
     #[allow(async_fn_in_trait, unused_variables)]
     async fn fetch_or_create(&mut self, connection: &'a C) -> Result<(), crate::Error>;
 
+    /// Fetch or create a row in the database and return if the row was created
+    /// or already existed (based on the unique columns).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    /// use geekorm::FetchOrCreate;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     let state = user.refresh_or_insert(&connection).await?;
+    ///     # assert_eq!(state, FetchOrCreate::Created);
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     let state = user.refresh_or_insert(&connection).await?;
+    ///     # assert_eq!(state, FetchOrCreate::Existing);
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn refresh_or_insert(&mut self, connection: &'a C)
+        -> Result<FetchOrCreate, crate::Error>;
+
     /// Search for a row in the database based on specific criteria
     #[cfg(feature = "search")]
     #[allow(async_fn_in_trait, unused_variables)]
@@ -282,6 +1246,15 @@ where
     }
 }
 
+/// The result of [`GeekConnector::refresh_or_insert`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchOrCreate {
+    /// A new row was inserted into the database
+    Created,
+    /// An existing row was loaded from the database
+    Existing,
+}
+
 /// GeekConnection is the trait that all backends must implement to be able
 /// to interact with the database.
 pub trait GeekConnection {
@@ -289,6 +1262,8 @@ pub trait GeekConnection {
     type Connection;
 
     /// Create a table in the database
+    ///
+    /// Errors if the table does not have a primary key column.
     #[allow(async_fn_in_trait, unused_variables)]
     async fn create_table<T>(connection: &Self::Connection) -> Result<(), crate::Error>
     where
@@ -301,6 +1276,65 @@ pub trait GeekConnection {
         Err(crate::Error::NotImplemented)
     }
 
+    /// Create all the tables of a database (e.g. from the `tables!()` macro)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// mod models {
+    ///     use geekorm::prelude::*;
+    ///
+    ///     #[derive(Table, Debug, Default, serde::Serialize, serde::Deserialize)]
+    ///     pub struct Users {
+    ///         #[geekorm(primary_key, auto_increment)]
+    ///         pub id: PrimaryKeyInteger,
+    ///         pub username: String,
+    ///     }
+    ///
+    ///     pub mod projects {
+    ///         use geekorm::prelude::*;
+    ///
+    ///         #[derive(Table, Debug, Default, serde::Serialize, serde::Deserialize)]
+    ///         pub struct Projects {
+    ///             #[geekorm(primary_key, auto_increment)]
+    ///             pub id: PrimaryKeyInteger,
+    ///             pub name: String,
+    ///         }
+    ///     }
+    /// }
+    /// use models::{projects::Projects, Users};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///
+    ///     // Create every derived table at once
+    ///     let database = geekorm::tables!();
+    ///     # assert_eq!(database.tables.len(), 2);
+    ///     rusqlite::Connection::create_all(&connection, &database).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///     let mut project = Projects::new("geekorm");
+    ///     project.save(&connection).await?;
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///     # assert_eq!(Projects::total(&connection).await?, 1);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn create_all(
+        connection: &Self::Connection,
+        database: &crate::builder::database::Database,
+    ) -> Result<(), crate::Error> {
+        for query in database.create_queries()? {
+            Self::execute(connection, query).await?;
+        }
+        Ok(())
+    }
+
     /// Run a SELECT Count query on the database and return the number of rows
     #[allow(async_fn_in_trait, unused_variables)]
     async fn row_count(connection: &Self::Connection, query: Query) -> Result<i64, crate::Error> {
@@ -308,17 +1342,334 @@ pub trait GeekConnection {
     }
 
     /// Execute a query on the database and do not return any rows
+    ///
+    /// Select queries return an error as their rows would be discarded,
+    /// use [`GeekConnection::query`] or [`GeekConnection::query_first`] instead.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     <rusqlite::Connection as GeekConnection>::execute(&connection, Users::query_insert(&Users::new("geekmasher"))).await?;
+    ///
+    ///     let result = <rusqlite::Connection as GeekConnection>::execute(&connection, Users::query_all()).await;
+    ///     # assert!(matches!(result, Err(geekorm::Error::QueryBuilderError(_, _))));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
     #[allow(async_fn_in_trait, unused_variables)]
     async fn execute(connection: &Self::Connection, query: Query) -> Result<(), crate::Error> {
         Err(crate::Error::NotImplemented)
     }
 
+    /// Execute a query on the database and return the number of rows affected
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn execute_affected(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<u64, crate::Error> {
+        Err(crate::Error::NotImplemented)
+    }
+
     /// Execute a batch query on the database and do not return any rows
     #[allow(async_fn_in_trait, unused_variables)]
     async fn batch(connection: &Self::Connection, query: Query) -> Result<(), crate::Error> {
         Err(crate::Error::NotImplemented)
     }
 
+    /// Execute multiple queries, with their parameters, in a single transaction
+    ///
+    /// If any query fails the transaction is rolled back and the error is
+    /// returned, so either all of the queries are applied or none of them are.
+    /// Unlike [`GeekConnection::batch`], each query is executed with its own
+    /// bound parameters.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     // The second insert violates the unique constraint so the first is rolled back
+    ///     let result = rusqlite::Connection::execute_all(
+    ///         &connection,
+    ///         vec![
+    ///             Users::query_insert(&Users::new("geekmasher")),
+    ///             Users::query_insert(&Users::new("geekmasher")),
+    ///         ],
+    ///     )
+    ///     .await;
+    ///     # assert!(result.is_err());
+    ///     # assert_eq!(Users::total(&connection).await?, 0);
+    ///
+    ///     rusqlite::Connection::execute_all(
+    ///         &connection,
+    ///         vec![
+    ///             Users::query_insert(&Users::new("geekmasher")),
+    ///             Users::query_insert(&Users::new("bob")),
+    ///         ],
+    ///     )
+    ///     .await?;
+    ///     # assert_eq!(Users::total(&connection).await?, 2);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn execute_all(
+        connection: &Self::Connection,
+        queries: Vec<Query>,
+    ) -> Result<(), crate::Error> {
+        Self::batch(
+            connection,
+            Query::raw(QueryType::Update, "BEGIN TRANSACTION;"),
+        )
+        .await?;
+
+        for query in queries {
+            if let Err(err) = Self::execute(connection, query).await {
+                // Return the query error, not a (possible) rollback error
+                if let Err(rollback) =
+                    Self::batch(connection, Query::raw(QueryType::Update, "ROLLBACK;")).await
+                {
+                    #[cfg(feature = "log")]
+                    {
+                        log::error!("Failed to rollback the transaction: {}", rollback);
+                    }
+                }
+                return Err(err);
+            }
+        }
+
+        Self::batch(connection, Query::raw(QueryType::Update, "COMMIT;")).await
+    }
+
+    /// Create a `SAVEPOINT` with the given name
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn savepoint(connection: &Self::Connection, name: &str) -> Result<(), crate::Error> {
+        let name = savepoint_name(name, "savepoint")?;
+        Self::batch(
+            connection,
+            Query::raw(QueryType::Update, format!("SAVEPOINT {};", name)),
+        )
+        .await
+    }
+
+    /// Release (commit) a `SAVEPOINT`
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn release(connection: &Self::Connection, name: &str) -> Result<(), crate::Error> {
+        let name = savepoint_name(name, "release")?;
+        Self::batch(
+            connection,
+            Query::raw(QueryType::Update, format!("RELEASE SAVEPOINT {};", name)),
+        )
+        .await
+    }
+
+    /// Roll back all the changes made since a `SAVEPOINT` was created
+    ///
+    /// The savepoint stays active, call [`GeekConnection::release`] to remove it.
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn rollback_to(connection: &Self::Connection, name: &str) -> Result<(), crate::Error> {
+        let name = savepoint_name(name, "rollback_to")?;
+        Self::batch(
+            connection,
+            Query::raw(
+                QueryType::Update,
+                format!("ROLLBACK TO SAVEPOINT {};", name),
+            ),
+        )
+        .await
+    }
+
+    /// Run a closure inside a `SAVEPOINT`
+    ///
+    /// The savepoint is released if the closure returns `Ok` and rolled back if it
+    /// returns `Err`, so only the changes made by the closure are undone. This can be
+    /// nested inside a larger transaction for partial rollbacks.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     rusqlite::Connection::batch(&connection, Query::raw(QueryType::Update, "BEGIN;")).await?;
+    ///     Users::new("geekmasher").save(&connection).await?;
+    ///
+    ///     // The inner savepoint fails and only its changes are rolled back
+    ///     let result: Result<(), geekorm::Error> =
+    ///         rusqlite::Connection::with_savepoint(&connection, "risky", || async {
+    ///             Users::new("bob").save(&connection).await?;
+    ///             Err(geekorm::Error::ValidationError(String::from("Something went wrong")))
+    ///         })
+    ///         .await;
+    ///     # assert!(result.is_err());
+    ///
+    ///     rusqlite::Connection::batch(&connection, Query::raw(QueryType::Update, "COMMIT;")).await?;
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn with_savepoint<F, Fut, R>(
+        connection: &Self::Connection,
+        name: &str,
+        f: F,
+    ) -> Result<R, crate::Error>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<R, crate::Error>>,
+    {
+        Self::savepoint(connection, name).await?;
+
+        match f().await {
+            Ok(result) => {
+                Self::release(connection, name).await?;
+                Ok(result)
+            }
+            Err(err) => {
+                // Return the closure error, not a (possible) rollback error
+                if let Err(rollback) = Self::rollback_to(connection, name).await {
+                    #[cfg(feature = "log")]
+                    {
+                        log::error!("Failed to rollback savepoint `{}`: {}", name, rollback);
+                    }
+                }
+                if let Err(release) = Self::release(connection, name).await {
+                    #[cfg(feature = "log")]
+                    {
+                        log::error!("Failed to release savepoint `{}`: {}", name, release);
+                    }
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Configure a newly opened connection with the [`DEFAULT_PRAGMAS`]
+    ///
+    /// Pragmas are per connection, so call this every time a connection is
+    /// opened (before it is used). Use [`GeekConnection::pragma`] to set other
+    /// pragmas or to override the defaults afterwards.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     # let path = std::env::temp_dir().join(format!("geekorm-configure-{}.db", std::process::id()));
+    ///     let connection = rusqlite::Connection::open(&path)?;
+    ///     rusqlite::Connection::configure(&connection).await?;
+    ///
+    ///     let journal_mode: String =
+    ///         connection.pragma_query_value(None, "journal_mode", |row| row.get(0))?;
+    ///     assert_eq!(journal_mode, "wal");
+    ///     # let foreign_keys: i64 = connection.pragma_query_value(None, "foreign_keys", |row| row.get(0))?;
+    ///     # assert_eq!(foreign_keys, 1);
+    ///     # let synchronous: i64 = connection.pragma_query_value(None, "synchronous", |row| row.get(0))?;
+    ///     # assert_eq!(synchronous, 1);
+    ///     # drop(connection);
+    ///     # for suffix in ["", "-wal", "-shm"] {
+    ///     #     let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+    ///     # }
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn configure(connection: &Self::Connection) -> Result<(), crate::Error> {
+        for (name, value) in DEFAULT_PRAGMAS {
+            Self::pragma(connection, name, value).await?;
+        }
+        Ok(())
+    }
+
+    /// Set a SQLite `PRAGMA` on the connection (e.g. `journal_mode = WAL`)
+    ///
+    /// Pragmas are per connection, so they need to be set every time a new
+    /// connection is opened. [`GeekConnection::configure`] applies the
+    /// [`DEFAULT_PRAGMAS`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///
+    ///     // `rusqlite::Connection` has its own `pragma` method
+    ///     <rusqlite::Connection as GeekConnection>::pragma(&connection, "foreign_keys", "ON").await?;
+    ///     # let enabled: i64 = connection.pragma_query_value(None, "foreign_keys", |row| row.get(0))?;
+    ///     # assert_eq!(enabled, 1);
+    ///
+    ///     // Invalid pragma names or values are rejected
+    ///     # assert!(<rusqlite::Connection as GeekConnection>::pragma(&connection, "foreign_keys; DROP", "ON").await.is_err());
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn pragma(
+        connection: &Self::Connection,
+        name: &str,
+        value: &str,
+    ) -> Result<(), crate::Error> {
+        let valid = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        };
+        if !valid(name) || !valid(value) {
+            return Err(crate::Error::QueryBuilderError(
+                format!("Invalid pragma `{} = {}`", name, value),
+                String::from("pragma"),
+            ));
+        }
+        Self::batch(
+            connection,
+            Query::raw(QueryType::Raw, format!("PRAGMA {} = {};", name, value)),
+        )
+        .await
+    }
+
     /// Query the database with an active Connection and Query
     #[allow(async_fn_in_trait, unused_variables)]
     async fn query<T>(connection: &Self::Connection, query: Query) -> Result<Vec<T>, crate::Error>
@@ -349,6 +1700,143 @@ pub trait GeekConnection {
         Err(crate::Error::NotImplemented)
     }
 
+    /// Query the database and return the first column of the first row as a typed value
+    ///
+    /// Returns `None` if there are no rows or the value is `NULL`.
+    /// The query should only select a single column (e.g. `COUNT(1)` or `MAX(column)`).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let max_age = Query::raw(QueryType::Select, "SELECT MAX(age) FROM Users;");
+    ///     let age: Option<i64> = rusqlite::Connection::query_scalar(&connection, max_age.clone()).await?;
+    ///     # assert_eq!(age, None);
+    ///
+    ///     for age in [21, 42, 30] {
+    ///         Users::new(age).save(&connection).await?;
+    ///     }
+    ///     let age: Option<i64> = rusqlite::Connection::query_scalar(&connection, max_age).await?;
+    ///     # assert_eq!(age, Some(42));
+    ///
+    ///     // REAL results (e.g. AVG / SUM) can be read as floats
+    ///     let average: Option<f64> = rusqlite::Connection::query_scalar(
+    ///         &connection,
+    ///         Query::raw(QueryType::Select, "SELECT AVG(age) FROM Users;"),
+    ///     ).await?;
+    ///     # assert_eq!(average, Some(31.0));
+    ///
+    ///     let count: Option<i64> = rusqlite::Connection::query_scalar(
+    ///         &connection,
+    ///         Users::query_count().build()?,
+    ///     ).await?;
+    ///     # assert_eq!(count, Some(3));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn query_scalar<V>(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<Option<V>, crate::Error>
+    where
+        V: TryFrom<Value>,
+        V::Error: std::fmt::Display,
+    {
+        Err(crate::Error::NotImplemented)
+    }
+
+    /// Query the database and deserialize each row positionally into a tuple
+    ///
+    /// The columns are matched by their position in the projection (not by name),
+    /// so ad-hoc multi-column selects can be read without defining a struct.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i64,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     for (username, age) in [("geekmasher", 42), ("bob", 21)] {
+    ///         Users::new(username, age).save(&connection).await?;
+    ///     }
+    ///
+    ///     let rows: Vec<(String, i64)> = rusqlite::Connection::query_tuple(
+    ///         &connection,
+    ///         Query::raw(QueryType::Select, "SELECT username, age FROM Users ORDER BY age;"),
+    ///     )
+    ///     .await?;
+    ///     # assert_eq!(
+    ///     #     rows,
+    ///     #     vec![(String::from("bob"), 21), (String::from("geekmasher"), 42)]
+    ///     # );
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn query_tuple<T>(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        Err(crate::Error::NotImplemented)
+    }
+
+    /// Get the version of the database engine (e.g. `3.45.0` for SQLite)
+    ///
+    /// This can be used to pick features the database supports, for example
+    /// `ALTER TABLE ... DROP COLUMN` requires SQLite 3.35 or newer.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///
+    ///     let version = rusqlite::Connection::database_version(&connection).await?;
+    ///     # assert!(!version.is_empty());
+    ///     # assert!(version.starts_with("3."));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn database_version(connection: &Self::Connection) -> Result<String, crate::Error> {
+        let query = Query::raw(QueryType::Raw, "SELECT sqlite_version();");
+        let sql = query.query.clone();
+        Self::query_scalar::<String>(connection, query)
+            .await?
+            .ok_or(crate::Error::NoRowsFound { query: sql })
+    }
+
     /// Get Table Names
     #[cfg(feature = "migrations")]
     #[allow(async_fn_in_trait, unused_variables)]
@@ -356,11 +1844,10 @@ pub trait GeekConnection {
         // TODO: This only works for SQLite
         let results: Vec<TableNames> = Self::query(
             connection,
-            Query {
-                query: format!("SELECT name FROM sqlite_master WHERE type='table'"),
-                query_type: crate::builder::models::QueryType::Select,
-                ..Default::default()
-            },
+            Query::raw(
+                QueryType::Raw,
+                "SELECT name FROM sqlite_master WHERE type='table'",
+            ),
         )
         .await?;
 
@@ -378,6 +1865,41 @@ pub trait GeekConnection {
     }
 
     /// Pragma table info
+    ///
+    /// Table names which are reserved keywords are quoted.
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "migrations", feature = "rusqlite"))] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Order {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub group: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Order::create_table(&connection).await?;
+    ///
+    ///     let mut order = Order::new("books");
+    ///     order.save(&connection).await?;
+    ///     let order = Order::fetch_by_primary_key(&connection, order.id).await?;
+    ///     # assert_eq!(order.group, "books");
+    ///
+    ///     let columns = rusqlite::Connection::pragma_info(&connection, "Order").await?;
+    ///     # assert_eq!(columns.len(), 2);
+    ///     # assert_eq!(columns[1].name, "group");
+    ///     let indexes = rusqlite::Connection::index_info(&connection, "Order").await?;
+    ///     # assert_eq!(indexes.len(), 1);
+    ///     # assert_eq!(indexes[0].columns, vec!["group"]);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
     #[cfg(feature = "migrations")]
     #[allow(async_fn_in_trait, unused_variables)]
     async fn pragma_info(
@@ -386,14 +1908,159 @@ pub trait GeekConnection {
     ) -> Result<Vec<TableInfo>, crate::Error> {
         Self::query(
             connection,
-            Query {
-                query: format!("PRAGMA table_info({})", table),
-                query_type: crate::builder::models::QueryType::Select,
-                ..Default::default()
-            },
+            Query::raw(
+                QueryType::Raw,
+                format!("PRAGMA table_info({})", quote_identifier(table)),
+            ),
         )
         .await
     }
+
+    /// List the indexes on a table and the columns they cover
+    ///
+    /// This includes the indexes SQLite creates for `UNIQUE` and `PRIMARY KEY` constraints.
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "migrations", feature = "rusqlite"))] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique)]
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     rusqlite::Connection::batch(
+    ///         &connection,
+    ///         Query::raw(QueryType::Create, "CREATE INDEX idx_users_age ON Users (age);"),
+    ///     )
+    ///     .await?;
+    ///
+    ///     let indexes = rusqlite::Connection::index_info(&connection, "Users").await?;
+    ///     # assert_eq!(indexes.len(), 2);
+    ///     let age = indexes.iter().find(|index| index.name == "idx_users_age").unwrap();
+    ///     # assert_eq!(age.columns, vec!["age"]);
+    ///     # assert!(!age.unique);
+    ///     # let username = indexes.iter().find(|index| index.unique).unwrap();
+    ///     # assert_eq!(username.columns, vec!["username"]);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "migrations")]
+    #[allow(async_fn_in_trait, unused_variables)]
+    async fn index_info(
+        connection: &Self::Connection,
+        table: &str,
+    ) -> Result<Vec<IndexInfo>, crate::Error> {
+        // TODO: This only works for SQLite
+        let indexes: Vec<IndexList> = Self::query(
+            connection,
+            Query::raw(
+                QueryType::Raw,
+                format!("PRAGMA index_list({})", quote_identifier(table)),
+            ),
+        )
+        .await?;
+
+        let mut results = Vec::new();
+        for index in indexes {
+            let columns: Vec<IndexColumn> = Self::query(
+                connection,
+                Query::raw(
+                    QueryType::Raw,
+                    format!("PRAGMA index_info({})", quote_identifier(&index.name)),
+                ),
+            )
+            .await?;
+
+            results.push(IndexInfo {
+                name: index.name,
+                unique: index.unique != 0,
+                origin: index.origin,
+                partial: index.partial != 0,
+                // Expression indexes do not have a column name
+                columns: columns.into_iter().filter_map(|c| c.name).collect(),
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// Query rows with a closure over the native row type of a backend
+///
+/// This is kept apart from [`GeekConnection`] so custom connections do not need
+/// to name a native row type.
+pub trait GeekQueryMap: GeekConnection {
+    /// Native Row (passed to the closure of [`GeekQueryMap::query_map`])
+    type Row<'r>;
+
+    /// Query the database and map each native row with a closure
+    ///
+    /// This is an escape hatch for rows that do not map cleanly to a struct
+    /// (custom transforms, computed fields, etc).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub first_name: String,
+    ///     pub last_name: String,
+    /// }
+    ///
+    /// #[derive(Debug)]
+    /// struct Person {
+    ///     id: i64,
+    ///     full_name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     Users::new("Jane", "Doe").save(&connection).await?;
+    ///     Users::new("John", "Smith").save(&connection).await?;
+    ///
+    ///     let people = rusqlite::Connection::query_map(
+    ///         &connection,
+    ///         Users::query_select().order_by("id", QueryOrder::Asc).build()?,
+    ///         |row| {
+    ///             let first: String = row.get("first_name")?;
+    ///             let last: String = row.get("last_name")?;
+    ///             Ok(Person {
+    ///                 id: row.get("id")?,
+    ///                 full_name: format!("{} {}", first, last),
+    ///             })
+    ///         },
+    ///     )
+    ///     .await?;
+    ///     # assert_eq!(people.len(), 2);
+    ///     # assert_eq!(people[0].id, 1);
+    ///     # assert_eq!(people[0].full_name, "Jane Doe");
+    ///     # assert_eq!(people[1].full_name, "John Smith");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[allow(async_fn_in_trait)]
+    async fn query_map<T, F>(
+        connection: &Self::Connection,
+        query: Query,
+        f: F,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        F: for<'r> Fn(&Self::Row<'r>) -> Result<T, crate::Error>;
 }
 
 /// Table Info
@@ -414,9 +2081,75 @@ pub struct TableInfo {
     pub pk: i32,
 }
 
+/// Index Info
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct IndexInfo {
+    /// The index name
+    pub name: String,
+    /// If the index is unique
+    pub unique: bool,
+    /// How the index was created (`c` for `CREATE INDEX`, `u` for `UNIQUE`
+    /// and `pk` for `PRIMARY KEY` constraints)
+    pub origin: String,
+    /// If the index is a partial index
+    pub partial: bool,
+    /// The columns in the index (in order)
+    pub columns: Vec<String>,
+}
+
+/// Row of `PRAGMA index_list`
+#[cfg(feature = "migrations")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct IndexList {
+    name: String,
+    unique: i32,
+    origin: String,
+    partial: i32,
+}
+
+/// Row of `PRAGMA index_info`
+#[cfg(feature = "migrations")]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct IndexColumn {
+    name: Option<String>,
+}
+
 /// Table Names
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 struct TableNames {
     /// The table name
     pub name: String,
 }
+
+/// Validate a savepoint name (savepoint names can not be bound as parameters)
+fn savepoint_name<'n>(name: &'n str, function: &str) -> Result<&'n str, crate::Error> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(crate::Error::QueryBuilderError(
+            format!("Invalid savepoint name `{}`", name),
+            function.to_string(),
+        ));
+    }
+    Ok(name)
+}
+
+/// Make sure a query that does not return rows (`execute`) is not a select query
+/// (or a write query with a `RETURNING` clause)
+pub(crate) fn ensure_not_select(query: &Query) -> Result<(), crate::Error> {
+    if query.query_type == QueryType::Select {
+        return Err(crate::Error::QueryBuilderError(
+            String::from(
+                "Select queries return rows, use `query` or `query_first` instead of `execute`",
+            ),
+            String::from("execute"),
+        ));
+    }
+    if query.returns_rows() {
+        return Err(crate::Error::QueryBuilderError(
+            String::from(
+                "Queries with `RETURNING` return rows, use `query` or `query_first` instead of `execute`",
+            ),
+            String::from("execute"),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,345 @@
+//! # Read-only connections
+//!
+//! [`ReadOnly`] wraps a connection and rejects every query that could write to
+//! the database before it reaches the backend. Only read-only statements
+//! (`SELECT`, `EXPLAIN` and `PRAGMA` without an argument) are allowed, inserts,
+//! updates, deletes and `CREATE TABLE` all return a
+//! [`crate::Error::QueryBuilderError`]. The SQL of select queries is checked as
+//! well, so a raw select query can not smuggle in a write.
+//!
+//! The check is conservative: the SQL is split into statements on `;` and each
+//! statement has to start with one of the allowed keywords. Common table
+//! expressions (`WITH ... SELECT`) and string literals containing a `;` are
+//! rejected even if the query only reads.
+//!
+//! The table introspection helpers (`pragma_info` / `index_info`) are passed to
+//! the wrapped connection as they only run `PRAGMA` queries which read the schema.
+//! Savepoints (`savepoint`, `release`, `rollback_to` and `with_savepoint`) are
+//! passed to the wrapped connection too so reads can share a snapshot, the
+//! queries run inside the savepoint are still checked.
+//!
+//! ```rust
+//! # #[cfg(feature = "rusqlite")] {
+//! use geekorm::prelude::*;
+//!
+//! #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//! pub struct Users {
+//!     #[geekorm(primary_key, auto_increment)]
+//!     pub id: PrimaryKeyInteger,
+//!     pub username: String,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let connection = rusqlite::Connection::open_in_memory()?;
+//!     Users::create_table(&connection).await?;
+//!     Users::new("geekmasher").save(&connection).await?;
+//!
+//!     let readonly = ReadOnly::new(connection);
+//!
+//!     // Reads are passed to the wrapped connection
+//!     let users = Users::all(&readonly).await?;
+//!     # assert_eq!(users.len(), 1);
+//!     # assert_eq!(Users::total(&readonly).await?, 1);
+//!     # #[cfg(feature = "pagination")]
+//!     # assert_eq!(Users::fetch_page_with_total(&readonly, 0, 10).await?.1, 1);
+//!     # assert_eq!(ReadOnly::<rusqlite::Connection>::table_names(&readonly).await?, vec!["Users"]);
+//!     # #[cfg(feature = "migrations")]
+//!     # assert_eq!(ReadOnly::<rusqlite::Connection>::pragma_info(&readonly, "Users").await?.len(), 2);
+//!
+//!     // Writes are rejected
+//!     let mut user = Users::new("bob");
+//!     assert!(user.save(&readonly).await.is_err());
+//!     # assert!(Users::create_table(&readonly).await.is_err());
+//!     # let mut geekmasher = users[0].clone();
+//!     # geekmasher.username = String::from("geek");
+//!     # assert!(geekmasher.update(&readonly).await.is_err());
+//!     # assert!(geekmasher.delete(&readonly).await.is_err());
+//!     # assert!(ReadOnly::<rusqlite::Connection>::pragma(&readonly, "foreign_keys", "OFF").await.is_err());
+//!     # let write = ReadOnly::<rusqlite::Connection>::with_savepoint(&readonly, "write", || async {
+//!     #     Users::new("eve").save(&readonly).await
+//!     # });
+//!     # assert!(write.await.is_err());
+//!     # assert_eq!(Users::total(readonly.inner()).await?, 1);
+//!     Ok(())
+//! }
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::builder::models::QueryType;
+use crate::{GeekConnection, GeekQueryMap, Query, QueryBuilderTrait, TableBuilder, Value};
+
+/// Read-only connection wrapper
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone, Default)]
+pub struct ReadOnly<C>(C);
+
+impl<C> ReadOnly<C> {
+    /// Wrap a connection so only read queries can be run on it
+    pub fn new(connection: C) -> Self {
+        Self(connection)
+    }
+
+    /// Get a reference to the wrapped connection
+    pub fn inner(&self) -> &C {
+        &self.0
+    }
+
+    /// Unwrap the read-only connection
+    pub fn into_inner(self) -> C {
+        self.0
+    }
+}
+
+impl<C> From<C> for ReadOnly<C> {
+    fn from(connection: C) -> Self {
+        Self(connection)
+    }
+}
+
+impl<C> GeekConnection for ReadOnly<C>
+where
+    C: GeekConnection<Connection = C>,
+{
+    type Connection = ReadOnly<C>;
+
+    async fn create_table<T>(_connection: &Self::Connection) -> Result<(), crate::Error>
+    where
+        T: TableBuilder + QueryBuilderTrait + Sized + Serialize + DeserializeOwned,
+    {
+        Err(read_only_error(
+            format!("Unable to create table `{}`", T::table_name()),
+            "create_table",
+        ))
+    }
+
+    async fn row_count(connection: &Self::Connection, query: Query) -> Result<i64, crate::Error> {
+        ensure_read_only(&query, "row_count")?;
+        C::row_count(&connection.0, query).await
+    }
+
+    async fn execute(connection: &Self::Connection, query: Query) -> Result<(), crate::Error> {
+        ensure_read_only(&query, "execute")?;
+        C::execute(&connection.0, query).await
+    }
+
+    async fn execute_affected(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<u64, crate::Error> {
+        ensure_read_only(&query, "execute_affected")?;
+        C::execute_affected(&connection.0, query).await
+    }
+
+    async fn batch(connection: &Self::Connection, query: Query) -> Result<(), crate::Error> {
+        ensure_read_only(&query, "batch")?;
+        C::batch(&connection.0, query).await
+    }
+
+    async fn query<T>(connection: &Self::Connection, query: Query) -> Result<Vec<T>, crate::Error>
+    where
+        T: DeserializeOwned,
+    {
+        ensure_read_only(&query, "query")?;
+        C::query::<T>(&connection.0, query).await
+    }
+
+    async fn query_first<T>(connection: &Self::Connection, query: Query) -> Result<T, crate::Error>
+    where
+        T: DeserializeOwned,
+    {
+        ensure_read_only(&query, "query_first")?;
+        C::query_first::<T>(&connection.0, query).await
+    }
+
+    async fn query_raw(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<Vec<HashMap<String, Value>>, crate::Error> {
+        ensure_read_only(&query, "query_raw")?;
+        C::query_raw(&connection.0, query).await
+    }
+
+    async fn query_scalar<V>(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<Option<V>, crate::Error>
+    where
+        V: TryFrom<Value>,
+        V::Error: std::fmt::Display,
+    {
+        ensure_read_only(&query, "query_scalar")?;
+        C::query_scalar::<V>(&connection.0, query).await
+    }
+
+    async fn query_tuple<T>(
+        connection: &Self::Connection,
+        query: Query,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        T: DeserializeOwned,
+    {
+        ensure_read_only(&query, "query_tuple")?;
+        C::query_tuple::<T>(&connection.0, query).await
+    }
+
+    async fn savepoint(connection: &Self::Connection, name: &str) -> Result<(), crate::Error> {
+        C::savepoint(&connection.0, name).await
+    }
+
+    async fn release(connection: &Self::Connection, name: &str) -> Result<(), crate::Error> {
+        C::release(&connection.0, name).await
+    }
+
+    async fn rollback_to(connection: &Self::Connection, name: &str) -> Result<(), crate::Error> {
+        C::rollback_to(&connection.0, name).await
+    }
+
+    #[cfg(feature = "migrations")]
+    async fn pragma_info(
+        connection: &Self::Connection,
+        table: &str,
+    ) -> Result<Vec<crate::backends::TableInfo>, crate::Error> {
+        C::pragma_info(&connection.0, table).await
+    }
+
+    #[cfg(feature = "migrations")]
+    async fn index_info(
+        connection: &Self::Connection,
+        table: &str,
+    ) -> Result<Vec<crate::backends::IndexInfo>, crate::Error> {
+        C::index_info(&connection.0, table).await
+    }
+}
+
+impl<C> GeekQueryMap for ReadOnly<C>
+where
+    C: GeekQueryMap<Connection = C>,
+{
+    type Row<'r> = C::Row<'r>;
+
+    async fn query_map<T, F>(
+        connection: &Self::Connection,
+        query: Query,
+        f: F,
+    ) -> Result<Vec<T>, crate::Error>
+    where
+        F: for<'r> Fn(&Self::Row<'r>) -> Result<T, crate::Error>,
+    {
+        ensure_read_only(&query, "query_map")?;
+        C::query_map::<T, F>(&connection.0, query, f).await
+    }
+}
+
+/// Make sure a query can not write to the database
+///
+/// Select and raw queries are only allowed if every statement is a `SELECT`,
+/// `EXPLAIN` or a `PRAGMA` without an argument.
+fn ensure_read_only(query: &Query, function: &str) -> Result<(), crate::Error> {
+    let allowed = match query.query_type {
+        QueryType::Select | QueryType::Raw => is_read_only_sql(query.to_str()),
+        _ => false,
+    };
+    if allowed {
+        Ok(())
+    } else {
+        Err(read_only_error(
+            format!("Unable to run `{:?}` query", query.query_type),
+            function,
+        ))
+    }
+}
+
+/// Check if all the statements in raw SQL are read-only
+fn is_read_only_sql(sql: &str) -> bool {
+    let mut statements = sql
+        .split(';')
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty())
+        .peekable();
+    if statements.peek().is_none() {
+        return false;
+    }
+
+    statements.all(|statement| {
+        let keyword = statement
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        match keyword.as_str() {
+            "SELECT" | "EXPLAIN" => true,
+            // `PRAGMA name = value` and `PRAGMA name(value)` can both set a value
+            "PRAGMA" => !statement.contains(['=', '(']),
+            _ => false,
+        }
+    })
+}
+
+fn read_only_error(message: String, function: &str) -> crate::Error {
+    crate::Error::QueryBuilderError(
+        format!("{} on a read-only connection", message),
+        function.to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_sql() {
+        assert!(is_read_only_sql("SELECT * FROM Users;"));
+        assert!(is_read_only_sql("select name from sqlite_master"));
+        assert!(is_read_only_sql("PRAGMA user_version;"));
+        assert!(is_read_only_sql("EXPLAIN QUERY PLAN SELECT 1;"));
+
+        assert!(!is_read_only_sql(""));
+        assert!(!is_read_only_sql("PRAGMA foreign_keys = OFF;"));
+        assert!(!is_read_only_sql("PRAGMA table_info(Users);"));
+        assert!(!is_read_only_sql("PRAGMA journal_mode(WAL);"));
+        assert!(!is_read_only_sql("PRAGMA user_version(5);"));
+        assert!(!is_read_only_sql("SELECT 1; DROP TABLE Users;"));
+        assert!(!is_read_only_sql("DELETE FROM Users;"));
+        assert!(!is_read_only_sql("SAVEPOINT sp;"));
+        // Conservative: CTEs and literals containing `;` are rejected
+        assert!(!is_read_only_sql(
+            "WITH names AS (SELECT 1) SELECT * FROM names;"
+        ));
+        assert!(!is_read_only_sql("SELECT * FROM Users WHERE name = 'a;b';"));
+    }
+
+    #[test]
+    fn test_ensure_read_only() {
+        let select = Query::raw(QueryType::Select, "SELECT * FROM Users;");
+        assert!(ensure_read_only(&select, "query").is_ok());
+
+        // The SQL of select queries is checked too
+        let select = Query::raw(QueryType::Select, "DROP TABLE Users;");
+        assert!(ensure_read_only(&select, "query").is_err());
+        let select = Query::raw(QueryType::Select, "PRAGMA journal_mode(WAL);");
+        assert!(ensure_read_only(&select, "query").is_err());
+
+        for query_type in [
+            QueryType::Create,
+            QueryType::Insert,
+            QueryType::Update,
+            QueryType::Delete,
+        ] {
+            let query = Query::raw(query_type, "SELECT 1;");
+            let error = ensure_read_only(&query, "execute").unwrap_err();
+            assert!(error.to_string().contains("read-only connection"));
+        }
+
+        let raw = Query::raw(
+            QueryType::Raw,
+            "INSERT INTO Users (username) VALUES ('bob');",
+        );
+        assert!(ensure_read_only(&raw, "batch").is_err());
+    }
+}
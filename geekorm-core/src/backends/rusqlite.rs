@@ -37,7 +37,7 @@ use log::debug;
 use rusqlite::ToSql;
 use serde_rusqlite::*;
 
-use super::GeekConnection;
+use super::{GeekConnection, GeekQueryMap};
 
 impl GeekConnection for rusqlite::Connection {
     type Connection = rusqlite::Connection;
@@ -50,6 +50,8 @@ impl GeekConnection for rusqlite::Connection {
             + serde::Serialize
             + serde::de::DeserializeOwned,
     {
+        T::table().try_primary_key()?;
+
         let query = T::query_create().build()?;
         #[cfg(feature = "log")]
         {
@@ -57,7 +59,17 @@ impl GeekConnection for rusqlite::Connection {
         }
         connection
             .execute(query.to_str(), ())
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
+            .map_err(|e| query_error(e, &query.query))?;
+
+        for index in T::table().on_create_indexes() {
+            #[cfg(feature = "log")]
+            {
+                debug!("Create Index Query :: {:?}", index);
+            }
+            connection
+                .execute(index.as_str(), ())
+                .map_err(|e| query_error(e, &index))?;
+        }
         Ok(())
     }
 
@@ -74,12 +86,12 @@ impl GeekConnection for rusqlite::Connection {
         }
         let mut statement = connection
             .prepare(query.to_str())
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
+            .map_err(|e| query_error(e, &query.query))?;
 
         let params = if !query.parameters.values.is_empty() {
-            rusqlite::params_from_iter(query.parameters.into_iter())
+            rusqlite::params_from_iter(query.parameters)
         } else {
-            rusqlite::params_from_iter(query.values.into_iter())
+            rusqlite::params_from_iter(query.values)
         };
         #[cfg(feature = "log")]
         {
@@ -91,7 +103,7 @@ impl GeekConnection for rusqlite::Connection {
         let mut res = from_rows::<T>(
             statement
                 .query(params)
-                .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?,
+                .map_err(|e| query_error(e, &query.query))?,
         );
         while let Some(Ok(row)) = res.next() {
             results.push(row);
@@ -100,6 +112,17 @@ impl GeekConnection for rusqlite::Connection {
         Ok(results)
     }
 
+    async fn query_tuple<T>(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> std::result::Result<Vec<T>, crate::Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        // `serde_rusqlite` deserializes tuples (sequences) by column position
+        Self::query::<T>(connection, query).await
+    }
+
     async fn query_first<T>(
         connection: &Self::Connection,
         query: crate::Query,
@@ -113,12 +136,12 @@ impl GeekConnection for rusqlite::Connection {
         }
         let mut statement = connection
             .prepare(query.to_str())
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
+            .map_err(|e| query_error(e, &query.query))?;
 
         let params = if !query.parameters.values.is_empty() {
-            rusqlite::params_from_iter(query.parameters.into_iter())
+            rusqlite::params_from_iter(query.parameters)
         } else {
-            rusqlite::params_from_iter(query.values.into_iter())
+            rusqlite::params_from_iter(query.values)
         };
         #[cfg(feature = "log")]
         {
@@ -128,12 +151,17 @@ impl GeekConnection for rusqlite::Connection {
         let mut res = from_rows::<T>(
             statement
                 .query(params)
-                .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?,
+                .map_err(|e| query_error(e, &query.query))?,
         );
 
         match res.next() {
             Some(Ok(row)) => Ok(row),
-            _ => Err(crate::Error::RuSQLiteError("No rows found".to_string())),
+            // Writes with `RETURNING` can violate constraints
+            Some(Err(serde_rusqlite::Error::Rusqlite(error))) => Err(map_error(error)),
+            _ => Err(crate::Error::RuSQLiteError {
+                error: String::from("No rows found"),
+                query: query.query.clone(),
+            }),
         }
     }
 
@@ -141,28 +169,78 @@ impl GeekConnection for rusqlite::Connection {
         connection: &Self::Connection,
         query: crate::Query,
     ) -> std::result::Result<(), crate::Error> {
+        Self::execute_affected(connection, query).await?;
+        Ok(())
+    }
+
+    async fn execute_affected(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> std::result::Result<u64, crate::Error> {
         #[cfg(feature = "log")]
         {
             debug!("Execute :: {:?}", query.to_str());
         }
+        super::ensure_not_select(&query)?;
         let mut statement = connection
             .prepare(query.to_str())
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
+            .map_err(|e| query_error(e, &query.query))?;
 
         let params = if !query.parameters.values.is_empty() {
-            rusqlite::params_from_iter(query.parameters.into_iter())
+            rusqlite::params_from_iter(query.parameters)
         } else {
-            rusqlite::params_from_iter(query.values.into_iter())
+            rusqlite::params_from_iter(query.values)
         };
         #[cfg(feature = "log")]
         {
             debug!("Execute Params :: {:?}", params);
         }
 
-        statement
-            .execute(params)
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
-        Ok(())
+        let affected = statement.execute(params).map_err(map_error)?;
+        Ok(affected as u64)
+    }
+
+    async fn query_scalar<V>(
+        connection: &Self::Connection,
+        query: crate::Query,
+    ) -> std::result::Result<Option<V>, crate::Error>
+    where
+        V: TryFrom<crate::Value>,
+        V::Error: std::fmt::Display,
+    {
+        #[cfg(feature = "log")]
+        {
+            debug!("Query Scalar :: {:?}", query.to_str());
+        }
+        let mut statement = connection
+            .prepare(query.to_str())
+            .map_err(|e| query_error(e, &query.query))?;
+
+        let params = if !query.parameters.values.is_empty() {
+            rusqlite::params_from_iter(query.parameters)
+        } else {
+            rusqlite::params_from_iter(query.values)
+        };
+        let mut rows = statement
+            .query(params)
+            .map_err(|e| query_error(e, &query.query))?;
+
+        let value: rusqlite::types::Value =
+            match rows.next().map_err(|e| query_error(e, &query.query))? {
+                Some(row) => row.get(0).map_err(|e| query_error(e, &query.query))?,
+                None => return Ok(None),
+            };
+
+        let value = match value {
+            rusqlite::types::Value::Null => return Ok(None),
+            rusqlite::types::Value::Integer(value) => crate::Value::Integer(value),
+            rusqlite::types::Value::Real(value) => crate::Value::Text(value.to_string()),
+            rusqlite::types::Value::Text(value) => crate::Value::Text(value),
+            rusqlite::types::Value::Blob(value) => crate::Value::Blob(value),
+        };
+        V::try_from(value)
+            .map(Some)
+            .map_err(|e| crate::Error::SerdeError(e.to_string()))
     }
 
     async fn batch(
@@ -175,7 +253,7 @@ impl GeekConnection for rusqlite::Connection {
         }
         connection
             .execute_batch(query.query.as_str())
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
+            .map_err(map_error)?;
 
         Ok(())
     }
@@ -186,18 +264,100 @@ impl GeekConnection for rusqlite::Connection {
     ) -> std::result::Result<i64, crate::Error> {
         let mut statement = connection
             .prepare(query.to_str())
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
-        let params = rusqlite::params_from_iter(query.parameters.into_iter());
+            .map_err(|e| query_error(e, &query.query))?;
+        let params = rusqlite::params_from_iter(query.parameters);
         let mut res = statement
             .query(params)
-            .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?;
+            .map_err(|e| query_error(e, &query.query))?;
 
         match res.next() {
-            Ok(Some(row)) => Ok(row
-                .get(0)
-                .map_err(|e| crate::Error::RuSQLiteError(e.to_string()))?),
-            _ => Err(crate::Error::RuSQLiteError("No rows found".to_string())),
+            Ok(Some(row)) => Ok(row.get(0).map_err(|e| query_error(e, &query.query))?),
+            _ => Err(crate::Error::RuSQLiteError {
+                error: String::from("No rows found"),
+                query: query.query.clone(),
+            }),
+        }
+    }
+}
+
+impl GeekQueryMap for rusqlite::Connection {
+    type Row<'r> = rusqlite::Row<'r>;
+
+    async fn query_map<T, F>(
+        connection: &Self::Connection,
+        query: crate::Query,
+        f: F,
+    ) -> std::result::Result<Vec<T>, crate::Error>
+    where
+        F: for<'r> Fn(&Self::Row<'r>) -> std::result::Result<T, crate::Error>,
+    {
+        #[cfg(feature = "log")]
+        {
+            debug!("Query Map :: {:?}", query.to_str());
+        }
+        let mut statement = connection
+            .prepare(query.to_str())
+            .map_err(|e| query_error(e, &query.query))?;
+
+        let params = if !query.parameters.values.is_empty() {
+            rusqlite::params_from_iter(query.parameters)
+        } else {
+            rusqlite::params_from_iter(query.values)
+        };
+        let mut rows = statement
+            .query(params)
+            .map_err(|e| query_error(e, &query.query))?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| query_error(e, &query.query))? {
+            results.push(f(row)?);
+        }
+        Ok(results)
+    }
+}
+
+/// Convert a RuSQLite error, detecting unique constraint violations
+fn map_error(error: rusqlite::Error) -> crate::Error {
+    if let rusqlite::Error::SqliteFailure(ref failure, ref message) = error {
+        if failure.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE {
+            return message
+                .as_deref()
+                .and_then(crate::Error::unique_violation)
+                .unwrap_or(crate::Error::UniqueViolation { column: None });
+        }
+    }
+    match error {
+        // Keep the SQL out of the message (see `Error::redacted`)
+        rusqlite::Error::SqlInputError {
+            msg, sql, offset, ..
+        } => crate::Error::RuSQLiteError {
+            error: format!("{} at offset {}", msg, offset),
+            query: sql,
+        },
+        error => crate::Error::RuSQLiteError {
+            error: error.to_string(),
+            query: String::new(),
+        },
+    }
+}
+
+/// Convert a RuSQLite error raised while running a query
+fn query_error(error: rusqlite::Error, query: &str) -> crate::Error {
+    match map_error(error) {
+        crate::Error::RuSQLiteError { error, query: sql } if sql.is_empty() => {
+            crate::Error::RuSQLiteError {
+                error,
+                query: query.to_string(),
+            }
         }
+        error => error,
+    }
+}
+
+/// Convert RuSQLite Error to GeekORM Error
+impl From<rusqlite::Error> for crate::Error {
+    fn from(value: rusqlite::Error) -> Self {
+        map_error(value)
     }
 }
 
@@ -225,3 +385,78 @@ impl ToSql for crate::Value {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::GeekConnection;
+    use crate::builder::models::QueryType;
+
+    #[tokio::test]
+    async fn test_query_error_keeps_sql_out_of_the_message() {
+        let connection = rusqlite::Connection::open_in_memory().unwrap();
+
+        for sql in [
+            "SELECT * FORM Users WHERE username = 'geekmasher';",
+            "SELECT * FROM Users WHERE username = 'geekmasher';",
+        ] {
+            let query = crate::Query::raw(QueryType::Select, sql);
+            let error = rusqlite::Connection::query::<serde_json::Value>(&connection, query)
+                .await
+                .unwrap_err();
+            assert!(matches!(error, crate::Error::RuSQLiteError { .. }));
+            assert!(!error.to_string().contains("geekmasher"));
+            assert!(!error.redacted().to_string().contains("geekmasher"));
+            assert_eq!(error.query(), Some(sql));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_integer_enum_round_trip() {
+        use geekorm::prelude::*;
+
+        #[derive(Data, Debug, Default, Clone, PartialEq)]
+        #[geekorm(integer)]
+        #[repr(u8)]
+        enum Priority {
+            #[default]
+            Low,
+            Medium,
+            High = 10,
+        }
+
+        #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+        struct Tasks {
+            #[geekorm(primary_key, auto_increment)]
+            id: PrimaryKeyInteger,
+            name: String,
+            priority: Priority,
+            fallback: Option<Priority>,
+        }
+
+        let connection = rusqlite::Connection::open_in_memory().unwrap();
+        Tasks::create_table(&connection).await.unwrap();
+
+        let mut task = Tasks::new("deploy", Priority::High);
+        task.fallback = Some(Priority::Medium);
+        task.save(&connection).await.unwrap();
+
+        // The discriminant is stored in the column
+        let stored: i64 = connection
+            .query_row("SELECT priority FROM Tasks WHERE id = 1;", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(stored, 10);
+
+        let task = Tasks::fetch_by_primary_key(&connection, task.id)
+            .await
+            .unwrap();
+        assert_eq!(task.priority, Priority::High);
+        assert_eq!(task.fallback, Some(Priority::Medium));
+
+        let tasks = Tasks::fetch_by_priority(&connection, Priority::High)
+            .await
+            .unwrap();
+        assert_eq!(tasks.len(), 1);
+    }
+}
@@ -16,6 +16,8 @@ pub enum AlterMode {
     RenameColumn,
     /// Drop a column
     DropColumn,
+    /// Add an index on a column
+    AddIndex,
 
     /// Skip
     Skip,
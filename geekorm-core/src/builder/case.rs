@@ -0,0 +1,88 @@
+//! # CASE expression builder
+//!
+//! Build `CASE WHEN ... THEN ... ELSE ... END` expressions for the select list.
+use super::models::QueryCondition;
+use super::quote_identifier;
+use super::values::{Value, Values};
+use crate::{Table, ToSqlite};
+
+/// Builder for `CASE WHEN` expressions
+///
+/// All the values (conditions and results) are bound as parameters,
+/// see `QueryBuilder::select_case` for an example.
+#[derive(Debug, Clone, Default)]
+pub struct CaseBuilder {
+    /// When branches (column, condition, value, result)
+    whens: Vec<(String, QueryCondition, Value, Value)>,
+    /// The `ELSE` result
+    otherwise: Option<Value>,
+}
+
+impl CaseBuilder {
+    /// Create a new CASE expression builder
+    pub fn new() -> Self {
+        CaseBuilder::default()
+    }
+
+    /// Add a `WHEN column <condition> value THEN result` branch
+    pub fn when(
+        mut self,
+        column: &str,
+        condition: QueryCondition,
+        value: impl Into<Value>,
+        result: impl Into<Value>,
+    ) -> Self {
+        self.whens
+            .push((column.to_string(), condition, value.into(), result.into()));
+        self
+    }
+
+    /// Set the `ELSE` result (defaults to `NULL` if not set)
+    pub fn otherwise(mut self, result: impl Into<Value>) -> Self {
+        self.otherwise = Some(result.into());
+        self
+    }
+
+    /// Build the CASE expression and its parameters for a table
+    pub(crate) fn build(
+        &self,
+        table: &Table,
+        alias: &str,
+    ) -> Result<(String, Values), crate::Error> {
+        if self.whens.is_empty() {
+            return Err(crate::Error::QueryBuilderError(
+                String::from("CASE expression requires at least one `when` branch"),
+                String::from("select_case"),
+            ));
+        }
+
+        let mut expr = String::from("CASE");
+        let mut values = Values::new();
+
+        for (column, condition, value, result) in self.whens.iter() {
+            if !table.is_valid_column(column) {
+                return Err(crate::Error::QueryBuilderError(
+                    format!(
+                        "Column `{}` does not exist in table `{}`",
+                        column, table.name
+                    ),
+                    String::from("select_case"),
+                ));
+            }
+            expr.push_str(&format!(
+                " WHEN {} {} ? THEN ?",
+                quote_identifier(column),
+                condition.to_sqlite()
+            ));
+            values.push(column.clone(), value.clone());
+            values.push(alias.to_string(), result.clone());
+        }
+        if let Some(otherwise) = &self.otherwise {
+            expr.push_str(" ELSE ?");
+            values.push(alias.to_string(), otherwise.clone());
+        }
+        expr.push_str(" END");
+
+        Ok((expr, values))
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "migrations")]
 use super::alter::{AlterMode, AlterQuery};
-use crate::{ColumnType, ToSqlite};
+use super::{push_identifier, quote_identifier};
+use crate::{backends::TableInfo, ColumnType, ColumnTypeOptions, ToSqlite};
 use serde::{Deserialize, Serialize};
 
 /// A list of columns in a table
@@ -18,16 +19,31 @@ impl Columns {
         }
     }
 
-    /// Validate if a column exists
+    /// Validate if a column exists (by name or by alias)
     pub fn is_valid_column(&self, column: &str) -> bool {
         for col in &self.columns {
-            if col.name == column {
+            if col.name == column || (!col.alias.is_empty() && col.alias == column) {
                 return true;
             }
         }
         false
     }
 
+    /// Get the name of a column in the database (the alias if the column is renamed)
+    ///
+    /// Unknown columns are returned as is.
+    pub fn column_name(&self, column: &str) -> String {
+        match self
+            .columns
+            .iter()
+            .find(|col| col.name == column || (!col.alias.is_empty() && col.alias == column))
+        {
+            Some(col) if !col.alias.is_empty() => col.alias.clone(),
+            Some(col) => col.name.clone(),
+            None => column.to_string(),
+        }
+    }
+
     /// Get the Primary Key column of a table
     pub fn get_primary_key(&self) -> Option<Column> {
         self.columns
@@ -60,6 +76,131 @@ impl Columns {
     pub fn is_empty(&self) -> bool {
         self.columns.is_empty()
     }
+
+    /// Compare the columns with another set of columns
+    ///
+    /// `self` is the current schema and `other` is the expected schema, so
+    /// added columns only exist in `other` and removed columns only exist in `self`.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct UsersV2 {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: Option<String>,
+    ///     pub email: String,
+    /// }
+    ///
+    /// let diff = Users::table().columns.diff(&UsersV2::table().columns);
+    /// # assert_eq!(diff.added, vec!["email"]);
+    /// # assert_eq!(diff.removed, vec!["age"]);
+    /// # assert_eq!(diff.changed.len(), 1);
+    /// # assert_eq!(diff.changed[0].column, "username");
+    /// # assert_eq!(diff.changed[0].feature, "not-null");
+    /// ```
+    pub fn diff(&self, other: &Columns) -> ColumnDiff {
+        let mut diff = ColumnDiff::default();
+
+        for column in &self.columns {
+            match other.columns.iter().find(|c| c.name == column.name) {
+                Some(ocolumn) => {
+                    if column.column_type.sql_type() != ocolumn.column_type.sql_type() {
+                        diff.changed.push(ColumnChange::new(&column.name, "type"));
+                    }
+                    if column.is_primary_key() != ocolumn.is_primary_key() {
+                        diff.changed
+                            .push(ColumnChange::new(&column.name, "primary-key"));
+                    }
+                    if column.is_not_null() != ocolumn.is_not_null() {
+                        diff.changed
+                            .push(ColumnChange::new(&column.name, "not-null"));
+                    }
+                }
+                None => diff.removed.push(column.name.clone()),
+            }
+        }
+        for column in &other.columns {
+            if !self.columns.iter().any(|c| c.name == column.name) {
+                diff.added.push(column.name.clone());
+            }
+        }
+
+        diff
+    }
+
+    /// Write the `WHERE` and `ORDER BY` clauses of a select query into a buffer
+    pub(crate) fn write_select(&self, query: &crate::QueryBuilder, buf: &mut String) {
+        // Support for WHERE
+        if !query.where_clause.is_empty() {
+            buf.push_str("WHERE ");
+            for (index, column) in query.where_clause.iter().enumerate() {
+                if index > 0 {
+                    buf.push(' ');
+                }
+                buf.push_str(column);
+            }
+        }
+        // Support for ORDER BY
+        if !query.order_by.is_empty() {
+            if !query.where_clause.is_empty() {
+                buf.push(' ');
+            }
+            buf.push_str("ORDER BY ");
+            for (index, (column, order)) in query.order_by.iter().enumerate() {
+                // TODO(geekmasher): Validate that the column exists in the table
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+                push_identifier(buf, column);
+                buf.push(' ');
+                buf.push_str(&order.to_sqlite());
+            }
+        }
+    }
+}
+
+/// Differences between two sets of columns (see [`Columns::diff`])
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnDiff {
+    /// Columns only in the expected schema
+    pub added: Vec<String>,
+    /// Columns only in the current schema
+    pub removed: Vec<String>,
+    /// Columns in both schemas with different properties
+    pub changed: Vec<ColumnChange>,
+}
+
+impl ColumnDiff {
+    /// Check if there are no differences
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// A change to a column present in both schemas
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnChange {
+    /// Name of the column
+    pub column: String,
+    /// The property that changed (`type`, `primary-key` or `not-null`)
+    pub feature: String,
+}
+
+impl ColumnChange {
+    fn new(column: &str, feature: &str) -> Self {
+        ColumnChange {
+            column: column.to_string(),
+            feature: feature.to_string(),
+        }
+    }
 }
 
 impl Iterator for Columns {
@@ -76,6 +217,27 @@ impl From<Vec<Column>> for Columns {
     }
 }
 
+/// Convert the live column information from the database into a Column
+impl From<&TableInfo> for Column {
+    fn from(info: &TableInfo) -> Self {
+        let options = ColumnTypeOptions {
+            primary_key: info.pk != 0,
+            not_null: info.notnull != 0,
+            ..Default::default()
+        };
+        let column_type = if info.coltype.eq_ignore_ascii_case("TEXT") {
+            ColumnType::Text(options)
+        } else if info.coltype.eq_ignore_ascii_case("BLOB") {
+            ColumnType::Blob(options)
+        } else if info.pk != 0 {
+            ColumnType::Identifier(options)
+        } else {
+            ColumnType::Integer(options)
+        };
+        Column::new(info.name.clone(), column_type)
+    }
+}
+
 #[cfg(feature = "migrations")]
 impl quote::ToTokens for Columns {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
@@ -118,9 +280,9 @@ impl ToSqlite for Columns {
 
             sql.push(format!(
                 "FOREIGN KEY ({parent}) REFERENCES {child}({child_column})",
-                parent = foreign_key.name,
-                child = ctable,
-                child_column = ccolumn
+                parent = quote_identifier(&foreign_key.name),
+                child = quote_identifier(ctable),
+                child_column = quote_identifier(ccolumn)
             ));
         }
 
@@ -129,25 +291,7 @@ impl ToSqlite for Columns {
 
     fn on_select(&self, query: &crate::QueryBuilder) -> Result<String, crate::Error> {
         let mut full_query = String::new();
-
-        // Support for WHERE
-        if !query.where_clause.is_empty() {
-            full_query.push_str("WHERE ");
-            for column in &query.where_clause {
-                full_query.push_str(column);
-                full_query.push(' ');
-            }
-        }
-        // Support for ORDER BY
-        let mut order_by = Vec::new();
-        if !query.order_by.is_empty() {
-            for (column, order) in &query.order_by {
-                // TODO(geekmasher): Validate that the column exists in the table
-                order_by.push(format!("{} {}", column, order.to_sqlite()));
-            }
-
-            full_query += format!("ORDER BY {}", order_by.join(", ")).as_str();
-        }
+        self.write_select(query, &mut full_query);
         Ok(full_query)
     }
 }
@@ -164,6 +308,9 @@ pub struct Column {
     pub alias: String,
     /// Metadata for the column
     pub skip: bool,
+    /// Column is managed by the database (selected but never inserted or updated)
+    #[serde(default)]
+    pub readonly: bool,
 }
 
 impl Column {
@@ -174,6 +321,7 @@ impl Column {
             column_type,
             alias: String::new(),
             skip: false,
+            readonly: false,
         }
     }
 
@@ -190,6 +338,82 @@ impl Column {
     pub fn is_unique(&self) -> bool {
         self.column_type.is_unique()
     }
+    /// Check if the column is read-only (never inserted or updated)
+    ///
+    /// Read-only columns are managed by the database (e.g. triggers or
+    /// generated columns) and are only ever selected.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     #[geekorm(readonly)]
+    ///     pub slug: Option<String>,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    /// #   assert!(Users::table().columns.get("slug").unwrap().is_readonly());
+    ///
+    ///     let mut user = Users::new("GeekMasher");
+    ///     user.save(&connection).await?;
+    ///     # assert_eq!(Users::query_insert(&user).to_str(), "INSERT INTO Users (username) VALUES (?);");
+    ///
+    ///     // The database manages the column
+    ///     connection.execute("UPDATE Users SET slug = lower(username);", [])?;
+    ///
+    ///     let mut user = Users::fetch_by_primary_key(&connection, user.id).await?;
+    ///     # assert_eq!(user.slug, Some(String::from("geekmasher")));
+    ///
+    ///     // Changes to read-only columns are never written
+    ///     user.slug = Some(String::from("changed"));
+    ///     user.update(&connection).await?;
+    ///
+    ///     let user = Users::fetch_by_primary_key(&connection, user.id).await?;
+    ///     # assert_eq!(user.slug, Some(String::from("geekmasher")));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Check if the column has an index (`#[geekorm(index)]`)
+    pub fn is_indexed(&self) -> bool {
+        !self.skip && self.column_type.options().index
+    }
+
+    /// Get the name of the column's index (`idx_{table}_{column}`)
+    pub fn index_name(&self, table: &str) -> String {
+        format!("idx_{}_{}", table, self.database_name())
+    }
+
+    /// Generate the `CREATE INDEX` statement for the column
+    pub fn on_create_index(&self, table: &str) -> String {
+        format!(
+            "CREATE INDEX IF NOT EXISTS {} ON {} ({});",
+            quote_identifier(&self.index_name(table)),
+            quote_identifier(table),
+            quote_identifier(self.database_name())
+        )
+    }
+
+    /// Name of the column in the database (the alias if the column is renamed)
+    fn database_name(&self) -> &str {
+        if self.alias.is_empty() {
+            &self.name
+        } else {
+            &self.alias
+        }
+    }
 }
 
 impl Default for Column {
@@ -199,6 +423,7 @@ impl Default for Column {
             column_type: ColumnType::Text(Default::default()),
             alias: String::new(),
             skip: false,
+            readonly: false,
         }
     }
 }
@@ -210,6 +435,7 @@ impl quote::ToTokens for Column {
         let coltype = &self.column_type;
         let alias = &self.alias;
         let skip = &self.skip;
+        let readonly = &self.readonly;
 
         tokens.extend(quote::quote! {
             geekorm::Column {
@@ -217,6 +443,7 @@ impl quote::ToTokens for Column {
                 column_type: #coltype,
                 alias: String::from(#alias),
                 skip: #skip,
+                readonly: #readonly,
             }
         });
     }
@@ -229,43 +456,51 @@ impl ToSqlite for Column {
         }
 
         let name = if !&self.alias.is_empty() {
-            self.alias.clone()
+            quote_identifier(&self.alias)
         } else {
-            self.name.clone()
+            quote_identifier(&self.name)
         };
-        Ok(format!("{} {}", name, self.column_type.on_create(query)?))
+        let mut sql = format!("{} {}", name, self.column_type.on_create(query)?);
+        if let Some(check) = self.column_type.options().on_check(&name) {
+            sql.push(' ');
+            sql.push_str(&check);
+        }
+        Ok(sql)
     }
 
     #[cfg(feature = "migrations")]
     fn on_alter(&self, query: &AlterQuery) -> Result<String, crate::Error> {
+        let table = quote_identifier(&query.table);
+        let column = quote_identifier(&query.column);
+        let rename = quote_identifier(query.rename.as_ref().unwrap_or(&query.column));
         Ok(match query.mode {
             AlterMode::AddTable => {
-                format!("ALTER TABLE {} ADD COLUMN {};", query.table, query.column)
+                format!("ALTER TABLE {} ADD COLUMN {};", table, column)
             }
             AlterMode::RenameTable => {
-                format!("ALTER TABLE {} RENAME TO {};", query.table, query.column)
+                format!("ALTER TABLE {} RENAME TO {};", table, rename)
             }
             AlterMode::DropTable => {
-                format!("ALTER TABLE {} DROP COLUMN {};", query.table, query.column)
+                format!("DROP TABLE {};", table)
             }
             AlterMode::AddColumn => {
                 format!(
                     "ALTER TABLE {} ADD COLUMN {} {};",
-                    query.table,
-                    query.column,
+                    table,
+                    column,
                     self.column_type.on_alter(query)?
                 )
             }
             AlterMode::RenameColumn => {
                 format!(
                     "ALTER TABLE {} RENAME COLUMN {} TO {};",
-                    query.table,
-                    query.column,
-                    query.rename.as_ref().unwrap_or(&query.column)
+                    table, column, rename
                 )
             }
+            AlterMode::AddIndex => self.on_create_index(&query.table),
             AlterMode::DropColumn => {
-                format!("ALTER TABLE {} DROP COLUMN {};", query.table, query.column)
+                // Requires SQLite 3.35.0 or newer
+                format!("ALTER TABLE {} DROP COLUMN {};", table, column)
             }
             AlterMode::Skip => {
                 if query.column.is_empty() {
@@ -286,6 +521,32 @@ mod tests {
     use super::*;
     use crate::ColumnTypeOptions;
 
+    #[test]
+    fn test_valid_column_alias() {
+        let columns = Columns::from(vec![
+            Column::new(
+                String::from("id"),
+                ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+            ),
+            Column {
+                name: String::from("name"),
+                column_type: ColumnType::Text(ColumnTypeOptions::default()),
+                alias: String::from("full_name"),
+                ..Default::default()
+            },
+        ]);
+
+        assert!(columns.is_valid_column("name"));
+        assert!(columns.is_valid_column("full_name"));
+        assert!(!columns.is_valid_column("missing"));
+        // Columns without an alias do not match an empty name
+        assert!(!columns.is_valid_column(""));
+
+        assert_eq!(columns.column_name("name"), "full_name");
+        assert_eq!(columns.column_name("full_name"), "full_name");
+        assert_eq!(columns.column_name("id"), "id");
+    }
+
     fn create_table() -> crate::Table {
         crate::Table {
             name: String::from("users"),
@@ -333,6 +594,20 @@ mod tests {
             ..Default::default()
         };
         assert_eq!(column.on_create(&query).unwrap(), "user_id INTEGER");
+
+        // Length constraints
+        let column = Column::new(
+            String::from("username"),
+            ColumnType::Text(ColumnTypeOptions {
+                not_null: true,
+                max_length: Some(255),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            column.on_create(&query).unwrap(),
+            "username TEXT NOT NULL CHECK(length(username) <= 255)"
+        );
     }
 
     #[test]
@@ -354,7 +629,7 @@ mod tests {
         );
         assert_eq!(
             column.on_alter(&query).unwrap(),
-            "ALTER TABLE Table ADD COLUMN colname TEXT;"
+            "ALTER TABLE \"Table\" ADD COLUMN colname TEXT;"
         );
         let column = Column::new(
             String::from("name"),
@@ -365,7 +640,117 @@ mod tests {
         );
         assert_eq!(
             column.on_alter(&query).unwrap(),
-            "ALTER TABLE Table ADD COLUMN colname TEXT NOT NULL DEFAULT '';"
+            "ALTER TABLE \"Table\" ADD COLUMN colname TEXT NOT NULL DEFAULT '';"
         );
+
+        let column = Column::new(
+            String::from("image_id"),
+            ColumnType::ForeignKey(ColumnTypeOptions::foreign_key(String::from("images.id"))),
+        );
+        assert_eq!(
+            column.on_alter(&query).unwrap(),
+            "ALTER TABLE \"Table\" ADD COLUMN colname INTEGER REFERENCES images(id);"
+        );
+    }
+
+    #[test]
+    fn test_alter_rename_column() {
+        let mut query = crate::AlterQuery::new(AlterMode::RenameColumn, "Table", "name");
+        query.rename("full_name");
+
+        let column = Column::new(
+            String::from("full_name"),
+            ColumnType::Text(ColumnTypeOptions::default()),
+        );
+        assert_eq!(
+            column.on_alter(&query).unwrap(),
+            "ALTER TABLE \"Table\" RENAME COLUMN name TO full_name;"
+        );
+    }
+
+    #[test]
+    fn test_alter_drop_column() {
+        let query = crate::AlterQuery::new(AlterMode::DropColumn, "Table", "colname");
+
+        let column = Column::new(
+            String::from("colname"),
+            ColumnType::Text(ColumnTypeOptions::default()),
+        );
+        assert_eq!(
+            column.on_alter(&query).unwrap(),
+            "ALTER TABLE \"Table\" DROP COLUMN colname;"
+        );
+    }
+
+    fn columns(columns: Vec<(&str, ColumnType)>) -> Columns {
+        Columns::from(
+            columns
+                .into_iter()
+                .map(|(name, coltype)| Column::new(name.to_string(), coltype))
+                .collect::<Vec<Column>>(),
+        )
+    }
+
+    #[test]
+    fn test_columns_diff() {
+        let current = columns(vec![
+            (
+                "id",
+                ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+            ),
+            (
+                "name",
+                ColumnType::Text(ColumnTypeOptions {
+                    not_null: true,
+                    ..Default::default()
+                }),
+            ),
+            ("age", ColumnType::Integer(ColumnTypeOptions::default())),
+            ("score", ColumnType::Integer(ColumnTypeOptions::default())),
+        ]);
+        let expected = columns(vec![
+            (
+                "id",
+                ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+            ),
+            ("name", ColumnType::Text(ColumnTypeOptions::null())),
+            ("score", ColumnType::Text(ColumnTypeOptions::default())),
+            ("email", ColumnType::Text(ColumnTypeOptions::default())),
+        ]);
+
+        assert!(current.diff(&current).is_empty());
+
+        let diff = current.diff(&expected);
+        assert_eq!(diff.added, vec!["email"]);
+        assert_eq!(diff.removed, vec!["age"]);
+        assert_eq!(
+            diff.changed,
+            vec![
+                ColumnChange::new("name", "not-null"),
+                ColumnChange::new("score", "type"),
+            ]
+        );
+
+        // Reversing the comparison swaps added and removed
+        let diff = expected.diff(&current);
+        assert_eq!(diff.added, vec!["age"]);
+        assert_eq!(diff.removed, vec!["email"]);
+    }
+
+    #[test]
+    fn test_columns_diff_primary_key() {
+        let current = columns(vec![(
+            "id",
+            ColumnType::Integer(ColumnTypeOptions::default()),
+        )]);
+        let expected = columns(vec![(
+            "id",
+            ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+        )]);
+
+        let diff = current.diff(&expected);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.changed, vec![ColumnChange::new("id", "primary-key")]);
     }
 }
@@ -118,15 +118,41 @@ impl ToSqlite for ColumnType {
         })
     }
 
+    /// Generate the column type and constraints for `ALTER TABLE ... ADD COLUMN`
+    ///
+    /// SQLite has a number of restrictions on added columns: `NOT NULL` columns
+    /// need a default value, `PRIMARY KEY` and `UNIQUE` constraints are not allowed,
+    /// and columns with a `REFERENCES` clause must default to `NULL`.
     #[cfg(feature = "migrations")]
-    fn on_alter(&self, _query: &AlterQuery) -> Result<String, crate::Error> {
+    fn on_alter(&self, query: &AlterQuery) -> Result<String, crate::Error> {
         match self {
+            ColumnType::Identifier(_) => Err(crate::Error::QueryBuilderError(
+                format!(
+                    "Primary key column `{}.{}` cannot be added with ALTER TABLE",
+                    query.table, query.column
+                ),
+                String::from("on_alter"),
+            )),
+            ColumnType::ForeignKey(opts) => {
+                let (ftable, fcolumn) = opts.foreign_key.split_once('.').ok_or_else(|| {
+                    crate::Error::QueryBuilderError(
+                        format!("Invalid foreign key `{}`", opts.foreign_key),
+                        String::from("on_alter"),
+                    )
+                })?;
+                Ok(format!("INTEGER REFERENCES {}({})", ftable, fcolumn))
+            }
             ColumnType::Text(opts) => {
-                if opts.not_null {
-                    Ok("TEXT NOT NULL DEFAULT ''".to_string())
+                let mut sql = if opts.not_null {
+                    "TEXT NOT NULL DEFAULT ''".to_string()
                 } else {
-                    Ok("TEXT".to_string())
+                    "TEXT".to_string()
+                };
+                if let Some(collate) = opts.on_collate() {
+                    sql.push(' ');
+                    sql.push_str(&collate);
                 }
+                Ok(sql)
             }
             ColumnType::Integer(opts) | ColumnType::Boolean(opts) => {
                 if opts.not_null {
@@ -142,7 +168,6 @@ impl ToSqlite for ColumnType {
                     Ok("BLOB".to_string())
                 }
             }
-            _ => Ok("BEANS".to_string()),
         }
     }
 }
@@ -150,14 +175,19 @@ impl ToSqlite for ColumnType {
 impl ColumnType {
     /// Check if the column type is a primary key
     pub fn is_primary_key(&self) -> bool {
-        matches!(self, ColumnType::Identifier(_))
+        match self {
+            ColumnType::Identifier(_) => true,
+            // String / Uuid primary keys (`TEXT PRIMARY KEY`)
+            ColumnType::Text(opts) | ColumnType::Integer(opts) => opts.primary_key,
+            _ => false,
+        }
     }
 
     /// Check if the column type is nullable
     pub fn is_not_null(&self) -> bool {
         match self {
             ColumnType::Identifier(_) => false,
-            ColumnType::ForeignKey(_) => false,
+            ColumnType::ForeignKey(opts) => opts.not_null,
             ColumnType::Text(opts) => opts.not_null,
             ColumnType::Integer(opts) => opts.not_null,
             ColumnType::Boolean(opts) => opts.not_null,
@@ -186,6 +216,30 @@ impl ColumnType {
         }
     }
 
+    /// Get the SQLite type name for the column type (e.g. `TEXT`, `INTEGER`)
+    pub fn sql_type(&self) -> &'static str {
+        match self {
+            ColumnType::Text(_) => "TEXT",
+            ColumnType::Blob(_) => "BLOB",
+            ColumnType::Identifier(_)
+            | ColumnType::ForeignKey(_)
+            | ColumnType::Integer(_)
+            | ColumnType::Boolean(_) => "INTEGER",
+        }
+    }
+
+    /// Get the options / properties of the column type
+    pub fn options(&self) -> &ColumnTypeOptions {
+        match self {
+            ColumnType::Identifier(opts)
+            | ColumnType::ForeignKey(opts)
+            | ColumnType::Text(opts)
+            | ColumnType::Integer(opts)
+            | ColumnType::Boolean(opts)
+            | ColumnType::Blob(opts) => opts,
+        }
+    }
+
     /// Check if the column type is a foreign key
     pub fn is_foreign_key(&self) -> bool {
         matches!(self, ColumnType::ForeignKey(_))
@@ -227,6 +281,115 @@ pub struct ColumnTypeOptions {
     pub not_null: bool,
     /// Auto increment the column
     pub auto_increment: bool,
+    /// Collating sequence for the column (`BINARY`, `NOCASE` or `RTRIM`)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(unique, collate = "nocase")]
+    ///     pub email: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher@example.com");
+    ///     user.save(&connection).await?;
+    ///
+    ///     // The unique constraint ignores the case of the value
+    ///     let mut duplicate = Users::new("GeekMasher@Example.com");
+    ///     assert!(duplicate.save(&connection).await.is_err());
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
+    /// Minimum length of the column value (`CHECK(length(column) >= n)`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Maximum length of the column value (`CHECK(length(column) <= n)`)
+    ///
+    /// Length constraints are also validated by `save` and `update` before
+    /// the query is sent to the database.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(min_length = 3, max_length = 16)]
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     # assert_eq!(
+    ///     #     Users::query_create().build()?.query,
+    ///     #     "CREATE TABLE IF NOT EXISTS Users (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT NOT NULL CHECK(length(username) >= 3 AND length(username) <= 16));"
+    ///     # );
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///
+    ///     // Over-length values fail validation before hitting the database
+    ///     let mut user = Users::new("a-very-long-username");
+    ///     let result = user.save(&connection).await;
+    ///     # assert!(matches!(result, Err(geekorm::Error::ValidationError(_))));
+    ///     # assert_eq!(Users::total(&connection).await?, 1);
+    ///
+    ///     // The CHECK constraint also protects against raw writes
+    ///     # assert!(connection.execute("INSERT INTO Users (username) VALUES ('ab');", []).is_err());
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+    /// Create an index on the column (`CREATE INDEX idx_{table}_{column}`)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(index)]
+    ///     pub age: i32,
+    /// }
+    ///
+    /// # assert_eq!(
+    /// #     Users::table().on_create_indexes(),
+    /// #     vec!["CREATE INDEX IF NOT EXISTS idx_Users_age ON Users (age);"]
+    /// # );
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     // The indexes are created with the table
+    ///     Users::create_table(&connection).await?;
+    ///     # let indexes = rusqlite::Connection::index_info(&connection, "Users").await?;
+    ///     # assert!(indexes.iter().any(|index| index.name == "idx_Users_age"));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub index: bool,
 }
 
 impl ColumnTypeOptions {
@@ -245,6 +408,7 @@ impl ColumnTypeOptions {
             unique: false,
             not_null: true,
             auto_increment: false,
+            ..Default::default()
         }
     }
 
@@ -261,6 +425,29 @@ impl ColumnTypeOptions {
             ..Default::default()
         }
     }
+
+    /// Generate the `COLLATE` clause for the column (if set)
+    pub(crate) fn on_collate(&self) -> Option<String> {
+        self.collation
+            .as_ref()
+            .map(|collation| format!("COLLATE {}", collation.to_uppercase()))
+    }
+
+    /// Generate the `CHECK` clause for the column length constraints (if set)
+    pub(crate) fn on_check(&self, column: &str) -> Option<String> {
+        let mut checks = Vec::new();
+        if let Some(min) = self.min_length {
+            checks.push(format!("length({}) >= {}", column, min));
+        }
+        if let Some(max) = self.max_length {
+            checks.push(format!("length({}) <= {}", column, max));
+        }
+        if checks.is_empty() {
+            None
+        } else {
+            Some(format!("CHECK({})", checks.join(" AND ")))
+        }
+    }
 }
 
 impl Display for ColumnTypeOptions {
@@ -280,6 +467,19 @@ impl quote::ToTokens for ColumnTypeOptions {
         let unique = &self.unique;
         let not_null = &self.not_null;
         let auto_increment = &self.auto_increment;
+        let index = &self.index;
+        let collation = match &self.collation {
+            Some(collation) => quote! { Some(String::from(#collation)) },
+            None => quote! { None },
+        };
+        let min_length = match &self.min_length {
+            Some(min) => quote! { Some(#min) },
+            None => quote! { None },
+        };
+        let max_length = match &self.max_length {
+            Some(max) => quote! { Some(#max) },
+            None => quote! { None },
+        };
 
         tokens.extend(quote! {
             geekorm::ColumnTypeOptions {
@@ -288,6 +488,10 @@ impl quote::ToTokens for ColumnTypeOptions {
                 not_null: #not_null,
                 foreign_key: String::from(#foreign_key),
                 auto_increment: #auto_increment,
+                collation: #collation,
+                min_length: #min_length,
+                max_length: #max_length,
+                index: #index,
             }
         });
     }
@@ -308,7 +512,14 @@ impl ToSqlite for ColumnTypeOptions {
         if self.auto_increment {
             sql.push("AUTOINCREMENT");
         }
-        Ok(sql.join(" "))
+        let mut sql = sql.join(" ");
+        if let Some(collate) = self.on_collate() {
+            if !sql.is_empty() {
+                sql.push(' ');
+            }
+            sql.push_str(&collate);
+        }
+        Ok(sql)
     }
 }
 
@@ -339,6 +550,24 @@ mod tests {
         assert_eq!(column_type.on_create(&query).unwrap(), "INTEGER");
     }
 
+    #[test]
+    fn test_column_type_text_primary_key() {
+        let query = query();
+        let column_type = ColumnType::Text(ColumnTypeOptions {
+            primary_key: true,
+            ..Default::default()
+        });
+        assert!(column_type.is_primary_key());
+        assert!(!column_type.is_auto_increment());
+        assert_eq!(column_type.on_create(&query).unwrap(), "TEXT PRIMARY KEY");
+
+        let column_type = ColumnType::Identifier(ColumnTypeOptions::primary_key());
+        assert_eq!(
+            column_type.on_create(&query).unwrap(),
+            "INTEGER PRIMARY KEY AUTOINCREMENT"
+        );
+    }
+
     #[test]
     fn test_column_type_options_to_sql() {
         let query = query();
@@ -365,6 +594,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_column_type_collation() {
+        let query = query();
+        let column_type = ColumnType::Text(ColumnTypeOptions {
+            collation: Some(String::from("nocase")),
+            ..Default::default()
+        });
+        assert_eq!(
+            column_type.on_create(&query).unwrap(),
+            "TEXT COLLATE NOCASE"
+        );
+
+        let column_type = ColumnType::Text(ColumnTypeOptions {
+            unique: true,
+            not_null: true,
+            collation: Some(String::from("nocase")),
+            ..Default::default()
+        });
+        assert_eq!(
+            column_type.on_create(&query).unwrap(),
+            "TEXT NOT NULL UNIQUE COLLATE NOCASE"
+        );
+    }
+
+    #[test]
+    fn test_column_type_length_check() {
+        let options = ColumnTypeOptions {
+            max_length: Some(255),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.on_check("name"),
+            Some(String::from("CHECK(length(name) <= 255)"))
+        );
+
+        let options = ColumnTypeOptions {
+            min_length: Some(3),
+            max_length: Some(32),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.on_check("name"),
+            Some(String::from(
+                "CHECK(length(name) >= 3 AND length(name) <= 32)"
+            ))
+        );
+        assert_eq!(ColumnTypeOptions::default().on_check("name"), None);
+    }
+
     #[test]
     fn test_alter_table_to_sql() {
         let query = crate::AlterQuery::new(AlterMode::AddColumn, "Table", "colname");
@@ -383,5 +661,39 @@ mod tests {
 
         let column_type = ColumnType::Integer(ColumnTypeOptions::default());
         assert_eq!(column_type.on_alter(&query).unwrap(), "INTEGER");
+
+        let column_type = ColumnType::Integer(ColumnTypeOptions {
+            not_null: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            column_type.on_alter(&query).unwrap(),
+            "INTEGER NOT NULL DEFAULT 0"
+        );
+
+        let column_type = ColumnType::Boolean(ColumnTypeOptions::default());
+        assert_eq!(column_type.on_alter(&query).unwrap(), "INTEGER");
+
+        let column_type = ColumnType::Blob(ColumnTypeOptions::default());
+        assert_eq!(column_type.on_alter(&query).unwrap(), "BLOB");
+
+        let column_type = ColumnType::Blob(ColumnTypeOptions {
+            not_null: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            column_type.on_alter(&query).unwrap(),
+            "BLOB NOT NULL DEFAULT ''"
+        );
+
+        let column_type =
+            ColumnType::ForeignKey(ColumnTypeOptions::foreign_key(String::from("Users.id")));
+        assert_eq!(
+            column_type.on_alter(&query).unwrap(),
+            "INTEGER REFERENCES Users(id)"
+        );
+
+        let column_type = ColumnType::Identifier(ColumnTypeOptions::primary_key());
+        assert!(column_type.on_alter(&query).is_err());
     }
 }
@@ -2,6 +2,7 @@
 use serde::{Deserialize, Serialize};
 
 use super::table::Table;
+use crate::{Query, QueryBuilder};
 
 /// GeekORM Database
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,8 +12,49 @@ pub struct Database {
 }
 
 impl Database {
+    /// All the tables registered with `#[derive(Table)]`
+    ///
+    /// Tables are registered by the derive, so every table of the crates linked
+    /// into the binary is included (sorted by name). Generic tables are not registered.
+    pub fn registered() -> Self {
+        let mut tables: Vec<Table> = inventory::iter::<TableRegistration>
+            .into_iter()
+            .map(|registration| (registration.table)())
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        Database { tables }
+    }
+
     /// Find a table by name
     pub fn get_table(&self, name: &str) -> Option<&Table> {
         self.tables.iter().find(|table| table.name == name)
     }
+
+    /// Build the `CREATE TABLE` queries for all the tables in the database
+    ///
+    /// Errors if a table does not have a primary key column.
+    pub fn create_queries(&self) -> Result<Vec<Query>, crate::Error> {
+        self.tables
+            .iter()
+            .map(|table| {
+                table.try_primary_key()?;
+                QueryBuilder::create().table(table.clone()).build()
+            })
+            .collect()
+    }
+}
+
+/// A table registered by `#[derive(Table)]` (used by `tables!()`)
+#[doc(hidden)]
+pub struct TableRegistration {
+    table: fn() -> Table,
+}
+
+impl TableRegistration {
+    /// Register a table
+    pub const fn new(table: fn() -> Table) -> Self {
+        TableRegistration { table }
+    }
 }
+
+inventory::collect!(TableRegistration);
@@ -1,3 +1,4 @@
+use crate::builder::quote_identifier;
 use crate::{Table, ToSqlite};
 
 /// Struct for joining tables
@@ -22,9 +23,17 @@ impl TableJoins {
 
     /// Get the join by name
     pub fn get(&self, name: &str) -> Option<&TableJoin> {
-        self.joins.iter().find(|join| match join {
-            TableJoin::InnerJoin(opts) => opts.child.name == name,
-        })
+        self.joins
+            .iter()
+            .find(|join| join.options().child.name == name)
+    }
+
+    /// Get the joined (child) tables
+    pub fn tables(&self) -> Vec<&Table> {
+        self.joins
+            .iter()
+            .map(|join| &join.options().child)
+            .collect()
     }
 
     /// Check if the joins are empty
@@ -35,20 +44,24 @@ impl TableJoins {
 
 impl ToSqlite for TableJoins {
     fn on_select(&self, query: &crate::QueryBuilder) -> Result<String, crate::Error> {
-        let mut full_query = String::new();
+        let mut joins = Vec::with_capacity(self.joins.len());
         for join in self.joins.iter() {
-            let sql = join.on_select(query)?;
-            full_query.push_str(sql.as_str());
+            joins.push(join.on_select(query)?);
         }
-        Ok(full_query)
+        Ok(joins.join(" "))
     }
 }
 
 /// Enum for joining tables
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum TableJoin {
     /// Inner Join
     InnerJoin(TableJoinOptions),
+    /// Left (Outer) Join
+    ///
+    /// Keeps the rows of the parent table where the child row is missing
+    LeftJoin(TableJoinOptions),
 }
 
 impl TableJoin {
@@ -57,12 +70,22 @@ impl TableJoin {
         TableJoin::InnerJoin(TableJoinOptions { parent, child })
     }
 
-    /// Check if a Table.Column is valid
-    pub fn is_valid_column(&self, column: &str) -> bool {
+    /// Create a new left join between two tables
+    pub fn left(parent: Table, child: Table) -> Self {
+        TableJoin::LeftJoin(TableJoinOptions { parent, child })
+    }
+
+    /// Get the options of the join
+    pub fn options(&self) -> &TableJoinOptions {
         match self {
-            TableJoin::InnerJoin(opts) => opts.parent.is_valid_column(column),
+            TableJoin::InnerJoin(opts) | TableJoin::LeftJoin(opts) => opts,
         }
     }
+
+    /// Check if a Table.Column is valid
+    pub fn is_valid_column(&self, column: &str) -> bool {
+        self.options().parent.is_valid_column(column)
+    }
 }
 
 impl ToSqlite for TableJoin {
@@ -70,7 +93,12 @@ impl ToSqlite for TableJoin {
         match self {
             TableJoin::InnerJoin(opts) => Ok(format!(
                 "INNER JOIN {} ON {}",
-                opts.child.name,
+                quote_identifier(&opts.child.name),
+                opts.on_select(qb)?
+            )),
+            TableJoin::LeftJoin(opts) => Ok(format!(
+                "LEFT JOIN {} ON {}",
+                quote_identifier(&opts.child.name),
                 opts.on_select(qb)?
             )),
         }
@@ -112,10 +140,10 @@ impl ToSqlite for TableJoinOptions {
 
         Ok(format!(
             "{ctable}.{ccolumn} = {ptable}.{pcolumn}",
-            ctable = self.child.name,
-            ccolumn = ccolumn,
-            ptable = self.parent.name,
-            pcolumn = pcolumn_name,
+            ctable = quote_identifier(&self.child.name),
+            ccolumn = quote_identifier(&ccolumn),
+            ptable = quote_identifier(&self.parent.name),
+            pcolumn = quote_identifier(&pcolumn_name),
         ))
     }
 }
@@ -181,6 +209,53 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_table_left_join_on_select() {
+        let join = TableJoin::left(
+            table_parent(String::from("Parent")),
+            table_child(String::from("Child")),
+        );
+
+        let select_query = join
+            .on_select(&crate::QueryBuilder::select())
+            .expect("Failed to generate select query");
+        assert_eq!(
+            select_query,
+            "LEFT JOIN Child ON Child.id = Parent.image_id"
+        );
+        assert_eq!(join.options().child.name, "Child");
+    }
+
+    #[test]
+    fn test_table_join_reserved_names() {
+        let join = TableJoin::new(
+            table_parent(String::from("Group")),
+            table_child(String::from("Child")),
+        );
+
+        let select_query = join
+            .on_select(&crate::QueryBuilder::select())
+            .expect("Failed to generate select query");
+        assert_eq!(
+            select_query,
+            "INNER JOIN Child ON Child.id = \"Group\".image_id"
+        );
+
+        // Reserved child table name in a left join
+        let mut parent = table_parent(String::from("Parent"));
+        parent.columns.columns[1].column_type = crate::ColumnType::ForeignKey(
+            crate::ColumnTypeOptions::foreign_key(String::from("Order.id")),
+        );
+        let join = TableJoin::left(parent, table_child(String::from("Order")));
+        let select_query = join
+            .on_select(&crate::QueryBuilder::select())
+            .expect("Failed to generate select query");
+        assert_eq!(
+            select_query,
+            "LEFT JOIN \"Order\" ON \"Order\".id = Parent.image_id"
+        );
+    }
+
     #[test]
     fn test_join_options() {
         let join = TableJoinOptions {
@@ -3,7 +3,7 @@ use std::fmt::{Debug, Display};
 
 use serde::{de::Visitor, Deserialize, Serialize, Serializer};
 
-use crate::{PrimaryKey, TableBuilder};
+use crate::{GeekConnection, PrimaryKey, TableBuilder, TablePrimaryKey, Value};
 
 /// Foreign Key Type
 ///
@@ -44,6 +44,124 @@ use crate::{PrimaryKey, TableBuilder};
 /// println!("User posts query: {:?}", user_posts);
 /// // ...
 /// ```
+///
+/// # Fetching by a foreign key
+///
+/// The `fetch_by_{field}` functions for foreign key and `Option<T>` columns are
+/// keyed on the inner value, so they can be called with the primary key of the
+/// parent row (or the raw key value).
+///
+/// ```rust
+/// # #[cfg(feature = "rusqlite")] {
+/// use geekorm::prelude::*;
+///
+/// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+/// struct Users {
+///     #[geekorm(primary_key, auto_increment)]
+///     id: PrimaryKeyInteger,
+///     name: String,
+/// }
+///
+/// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+/// struct Posts {
+///     #[geekorm(primary_key, auto_increment)]
+///     id: PrimaryKeyInteger,
+///     title: String,
+///     #[geekorm(foreign_key = "Users.id")]
+///     author: ForeignKey<u64, Users>,
+///     tag: Option<String>,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let connection = rusqlite::Connection::open_in_memory()?;
+///     Users::create_table(&connection).await?;
+///     Posts::create_table(&connection).await?;
+///
+///     let mut geekmasher = Users::new("geekmasher");
+///     geekmasher.save(&connection).await?;
+///     let mut other = Users::new("other");
+///     other.save(&connection).await?;
+///
+///     for (title, author) in [("First", &geekmasher), ("Second", &geekmasher), ("Third", &other)] {
+///         let mut post = Posts::new(title, author.id);
+///         post.tag = Some(String::from("rust"));
+///         post.save(&connection).await?;
+///     }
+///
+///     // Fetch all the posts by the author's primary key
+///     let posts = Posts::fetch_by_author(&connection, geekmasher.id).await?;
+///     # assert_eq!(posts.len(), 2);
+///     # assert!(posts.iter().all(|post| post.author.key == 1));
+///     # assert_eq!(Posts::fetch_by_author(&connection, 2).await?.len(), 1);
+///
+///     // Option<T> columns are fetched by the inner value
+///     let tagged = Posts::fetch_by_tag(&connection, "rust").await?;
+///     # assert_eq!(tagged.len(), 3);
+///     Ok(())
+/// }
+/// # }
+/// ```
+///
+/// # Optional foreign keys
+///
+/// Wrapping the foreign key in an `Option` makes the column nullable.
+/// Unset foreign keys are written as `NULL` and the `fetch_{field}` function
+/// returns `None` for them.
+///
+/// ```rust
+/// # #[cfg(feature = "rusqlite")] {
+/// use geekorm::prelude::*;
+///
+/// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+/// struct Users {
+///     #[geekorm(primary_key, auto_increment)]
+///     id: PrimaryKeyInteger,
+///     name: String,
+/// }
+///
+/// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+/// struct Posts {
+///     #[geekorm(primary_key, auto_increment)]
+///     id: PrimaryKeyInteger,
+///     title: String,
+///     /// Anonymous posts have no author
+///     #[geekorm(foreign_key = "Users.id")]
+///     author: Option<ForeignKey<u64, Users>>,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     # assert_eq!(
+///     #     Posts::query_create().build()?.to_str(),
+///     #     "CREATE TABLE IF NOT EXISTS Posts (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL, author INTEGER, FOREIGN KEY (author) REFERENCES Users(id));"
+///     # );
+///     let connection = rusqlite::Connection::open_in_memory()?;
+///     Users::create_table(&connection).await?;
+///     Posts::create_table(&connection).await?;
+///
+///     let mut user = Users::new("geekmasher");
+///     user.save(&connection).await?;
+///
+///     let mut post = Posts::new("Hello, World!");
+///     post.author = Some(ForeignKey::from(user.id));
+///     post.save(&connection).await?;
+///
+///     // Anonymous post (the author is written as NULL)
+///     let mut anonymous = Posts::new("Anonymous");
+///     anonymous.save(&connection).await?;
+///
+///     let mut post = Posts::fetch_by_primary_key(&connection, post.id).await?;
+///     let author = post.fetch_author(&connection).await?;
+///     # assert_eq!(author.map(|u| u.name), Some(String::from("geekmasher")));
+///
+///     let mut anonymous = Posts::fetch_by_primary_key(&connection, anonymous.id).await?;
+///     # assert!(anonymous.author.is_none());
+///     # assert!(anonymous.fetch_author(&connection).await?.is_none());
+///     Ok(())
+/// }
+/// # }
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct ForeignKey<T, D>
 where
@@ -54,6 +172,8 @@ where
     pub key: T,
     /// Foreign Key Data Type
     pub data: D,
+    /// If `data` has been loaded (or attached) for the key
+    loaded: bool,
 }
 
 /// Foreign Key as an Integer
@@ -97,6 +217,7 @@ where
         Self {
             key: Default::default(),
             data: Default::default(),
+            loaded: false,
         }
     }
 }
@@ -109,6 +230,7 @@ where
         Self {
             key: Default::default(),
             data: Default::default(),
+            loaded: false,
         }
     }
 }
@@ -121,6 +243,7 @@ where
         Self {
             key: Default::default(),
             data: Default::default(),
+            loaded: false,
         }
     }
 }
@@ -134,6 +257,7 @@ where
         Self {
             key: value,
             data: Default::default(),
+            loaded: false,
         }
     }
 }
@@ -147,6 +271,7 @@ where
         Self {
             key: value,
             data: Default::default(),
+            loaded: false,
         }
     }
 }
@@ -160,8 +285,163 @@ where
         Self {
             key: value,
             data: Default::default(),
+            loaded: false,
+        }
+    }
+}
+
+impl<T, D> ForeignKey<T, D>
+where
+    T: serde::Serialize + 'static,
+    D: TableBuilder,
+{
+    /// Set the parent row data for the foreign key and mark it as loaded
+    pub fn set_data(&mut self, data: D) {
+        self.data = data;
+        self.loaded = true;
+    }
+
+    /// Check if the parent row has been loaded (or attached)
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+}
+
+impl<T, D> ForeignKey<T, D>
+where
+    T: serde::Serialize + Clone + Into<Value> + 'static,
+    D: TableBuilder + TablePrimaryKey,
+{
+    /// Attach the parent row which is already loaded in memory
+    ///
+    /// The data is kept when the row is saved, so the parent row does not
+    /// need to be fetched again.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Posts {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub title: String,
+    ///     #[geekorm(foreign_key = "Users.id")]
+    ///     pub author: ForeignKeyInteger<Users>,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     Posts::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///
+    ///     let mut post = Posts::new("Hello, World!", user.id);
+    ///     post.author = post.author.with_data(user.clone());
+    ///     post.save(&connection).await?;
+    ///
+    ///     // The author is still available after saving the post
+    ///     # assert_eq!(post.id, PrimaryKeyInteger::from(1));
+    ///     # assert_eq!(post.author.get().map(|u| u.username.as_str()), Some("geekmasher"));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub fn with_data(mut self, data: D) -> Self {
+        self.set_data(data);
+        self
+    }
+
+    /// Get the parent row if it has been loaded (using `load` or the table `fetch` methods)
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// // The default data is not treated as loaded, even if the keys match
+    /// let author = ForeignKeyInteger::<Users>::new(0);
+    /// assert!(author.get().is_none());
+    ///
+    /// let author = author.with_data(Users::default());
+    /// assert!(author.get().is_some());
+    /// ```
+    pub fn get(&self) -> Option<&D> {
+        if self.loaded {
+            Some(&self.data)
+        } else {
+            None
         }
     }
+
+    /// Load the parent row from the database and store it in the foreign key
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Posts {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub title: String,
+    ///     #[geekorm(foreign_key = "Users.id")]
+    ///     pub author: ForeignKeyInteger<Users>,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     Posts::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///     let mut post = Posts::new("Hello, World!", user.id);
+    ///     post.save(&connection).await?;
+    ///
+    ///     // The author has not been loaded yet
+    ///     # assert!(post.author.get().is_none());
+    ///
+    ///     // Load the author of the post
+    ///     let author = post.author.load(&connection).await?;
+    ///     # assert_eq!(author.username, "geekmasher");
+    ///     # assert_eq!(post.author.get().map(|u| u.username.as_str()), Some("geekmasher"));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub async fn load<'a, C>(&mut self, connection: &'a C) -> Result<&D, crate::Error>
+    where
+        C: GeekConnection<Connection = C> + 'a,
+        D: serde::de::DeserializeOwned,
+    {
+        let query = D::query_select_by_primary_key(self.key.clone());
+        self.set_data(C::query_first::<D>(connection, query).await?);
+        Ok(&self.data)
+    }
 }
 
 impl<D> From<u64> for ForeignKey<u64, D>
@@ -52,6 +52,13 @@
 //! };
 //! # assert_eq!(user.id.clone(), PrimaryKey::from("1"));
 //! # assert_eq!(user.username.clone(), String::from("JohnDoe"));
+//!
+//! // String primary keys are created as `TEXT PRIMARY KEY` (no `AUTOINCREMENT`)
+//! let create = Users::query_create().build().expect("Failed to build query");
+//! # assert_eq!(
+//! #     create.to_str(),
+//! #     "CREATE TABLE IF NOT EXISTS Users (id TEXT PRIMARY KEY, username TEXT NOT NULL UNIQUE);"
+//! # );
 //! ```
 //!
 //! # Uuid Example
@@ -78,10 +85,19 @@
 //! };
 //! # assert_eq!(user.username.clone(), String::from("JohnDoe"));
 //! # assert_eq!(user.id.clone(), PrimaryKeyUuid::from(new_uuid));
+//!
+//! // Uuid primary keys are created as `TEXT PRIMARY KEY` (no `AUTOINCREMENT`)
+//! let create = Users::query_create().build().expect("Failed to build query");
+//! # assert_eq!(
+//! #     create.to_str(),
+//! #     "CREATE TABLE IF NOT EXISTS Users (id TEXT PRIMARY KEY, username TEXT NOT NULL UNIQUE);"
+//! # );
 //! ```
 //!
 use core::fmt;
+use std::borrow::Borrow;
 use std::fmt::{Debug, Display};
+use std::ops::Deref;
 
 use serde::{de::Visitor, Deserialize, Serialize, Serializer};
 #[cfg(feature = "uuid")]
@@ -154,11 +170,65 @@ where
     }
 }
 
+/// Dereference a Primary Key to its inner value
+///
+/// ```rust
+/// use geekorm::prelude::*;
+///
+/// fn lookup(id: &u64) -> u64 {
+///     *id
+/// }
+///
+/// let id = PrimaryKeyInteger::from(42);
+/// assert_eq!(lookup(&id), 42);
+/// assert_eq!(*id + 1, 43);
+/// ```
+impl<T> Deref for PrimaryKey<T>
+where
+    T: serde::Serialize + 'static,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> AsRef<T> for PrimaryKey<T>
+where
+    T: serde::Serialize + 'static,
+{
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Borrow<T> for PrimaryKey<T>
+where
+    T: serde::Serialize + 'static,
+{
+    fn borrow(&self) -> &T {
+        &self.value
+    }
+}
+
 impl PrimaryKey<u64> {
     /// Create a new primary key with an integer
     pub fn new(value: u64) -> Self {
         Self { value }
     }
+
+    /// Check if the primary key has been set (is not `0`)
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// # assert!(!PrimaryKeyInteger::default().is_set());
+    /// # assert!(PrimaryKeyInteger::from(1).is_set());
+    /// ```
+    pub fn is_set(&self) -> bool {
+        self.value != 0
+    }
 }
 
 impl PrimaryKey<String> {
@@ -166,6 +236,11 @@ impl PrimaryKey<String> {
     pub fn new(value: String) -> Self {
         Self { value }
     }
+
+    /// Check if the primary key has been set (is not an empty string)
+    pub fn is_set(&self) -> bool {
+        !self.value.is_empty()
+    }
 }
 
 /// Primary Key as an Integer (u64)
@@ -280,6 +355,14 @@ impl PrimaryKeyUuid {
     pub fn new(value: Uuid) -> Self {
         Self { value }
     }
+
+    /// Check if the primary key has been set (is not the nil Uuid)
+    ///
+    /// Note: [`PrimaryKeyUuid::default`] generates a new random Uuid,
+    /// so a default Uuid primary key is always set.
+    pub fn is_set(&self) -> bool {
+        !self.value.is_nil()
+    }
 }
 
 #[cfg(feature = "uuid")]
@@ -704,4 +787,54 @@ mod tests {
         let pk_deserialized: PrimaryKeyUuid = serde_json::from_str(&pk_json).unwrap();
         assert_eq!(pk, pk_deserialized);
     }
+
+    #[test]
+    fn test_primary_key_deref() {
+        fn inner(id: &u64) -> u64 {
+            *id
+        }
+        fn name_len(name: &str) -> usize {
+            name.len()
+        }
+
+        let pk = PrimaryKeyInteger::new(42);
+        assert_eq!(inner(&pk), 42);
+        assert_eq!(*pk, 42);
+        assert_eq!(AsRef::<u64>::as_ref(&pk), &42);
+        assert_eq!(Borrow::<u64>::borrow(&pk), &42);
+
+        let pk = PrimaryKeyString::from("abc");
+        assert_eq!(name_len(&pk), 3);
+        assert_eq!(pk.as_str(), "abc");
+
+        // Conversions and serde still use the primary key, not the inner value
+        assert_eq!(
+            crate::Value::from(&PrimaryKeyInteger::new(1)),
+            crate::Value::Identifier(1)
+        );
+        assert_eq!(serde_json::to_string(&pk).unwrap(), "\"abc\"");
+        assert_eq!(pk.to_string(), "abc");
+    }
+
+    #[test]
+    fn test_primary_key_integer_is_set() {
+        assert!(!PrimaryKeyInteger::default().is_set());
+        assert!(!PrimaryKeyInteger::new(0).is_set());
+        assert!(PrimaryKeyInteger::new(1).is_set());
+    }
+
+    #[test]
+    fn test_primary_key_string_is_set() {
+        assert!(!PrimaryKeyString::default().is_set());
+        assert!(!PrimaryKeyString::from("").is_set());
+        assert!(PrimaryKeyString::from("abc").is_set());
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn test_primary_key_uuid_is_set() {
+        assert!(!PrimaryKeyUuid::new(Uuid::nil()).is_set());
+        assert!(PrimaryKeyUuid::new(Uuid::new_v4()).is_set());
+        assert!(PrimaryKeyUuid::default().is_set());
+    }
 }
@@ -2,6 +2,8 @@
 
 #[cfg(feature = "migrations")]
 pub mod alter;
+/// CASE expression builder module
+pub mod case;
 /// Column builder module
 pub mod columns;
 /// Column types module
@@ -17,3 +19,113 @@ pub mod models;
 pub mod table;
 /// Query values module
 pub mod values;
+
+/// SQL keywords which can not be used as bare identifiers in SQLite
+const RESERVED_KEYWORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "AS",
+    "AUTOINCREMENT",
+    "BETWEEN",
+    "CASE",
+    "CHECK",
+    "COLLATE",
+    "COMMIT",
+    "CONSTRAINT",
+    "CREATE",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DELETE",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "ESCAPE",
+    "EXCEPT",
+    "EXISTS",
+    "FOREIGN",
+    "FROM",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INDEX",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "ISNULL",
+    "JOIN",
+    "LIMIT",
+    "NOT",
+    "NOTHING",
+    "NOTNULL",
+    "NULL",
+    "ON",
+    "OR",
+    "ORDER",
+    "PRIMARY",
+    "REFERENCES",
+    "RETURNING",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRANSACTION",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+];
+
+/// Quote an identifier (table or column name) if it is a reserved SQL keyword
+///
+/// Qualified names (`table.column`) are quoted per part. Other identifiers are
+/// returned unchanged so the generated SQL stays readable.
+///
+/// ```rust
+/// use geekorm_core::builder::quote_identifier;
+///
+/// assert_eq!(quote_identifier("username"), "username");
+/// assert_eq!(quote_identifier("order"), "\"order\"");
+/// assert_eq!(quote_identifier("Orders.group"), "Orders.\"group\"");
+/// ```
+pub fn quote_identifier(name: &str) -> String {
+    let mut quoted = String::with_capacity(name.len());
+    push_identifier(&mut quoted, name);
+    quoted
+}
+
+/// Write a (possibly quoted) identifier into an existing buffer
+///
+/// Same rules as `quote_identifier` but without allocating a new String.
+pub(crate) fn push_identifier(buf: &mut String, name: &str) {
+    for (index, part) in name.split('.').enumerate() {
+        if index > 0 {
+            buf.push('.');
+        }
+        let reserved = if part.is_ascii() {
+            RESERVED_KEYWORDS
+                .iter()
+                .any(|keyword| keyword.eq_ignore_ascii_case(part))
+        } else {
+            RESERVED_KEYWORDS.contains(&part.to_uppercase().as_str())
+        };
+        if reserved {
+            buf.push('"');
+            for c in part.chars() {
+                if c == '"' {
+                    buf.push('"');
+                }
+                buf.push(c);
+            }
+            buf.push('"');
+        } else {
+            buf.push_str(part);
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::ToSqlite;
 
 /// Query Type (CREATE, SELECT, etc.)
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum QueryType {
     /// Create a new table
     Create,
@@ -14,15 +16,31 @@ pub enum QueryType {
     Update,
     /// Delete data from a table
     Delete,
+    /// Raw SQL (introspection / `PRAGMA` queries) which is used verbatim
+    ///
+    /// Raw queries are never generated by the [`crate::QueryBuilder`], only
+    /// created using [`crate::Query::raw`].
+    Raw,
 }
 
 /// Query Order (ASC / DESC)
+///
+/// By default SQLite sorts `NULL` values first for `ASC` and last for `DESC`,
+/// the `Nulls*` variants set the position of `NULL` values explicitly.
 #[derive(Debug, Clone)]
 pub enum QueryOrder {
     /// Ascending
     Asc,
     /// Descending
     Desc,
+    /// Ascending with `NULL` values first
+    AscNullsFirst,
+    /// Ascending with `NULL` values last
+    AscNullsLast,
+    /// Descending with `NULL` values first
+    DescNullsFirst,
+    /// Descending with `NULL` values last
+    DescNullsLast,
 }
 
 impl ToSqlite for QueryOrder {
@@ -30,6 +48,10 @@ impl ToSqlite for QueryOrder {
         match self {
             QueryOrder::Asc => String::from("ASC"),
             QueryOrder::Desc => String::from("DESC"),
+            QueryOrder::AscNullsFirst => String::from("ASC NULLS FIRST"),
+            QueryOrder::AscNullsLast => String::from("ASC NULLS LAST"),
+            QueryOrder::DescNullsFirst => String::from("DESC NULLS FIRST"),
+            QueryOrder::DescNullsLast => String::from("DESC NULLS LAST"),
         }
     }
 }
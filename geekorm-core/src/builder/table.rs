@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::fmt::{Display, Write};
 
+#[cfg(feature = "migrations")]
+use crate::builder::alter::{AlterMode, AlterQuery};
+use crate::builder::models::WhereCondition;
+use crate::builder::{push_identifier, quote_identifier};
 use crate::{Columns, QueryBuilder, ToSqlite, Values};
 
 /// The Table struct for defining a table
@@ -25,7 +29,98 @@ impl Table {
         }
     }
 
+    /// Get the name of a column in the database (the alias if the column is renamed)
+    ///
+    /// Supports `table.column` names, keeping the table prefix.
+    pub fn column_name(&self, column: &str) -> String {
+        match column.split_once('.') {
+            Some((table, name)) => format!("{}.{}", table, self.columns.column_name(name)),
+            None => self.columns.column_name(column),
+        }
+    }
+
+    /// Generate the `CREATE INDEX` statements for the indexed columns
+    pub fn on_create_indexes(&self) -> Vec<String> {
+        self.columns
+            .columns
+            .iter()
+            .filter(|col| col.is_indexed())
+            .map(|col| col.on_create_index(&self.name))
+            .collect()
+    }
+
+    /// Get the names of the columns selected from the table (in order)
+    ///
+    /// Skipped columns are not part of the table and are excluded.
+    pub fn selectable_columns(&self) -> Vec<String> {
+        self.columns
+            .columns
+            .iter()
+            .filter(|col| !col.skip)
+            .map(|col| col.name.clone())
+            .collect()
+    }
+
+    /// Get the names of the columns written when inserting a row (in order)
+    ///
+    /// Skipped, auto increment and read-only columns are excluded, and renamed
+    /// columns use their name in the database (the alias).
+    pub fn insertable_columns(&self) -> Vec<String> {
+        self.columns
+            .columns
+            .iter()
+            .filter(|col| !col.skip && !col.column_type.is_auto_increment() && !col.readonly)
+            .map(|col| {
+                if col.alias.is_empty() {
+                    col.name.clone()
+                } else {
+                    col.alias.clone()
+                }
+            })
+            .collect()
+    }
+
+    /// Write the `RETURNING` clause (all the selectable columns) if the query asks for it
+    fn push_returning(&self, query: &QueryBuilder, buf: &mut String) {
+        if !query.returning {
+            return;
+        }
+        buf.push_str(" RETURNING ");
+        for (index, column) in self.selectable_columns().iter().enumerate() {
+            if index > 0 {
+                buf.push_str(", ");
+            }
+            push_identifier(buf, column);
+        }
+    }
+
+    /// Check if two tables have the same schema
+    ///
+    /// Tables are compared by name and by their columns (matched by name), comparing
+    /// the column type, nullability, primary / foreign keys and uniqueness.
+    /// The order the columns are declared in is ignored.
+    pub fn schema_eq(&self, other: &Table) -> bool {
+        if self.name != other.name || !self.columns.diff(&other.columns).is_empty() {
+            return false;
+        }
+        self.columns.columns.iter().all(|column| {
+            other
+                .columns
+                .columns
+                .iter()
+                .find(|ocolumn| ocolumn.name == column.name)
+                .is_some_and(|ocolumn| {
+                    let (options, ooptions) =
+                        (column.column_type.options(), ocolumn.column_type.options());
+                    options.unique == ooptions.unique && options.foreign_key == ooptions.foreign_key
+                })
+        })
+    }
+
     /// Get the name of the primary key column
+    ///
+    /// Falls back to `id` if the table has no primary key column,
+    /// use [`Table::try_primary_key`] to error instead.
     pub fn get_primary_key(&self) -> String {
         self.columns
             .columns
@@ -35,6 +130,24 @@ impl Table {
             .unwrap_or_else(|| String::from("id"))
     }
 
+    /// Get the name of the primary key column or error if the table does not have one
+    ///
+    /// Updates, deletes and pagination rely on the primary key, so the backends
+    /// use this to reject tables without one when they are created.
+    pub fn try_primary_key(&self) -> Result<String, crate::Error> {
+        self.columns
+            .columns
+            .iter()
+            .find(|col| col.column_type.is_primary_key())
+            .map(|col| col.name.clone())
+            .ok_or_else(|| {
+                crate::Error::SchemaError(format!(
+                    "Table `{}` does not have a primary key column",
+                    self.name
+                ))
+            })
+    }
+
     /// Get the foreign key by table name
     pub fn get_foreign_key(&self, table_name: String) -> &crate::Column {
         for column in self.columns.get_foreign_keys() {
@@ -91,59 +204,124 @@ impl ToSqlite for Table {
     fn on_create(&self, query: &QueryBuilder) -> Result<String, crate::Error> {
         Ok(format!(
             "CREATE TABLE IF NOT EXISTS {} {};",
-            self.name,
+            quote_identifier(&self.name),
             self.columns.on_create(query)?
         ))
     }
 
     fn on_select(&self, qb: &QueryBuilder) -> Result<String, crate::Error> {
-        let mut full_query = String::new();
+        // The whole query is written into a single buffer
+        let mut full_query = String::with_capacity(256);
 
-        // Resolve the rest of the query, and append if necessary
-        let columns = self.columns.on_select(qb);
+        if qb.count {
+            // If the query is a count query, return the count query
+            full_query.push_str("SELECT COUNT(1)");
+        } else {
+            // Select selective columns
+            full_query.push_str("SELECT ");
+            let mut first = true;
+            let mut separator = |buf: &mut String| {
+                if !first {
+                    buf.push_str(", ");
+                }
+                first = false;
+            };
 
-        if let Ok(ref columns) = columns {
-            if qb.count {
-                // If the query is a count query, return the count query
-                full_query = String::from("SELECT COUNT(1)");
+            let scolumns: Vec<String> = if !qb.columns.is_empty() {
+                qb.columns.clone()
+            } else if qb.aliases && !qb.joins.is_empty() {
+                // Select the columns of this table and all the joined tables
+                std::iter::once(self)
+                    .chain(qb.joins.tables())
+                    .flat_map(|table| {
+                        table
+                            .columns
+                            .columns
+                            .iter()
+                            .filter(|col| !col.skip)
+                            .map(|col| format!("{}.{}", table.name, col.name))
+                    })
+                    .collect()
             } else {
-                // Select selective columns
-                let mut select_columns: Vec<String> = Vec::new();
+                self.selectable_columns()
+            };
 
-                let scolumns: Vec<String> = if !qb.columns.is_empty() {
-                    qb.columns.clone()
-                } else {
-                    self.columns
-                        .columns
-                        .iter()
-                        .filter(|col| !col.skip)
-                        .map(|col| col.name.clone())
-                        .collect()
-                };
-
-                for column in scolumns {
-                    // TODO(geekmasher): Validate that the column exists in the table
-                    if qb.joins.is_empty() {
-                        // If the query does not join multiple tables, we can use the column name directly
-                        select_columns.push(column);
+            for column in scolumns {
+                // TODO(geekmasher): Validate that the column exists in the table
+                separator(&mut full_query);
+                if qb.aliases && !qb.joins.is_empty() {
+                    // Alias the column with the table name (Table.column AS Table_column)
+                    let fullname = if column.contains('.') {
+                        column
                     } else {
-                        // We have to use the full column name
-                        if column.contains('.') {
-                            // Table.column
-                            select_columns.push(column);
-                        } else {
-                            // Lookup the column in the table
-                            let fullname = qb.table.get_fullname(&column)?;
-                            select_columns.push(fullname);
-                        }
+                        qb.table.get_fullname(&column)?
+                    };
+                    push_identifier(&mut full_query, &fullname);
+                    full_query.push_str(" AS ");
+                    push_identifier(&mut full_query, &fullname.replace('.', "_"));
+                } else if qb.joins.is_empty() || column.contains('.') {
+                    // If the query does not join multiple tables, we can use the column name directly
+                    // (or the column is already Table.column)
+                    push_identifier(&mut full_query, &column);
+                } else {
+                    // Lookup the column in the table
+                    let fullname = qb.table.get_fullname(&column)?;
+                    push_identifier(&mut full_query, &fullname);
+                }
+            }
+            // Computed expressions (expression AS alias)
+            for (expr, alias) in qb.expressions.iter() {
+                separator(&mut full_query);
+                full_query.push_str(expr);
+                full_query.push_str(" AS ");
+                push_identifier(&mut full_query, alias);
+            }
+        }
+
+        if let Some((partition, order_column, order, limit)) = &qb.top_n {
+            // FROM (SELECT *, ROW_NUMBER() OVER (...) FROM {table} WHERE {where_clause})
+            if !qb.joins.is_empty() {
+                return Err(crate::Error::QueryBuilderError(
+                    String::from("Top N per group queries do not support joins"),
+                    String::from("on_select"),
+                ));
+            }
+            full_query.push_str(" FROM (SELECT *, ROW_NUMBER() OVER (PARTITION BY ");
+            push_identifier(&mut full_query, partition);
+            full_query.push_str(" ORDER BY ");
+            push_identifier(&mut full_query, order_column);
+            full_query.push(' ');
+            full_query.push_str(&order.to_sqlite());
+            full_query.push_str(") AS __row_number FROM ");
+            push_identifier(&mut full_query, &self.name);
+            if !qb.where_clause.is_empty() {
+                full_query.push_str(" WHERE ");
+                for (index, clause) in qb.where_clause.iter().enumerate() {
+                    if index > 0 {
+                        full_query.push(' ');
                     }
+                    full_query.push_str(clause);
                 }
-                full_query = format!("SELECT {}", select_columns.join(", "));
             }
+            // Writing into a String never fails
+            let _ = write!(full_query, ") WHERE __row_number <= {}", limit);
 
+            // ORDER BY {order_by}
+            if !qb.order_by.is_empty() {
+                full_query.push_str(" ORDER BY ");
+                for (index, (column, order)) in qb.order_by.iter().enumerate() {
+                    if index > 0 {
+                        full_query.push_str(", ");
+                    }
+                    push_identifier(&mut full_query, column);
+                    full_query.push(' ');
+                    full_query.push_str(&order.to_sqlite());
+                }
+            }
+        } else {
             // FROM {table}
             full_query.push_str(" FROM ");
-            full_query.push_str(&self.name);
+            push_identifier(&mut full_query, &self.name);
 
             // JOIN
             if !qb.joins.is_empty() {
@@ -152,31 +330,36 @@ impl ToSqlite for Table {
             }
 
             // WHERE {where_clause} ORDER BY {order_by}
-            if !columns.is_empty() {
-                full_query.push(' ');
-                full_query.push_str(columns);
+            let mark = full_query.len();
+            full_query.push(' ');
+            self.columns.write_select(qb, &mut full_query);
+            if full_query.len() == mark + 1 {
+                full_query.truncate(mark);
             }
+        }
 
-            // LIMIT {limit} OFFSET {offset}
-            if let Some(limit) = qb.limit {
-                // TODO(geekmasher): Check offset
-                full_query.push_str(" LIMIT ");
-                full_query.push_str(&limit.to_string());
-                if let Some(offset) = qb.offset {
-                    full_query.push_str(" OFFSET ");
-                    full_query.push_str(&offset.to_string());
-                }
+        // LIMIT {limit} OFFSET {offset}
+        // Count queries always count every row, so LIMIT / OFFSET are dropped
+        if let Some(limit) = qb.limit.filter(|_| !qb.count) {
+            // TODO(geekmasher): Check offset
+            let _ = write!(full_query, " LIMIT {}", limit);
+            if let Some(offset) = qb.offset {
+                let _ = write!(full_query, " OFFSET {}", offset);
             }
-
-            // End
-            full_query = full_query.trim().to_string();
-            full_query.push(';');
         }
+
+        // End
+        full_query.truncate(full_query.trim_end().len());
+        full_query.push(';');
         Ok(full_query)
     }
 
     fn on_insert(&self, query: &QueryBuilder) -> Result<(String, Values), crate::Error> {
-        let mut full_query = format!("INSERT INTO {} ", self.name);
+        let mut full_query = format!(
+            "INSERT {}INTO {} ",
+            if query.or_ignore { "OR IGNORE " } else { "" },
+            quote_identifier(&self.name)
+        );
 
         let mut columns: Vec<String> = Vec::new();
         let mut values: Vec<String> = Vec::new();
@@ -191,12 +374,12 @@ impl ToSqlite for Table {
                 column_name = column.alias.to_string();
             }
 
-            // Skip auto increment columns
-            if column.column_type.is_auto_increment() {
+            // Skip auto increment and read-only columns
+            if column.column_type.is_auto_increment() || column.readonly {
                 continue;
             }
 
-            columns.push(column_name.clone());
+            columns.push(quote_identifier(&column_name));
 
             // Add to Values
             match value {
@@ -204,12 +387,12 @@ impl ToSqlite for Table {
                     // Security: String values should never be directly inserted into the query
                     // This is to prevent SQL injection attacks
                     values.push(String::from("?"));
-                    parameters.push(column_name, value.clone());
+                    parameters.push_unique(column_name, value.clone());
                 }
                 crate::Value::Blob(value) => {
                     // Security: Blods should never be directly inserted into the query
                     values.push(String::from("?"));
-                    parameters.push(column_name, value.clone());
+                    parameters.push_unique(column_name, value.clone());
                 }
                 crate::Value::Integer(value) => values.push(value.to_string()),
                 crate::Value::Boolean(value) => values.push(value.to_string()),
@@ -217,6 +400,14 @@ impl ToSqlite for Table {
             }
         }
 
+        // No insertable columns (e.g. only an auto increment primary key)
+        if columns.is_empty() {
+            full_query.push_str("DEFAULT VALUES");
+            self.push_returning(query, &mut full_query);
+            full_query.push(';');
+            return Ok((full_query, parameters));
+        }
+
         // Generate the column names
         full_query.push('(');
         full_query.push_str(&columns.join(", "));
@@ -226,13 +417,14 @@ impl ToSqlite for Table {
         full_query.push_str(" VALUES (");
         full_query.push_str(&values.join(", "));
         full_query.push(')');
+        self.push_returning(query, &mut full_query);
         full_query.push(';');
 
         Ok((full_query, parameters))
     }
 
     fn on_update(&self, query: &QueryBuilder) -> Result<(String, Values), crate::Error> {
-        let mut full_query = format!("UPDATE {} SET ", self.name);
+        let mut full_query = format!("UPDATE {} SET ", quote_identifier(&self.name));
 
         let mut columns: Vec<String> = Vec::new();
         let mut parameters = Values::new();
@@ -240,8 +432,8 @@ impl ToSqlite for Table {
         for (cname, value) in query.values.values.iter() {
             let column = query.table.columns.get(cname.as_str()).unwrap();
 
-            // Skip if primary key
-            if column.column_type.is_primary_key() || cname == "id" {
+            // Skip if primary key or read-only
+            if column.column_type.is_primary_key() || cname == "id" || column.readonly {
                 continue;
             }
             // Get the column (might be an alias)
@@ -258,16 +450,18 @@ impl ToSqlite for Table {
                 | crate::Value::Json(_) => {
                     // Security: String values should never be directly inserted into the query
                     // This is to prevent SQL injection attacks
-                    columns.push(format!("{} = ?", column_name));
-                    parameters.push(column_name, value.clone());
+                    columns.push(format!("{} = ?", quote_identifier(&column_name)));
+                    parameters.push_unique(column_name, value.clone());
                 }
                 crate::Value::Integer(value) => {
-                    columns.push(format!("{} = {}", column_name, value))
+                    columns.push(format!("{} = {}", quote_identifier(&column_name), value))
                 }
                 crate::Value::Boolean(value) => {
-                    columns.push(format!("{} = {}", column_name, value))
+                    columns.push(format!("{} = {}", quote_identifier(&column_name), value))
+                }
+                crate::Value::Null => {
+                    columns.push(format!("{} = NULL", quote_identifier(&column_name)))
                 }
-                crate::Value::Null => columns.push(format!("{} = NULL", column_name)),
             }
         }
 
@@ -275,11 +469,45 @@ impl ToSqlite for Table {
         full_query.push_str(&columns.join(", "));
 
         // WHERE
-        // TODO(geekmasher): We only support updating by primary key
-        let primary_key_name = query.table.get_primary_key();
-        let primary_key = query.values.get(&primary_key_name).unwrap();
-        let where_clause = format!(" WHERE {} = {}", primary_key_name, primary_key);
+        // Updates are always by primary key, any where conditions are added to it
+        let primary_key_name = query.table.try_primary_key()?;
+        let primary_key = query.values.get(&primary_key_name).ok_or_else(|| {
+            crate::Error::QueryBuilderError(
+                format!("Update query on `{}` requires a primary key", self.name),
+                String::from("on_update"),
+            )
+        })?;
+        let where_clause = format!(
+            " WHERE {} = {}",
+            quote_identifier(&primary_key_name),
+            primary_key
+        );
         full_query.push_str(&where_clause);
+
+        // Extra conditions (e.g. the expected version of the row)
+        let mut conditions = query.where_clause.as_slice();
+        // Drop a dangling AND / OR left by the builder
+        if let Some((last, rest)) = conditions.split_last() {
+            if last == &WhereCondition::Or.to_sqlite() || last == &WhereCondition::And.to_sqlite() {
+                conditions = rest;
+            }
+        }
+        match conditions {
+            [] => {}
+            [condition] => {
+                full_query.push_str(" AND ");
+                full_query.push_str(condition);
+            }
+            conditions => {
+                full_query.push_str(" AND (");
+                full_query.push_str(&conditions.join(" "));
+                full_query.push(')');
+            }
+        }
+        for (column, value) in query.where_values.values.iter() {
+            parameters.push(column.clone(), value.clone());
+        }
+        self.push_returning(query, &mut full_query);
         full_query.push(';');
 
         Ok((full_query, parameters))
@@ -289,19 +517,81 @@ impl ToSqlite for Table {
     ///
     /// Only supports deleting by primary key
     fn on_delete(&self, query: &QueryBuilder) -> Result<(String, Values), crate::Error> {
-        let mut full_query = format!("DELETE FROM {}", self.name);
-        let mut parameters = Values::new();
+        let mut full_query = format!("DELETE FROM {}", quote_identifier(&self.name));
+
+        // Delete by where clause
+        let mut where_clause = query.where_clause.as_slice();
+        // Drop a dangling AND / OR left by the builder
+        if let Some((last, rest)) = where_clause.split_last() {
+            if last == &WhereCondition::Or.to_sqlite() || last == &WhereCondition::And.to_sqlite() {
+                where_clause = rest;
+            }
+        }
+        if !where_clause.is_empty() {
+            full_query.push_str(" WHERE ");
+            full_query.push_str(where_clause.join(" ").as_str());
+            self.push_returning(query, &mut full_query);
+            full_query.push(';');
+
+            return Ok((full_query, query.values.clone()));
+        }
 
         // Delete by primary key
-        let primary_key_name = self.get_primary_key();
-        let primary_key = query.values.get(&primary_key_name).unwrap();
+        let mut parameters = Values::new();
+        let primary_key_name = self.try_primary_key()?;
+        let primary_key = query.values.get(&primary_key_name).ok_or_else(|| {
+            crate::Error::QueryBuilderError(
+                format!(
+                    "Delete query on `{}` requires a where clause or primary key",
+                    self.name
+                ),
+                String::from("on_delete"),
+            )
+        })?;
 
         parameters.push(primary_key_name.to_string(), primary_key.clone());
 
-        full_query.push_str(&format!(" WHERE {} = ?;", primary_key_name));
+        full_query.push_str(&format!(
+            " WHERE {} = ?",
+            quote_identifier(&primary_key_name)
+        ));
+        self.push_returning(query, &mut full_query);
+        full_query.push(';');
 
         Ok((full_query, parameters))
     }
+
+    /// Function to alter the table (create, rename or drop)
+    #[cfg(feature = "migrations")]
+    fn on_alter(&self, query: &AlterQuery) -> Result<String, crate::Error> {
+        match query.mode {
+            AlterMode::AddTable => {
+                let mut sql = self.on_create(&QueryBuilder::create().table(self.clone()))?;
+                for index in self.on_create_indexes() {
+                    sql.push('\n');
+                    sql.push_str(&index);
+                }
+                Ok(sql)
+            }
+            AlterMode::RenameTable => match &query.rename {
+                Some(rename) => Ok(format!(
+                    "ALTER TABLE {} RENAME TO {};",
+                    quote_identifier(&query.table),
+                    quote_identifier(rename)
+                )),
+                None => Err(crate::Error::QueryBuilderError(
+                    format!("No new name set to rename table `{}`", query.table),
+                    String::from("on_alter"),
+                )),
+            },
+            AlterMode::DropTable => Ok(format!("DROP TABLE {};", quote_identifier(&query.table))),
+            AlterMode::Skip => Ok(format!("-- Skipping {} this migration", query.table)),
+            _ => Err(crate::Error::QueryBuilderError(
+                format!("Unsupported table alter mode `{:?}`", query.mode),
+                String::from("on_alter"),
+            )),
+        }
+    }
 }
 
 impl Display for Table {
@@ -333,6 +623,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_table_without_primary_key() {
+        use crate::{Column, ColumnType, ColumnTypeOptions};
+
+        let table = Table {
+            name: "Logs".to_string(),
+            columns: vec![Column::new(
+                "message".to_string(),
+                ColumnType::Text(ColumnTypeOptions::default()),
+            )]
+            .into(),
+        };
+        assert!(matches!(
+            table.try_primary_key(),
+            Err(crate::Error::SchemaError(_))
+        ));
+        assert_eq!(table.get_primary_key(), "id");
+
+        // The query can be built, creating the table checks for the primary key
+        let create = crate::QueryBuilder::create().table(table.clone()).build();
+        assert!(create.is_ok());
+        let database = crate::Database {
+            tables: vec![table.clone()],
+        };
+        assert!(matches!(
+            database.create_queries(),
+            Err(crate::Error::SchemaError(_))
+        ));
+
+        let delete = crate::QueryBuilder::delete()
+            .table(table.clone())
+            .add_value("message", "test")
+            .build();
+        assert!(matches!(delete, Err(crate::Error::SchemaError(_))));
+
+        assert_eq!(self::table().try_primary_key().unwrap(), "id");
+    }
+
     #[test]
     fn test_table_to_sql() {
         let table = table();
@@ -398,6 +726,403 @@ mod tests {
         assert_eq!(delete_query, "DELETE FROM Test WHERE id = ?;");
     }
 
+    #[test]
+    fn test_row_delete_where() {
+        let table = table();
+
+        let query = crate::QueryBuilder::delete()
+            .table(table.clone())
+            .filter(vec![("name", "expired"), ("name", "revoked")]);
+        let (delete_query, parameters) = table.on_delete(&query).unwrap();
+
+        assert_eq!(delete_query, "DELETE FROM Test WHERE name = ? OR name = ?;");
+        assert_eq!(parameters.len(), 2);
+
+        // Deleting without a where clause or primary key is an error
+        let query = crate::QueryBuilder::delete().table(table.clone());
+        assert!(table.on_delete(&query).is_err());
+    }
+
+    #[test]
+    fn test_update_where() {
+        let table = table();
+
+        // The same column can be written and compared
+        let query = crate::QueryBuilder::update()
+            .table(table.clone())
+            .add_value("id", 1)
+            .add_value("name", "new")
+            .where_eq("name", "old")
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.to_str(),
+            "UPDATE Test SET name = ? WHERE id = 1 AND name = ?;"
+        );
+        let parameters: Vec<&crate::Value> = query.parameters.iter().collect();
+        assert_eq!(
+            parameters,
+            vec![&crate::Value::from("new"), &crate::Value::from("old")]
+        );
+
+        let query = crate::QueryBuilder::update()
+            .table(table.clone())
+            .add_value("id", 1)
+            .add_value("name", "new")
+            .where_eq("name", "a")
+            .or()
+            .where_eq("name", "b")
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.to_str(),
+            "UPDATE Test SET name = ? WHERE id = 1 AND (name = ? OR name = ?);"
+        );
+    }
+
+    #[test]
+    fn test_returning_all() {
+        let table = table();
+
+        let query = crate::QueryBuilder::insert()
+            .table(table.clone())
+            .add_value("name", "geekmasher")
+            .returning_all()
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.to_str(),
+            "INSERT INTO Test (name) VALUES (?) RETURNING id, name;"
+        );
+        assert!(query.returns_rows());
+        assert_eq!(query.projected_columns(), vec!["id", "name"]);
+
+        let query = crate::QueryBuilder::update()
+            .table(table.clone())
+            .add_value("id", 1)
+            .add_value("name", "geekmasher")
+            .returning_all()
+            .build()
+            .unwrap();
+        assert!(query.to_str().ends_with(" RETURNING id, name;"));
+
+        let query = crate::QueryBuilder::delete()
+            .table(table.clone())
+            .where_eq("id", 1)
+            .returning_all()
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.to_str(),
+            "DELETE FROM Test WHERE id = ? RETURNING id, name;"
+        );
+
+        // Writes without `RETURNING` do not return rows
+        let query = crate::QueryBuilder::delete()
+            .table(table.clone())
+            .where_eq("id", 1)
+            .build()
+            .unwrap();
+        assert!(!query.returns_rows());
+
+        // Select queries always return rows
+        let select = crate::QueryBuilder::select()
+            .table(table.clone())
+            .returning_all()
+            .build();
+        assert!(select.is_err());
+    }
+
+    #[test]
+    fn test_select_sql() {
+        use crate::builder::models::QueryOrder;
+
+        let mut table = table();
+        table.name = String::from("Group");
+
+        let query = crate::QueryBuilder::select()
+            .table(table.clone())
+            .where_eq("name", "geekmasher")
+            .or()
+            .where_eq("id", 1)
+            .order_by("name", QueryOrder::Desc)
+            .order_by("id", QueryOrder::Asc)
+            .limit(10)
+            .offset(20);
+        assert_eq!(
+            table.on_select(&query).unwrap(),
+            "SELECT id, name FROM \"Group\" WHERE name = ? OR id = ? ORDER BY name DESC, id ASC LIMIT 10 OFFSET 20;"
+        );
+
+        // Without ORDER BY, LIMIT follows the WHERE clause after a single space
+        // (older versions generated `WHERE name = ?  LIMIT 5;` with two spaces)
+        let query = crate::QueryBuilder::select()
+            .table(table.clone())
+            .where_eq("name", "geekmasher")
+            .limit(5);
+        assert_eq!(
+            table.on_select(&query).unwrap(),
+            "SELECT id, name FROM \"Group\" WHERE name = ? LIMIT 5;"
+        );
+    }
+
+    #[test]
+    fn test_count_ignores_limit() {
+        let table = table();
+
+        let query = crate::QueryBuilder::select()
+            .table(table.clone())
+            .where_eq("name", "geekmasher")
+            .limit(10)
+            .offset(20)
+            .count()
+            .build()
+            .unwrap();
+        assert_eq!(query.query, "SELECT COUNT(1) FROM Test WHERE name = ?;");
+        assert!(!query.query.contains("LIMIT"));
+        assert!(!query.query.contains("OFFSET"));
+    }
+
+    #[test]
+    fn test_schema_eq() {
+        use crate::{Column, ColumnType, ColumnTypeOptions};
+
+        let id = Column::new(
+            "id".to_string(),
+            ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+        );
+        let name = Column::new(
+            "name".to_string(),
+            ColumnType::Text(ColumnTypeOptions::default()),
+        );
+        let email = Column::new(
+            "email".to_string(),
+            ColumnType::Text(ColumnTypeOptions::unique()),
+        );
+        let table = |columns: Vec<Column>| Table {
+            name: "Users".to_string(),
+            columns: columns.into(),
+        };
+
+        let users = table(vec![id.clone(), name.clone(), email.clone()]);
+        // Same columns in a different order
+        let reordered = table(vec![email.clone(), id.clone(), name.clone()]);
+        assert!(users.schema_eq(&reordered));
+        assert!(reordered.schema_eq(&users));
+
+        // Missing column
+        assert!(!users.schema_eq(&table(vec![id.clone(), name.clone()])));
+        // Different table name
+        let mut renamed = users.clone();
+        renamed.name = "Accounts".to_string();
+        assert!(!users.schema_eq(&renamed));
+        // Different column type
+        let age = Column::new(
+            "name".to_string(),
+            ColumnType::Integer(ColumnTypeOptions::default()),
+        );
+        assert!(!users.schema_eq(&table(vec![id.clone(), age, email.clone()])));
+        // Different uniqueness
+        let email_not_unique = Column::new(
+            "email".to_string(),
+            ColumnType::Text(ColumnTypeOptions::default()),
+        );
+        assert!(!users.schema_eq(&table(vec![id, name, email_not_unique])));
+    }
+
+    #[test]
+    fn test_insert_default_values() {
+        use crate::{Column, ColumnType, ColumnTypeOptions};
+
+        let table = Table {
+            name: "Sessions".to_string(),
+            columns: vec![Column::new(
+                "id".to_string(),
+                ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+            )]
+            .into(),
+        };
+
+        let query = crate::QueryBuilder::insert()
+            .table(table.clone())
+            .add_value("id", crate::Value::Identifier(0));
+        let (insert_query, parameters) = table.on_insert(&query).unwrap();
+
+        assert_eq!(insert_query, "INSERT INTO Sessions DEFAULT VALUES;");
+        assert_eq!(parameters.len(), 0);
+    }
+
+    #[test]
+    fn test_selectable_insertable_columns() {
+        use crate::{Column, ColumnType, ColumnTypeOptions};
+
+        let table = Table {
+            name: "Users".to_string(),
+            columns: vec![
+                Column::new(
+                    "id".to_string(),
+                    ColumnType::Integer(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    "username".to_string(),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+                Column {
+                    name: "kind".to_string(),
+                    column_type: ColumnType::Text(ColumnTypeOptions::default()),
+                    alias: "type".to_string(),
+                    ..Default::default()
+                },
+                Column {
+                    name: "created".to_string(),
+                    column_type: ColumnType::Text(ColumnTypeOptions::default()),
+                    readonly: true,
+                    ..Default::default()
+                },
+                Column {
+                    name: "posts".to_string(),
+                    column_type: ColumnType::Text(ColumnTypeOptions::default()),
+                    skip: true,
+                    ..Default::default()
+                },
+            ]
+            .into(),
+        };
+
+        assert_eq!(
+            table.selectable_columns(),
+            vec!["id", "username", "kind", "created"]
+        );
+        assert_eq!(table.insertable_columns(), vec!["username", "type"]);
+    }
+
+    #[test]
+    fn test_insert_or_ignore() {
+        let table = table();
+
+        let query = crate::QueryBuilder::insert_or_ignore()
+            .table(table.clone())
+            .add_value("name", "geekmasher");
+        let (insert_query, parameters) = table.on_insert(&query).unwrap();
+        assert_eq!(
+            insert_query,
+            "INSERT OR IGNORE INTO Test (name) VALUES (?);"
+        );
+        assert_eq!(parameters.len(), 1);
+
+        let query = crate::QueryBuilder::insert()
+            .table(table.clone())
+            .add_value("name", "geekmasher");
+        let (insert_query, _) = table.on_insert(&query).unwrap();
+        assert_eq!(insert_query, "INSERT INTO Test (name) VALUES (?);");
+    }
+
+    #[test]
+    fn test_reserved_keyword_columns() {
+        use crate::builder::models::QueryOrder;
+        use crate::{Column, ColumnType, ColumnTypeOptions};
+
+        let table = Table {
+            name: "Items".to_string(),
+            columns: vec![
+                Column::new(
+                    "id".to_string(),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    "order".to_string(),
+                    ColumnType::Integer(ColumnTypeOptions::default()),
+                ),
+                Column::new(
+                    "name".to_string(),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+            ]
+            .into(),
+        };
+
+        let create = crate::QueryBuilder::create()
+            .table(table.clone())
+            .build()
+            .unwrap();
+        assert_eq!(
+            create.query,
+            "CREATE TABLE IF NOT EXISTS Items (id INTEGER PRIMARY KEY AUTOINCREMENT, \"order\" INTEGER, name TEXT);"
+        );
+
+        let query = crate::QueryBuilder::insert()
+            .table(table.clone())
+            .add_value("order", 1)
+            .add_value("name", "geekmasher");
+        let (insert_query, parameters) = table.on_insert(&query).unwrap();
+        assert_eq!(
+            insert_query,
+            "INSERT INTO Items (\"order\", name) VALUES (1, ?);"
+        );
+        assert_eq!(parameters.len(), 1);
+
+        let select = crate::QueryBuilder::select()
+            .table(table.clone())
+            .where_eq("order", 1)
+            .order_by("order", QueryOrder::Asc)
+            .build()
+            .unwrap();
+        assert_eq!(
+            select.query,
+            "SELECT id, \"order\", name FROM Items WHERE \"order\" = ? ORDER BY \"order\" ASC;"
+        );
+    }
+
+    #[test]
+    fn test_readonly_columns() {
+        use crate::{Column, ColumnType, ColumnTypeOptions};
+
+        let table = Table {
+            name: "Users".to_string(),
+            columns: vec![
+                Column::new(
+                    "id".to_string(),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    "name".to_string(),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+                Column {
+                    name: "created_at".to_string(),
+                    column_type: ColumnType::Text(ColumnTypeOptions::default()),
+                    readonly: true,
+                    ..Default::default()
+                },
+            ]
+            .into(),
+        };
+
+        let select = crate::QueryBuilder::select()
+            .table(table.clone())
+            .build()
+            .unwrap();
+        assert_eq!(select.query, "SELECT id, name, created_at FROM Users;");
+
+        let query = crate::QueryBuilder::insert()
+            .table(table.clone())
+            .add_value("id", crate::Value::Identifier(1))
+            .add_value("name", "geekmasher")
+            .add_value("created_at", "2024-01-01");
+        let (insert_query, parameters) = table.on_insert(&query).unwrap();
+        assert_eq!(insert_query, "INSERT INTO Users (name) VALUES (?);");
+        assert_eq!(parameters.len(), 1);
+
+        let query = crate::QueryBuilder::update()
+            .table(table.clone())
+            .add_value("id", crate::Value::Identifier(1))
+            .add_value("name", "geekmasher")
+            .add_value("created_at", "2024-01-01");
+        let (update_query, parameters) = table.on_update(&query).unwrap();
+        assert_eq!(update_query, "UPDATE Users SET name = ? WHERE id = 1;");
+        assert_eq!(parameters.len(), 1);
+    }
+
     #[test]
     fn test_is_valid_column() {
         let table = table();
@@ -409,4 +1134,20 @@ mod tests {
         assert!(table.is_valid_column("Test.name"));
         assert!(!table.is_valid_column("Tests.name"));
     }
+
+    #[test]
+    #[cfg(feature = "migrations")]
+    fn test_alter_rename_table() {
+        let table = table();
+
+        let mut query = AlterQuery::new(AlterMode::RenameTable, "OldTest", "");
+        query.rename("Test");
+        assert_eq!(
+            table.on_alter(&query).unwrap(),
+            "ALTER TABLE OldTest RENAME TO Test;"
+        );
+
+        let query = AlterQuery::new(AlterMode::RenameTable, "OldTest", "");
+        assert!(table.on_alter(&query).is_err());
+    }
 }
@@ -6,18 +6,29 @@ use serde::{Deserialize, Serialize, Serializer};
 pub(crate) mod valchrono;
 #[cfg(feature = "semver")]
 pub(crate) mod valsemver;
+#[cfg(feature = "time")]
+pub(crate) mod valtime;
 #[cfg(feature = "uuid")]
 pub(crate) mod valuuid;
 
 use crate::{
-    builder::keys::{foreign::ForeignKeyInteger, primary::PrimaryKeyInteger},
+    builder::{
+        keys::{
+            foreign::{ForeignKeyInteger, ForeignKeyString},
+            primary::PrimaryKeyInteger,
+        },
+        models::QueryCondition,
+    },
     PrimaryKey, TableBuilder, TablePrimaryKey,
 };
 
 use super::keys::{foreign::ForeignKeyIntegerOld, primary::PrimaryKeyIntegerOld};
 
 /// List of Values
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+///
+/// Serialized as an ordered list of `(column, value)` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Values {
     /// List of values
     pub(crate) values: Vec<(String, Value)>,
@@ -32,10 +43,26 @@ impl Values {
     }
 
     /// Push a value to the list of values
+    ///
+    /// This always appends the value, even if the column is already present.
+    /// Where clauses rely on this as the same column can be referenced more than once.
     pub fn push(&mut self, column: String, value: impl Into<Value>) {
         self.values.push((column, value.into()));
     }
 
+    /// Push a value to the list of values, replacing the value if the column
+    /// already exists (last value wins).
+    ///
+    /// This is used by the insert and update builders so a column is never
+    /// written twice in the same query.
+    pub fn push_unique(&mut self, column: String, value: impl Into<Value>) {
+        let value = value.into();
+        match self.values.iter_mut().find(|(c, _)| *c == column) {
+            Some((_, existing)) => *existing = value,
+            None => self.values.push((column, value)),
+        }
+    }
+
     /// Get a value by index from the list of values
     pub fn get(&self, column: &String) -> Option<&Value> {
         self.values
@@ -47,21 +74,48 @@ impl Values {
     pub fn len(&self) -> usize {
         self.values.len()
     }
+
+    /// Iterate over the values (in order) without cloning them
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.values.iter().map(|(_, v)| v)
+    }
 }
 
 impl IntoIterator for Values {
     type Item = Value;
-    type IntoIter = std::vec::IntoIter<Value>;
+    type IntoIter =
+        std::iter::Map<std::vec::IntoIter<(String, Value)>, fn((String, Value)) -> Value>;
 
+    /// Moves the values out (no values are cloned or collected)
     fn into_iter(self) -> Self::IntoIter {
         self.values
             .into_iter()
-            .map(|(_, v)| v)
-            .collect::<Vec<Value>>()
-            .into_iter()
+            .map(value_of as fn((String, Value)) -> Value)
+    }
+}
+
+impl<'a> IntoIterator for &'a Values {
+    type Item = &'a Value;
+    type IntoIter =
+        std::iter::Map<std::slice::Iter<'a, (String, Value)>, fn(&'a (String, Value)) -> &'a Value>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values
+            .iter()
+            .map(value_ref as fn(&'a (String, Value)) -> &'a Value)
     }
 }
 
+/// Get the value of a `(column, value)` pair
+fn value_of((_, value): (String, Value)) -> Value {
+    value
+}
+
+/// Get a reference to the value of a `(column, value)` pair
+fn value_ref((_, value): &(String, Value)) -> &Value {
+    value
+}
+
 /// A value for a column
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Value {
@@ -88,6 +142,170 @@ impl Default for Value {
     }
 }
 
+impl Value {
+    /// Storage class rank used for ordering (follows SQLite's sort order)
+    fn storage_class(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Integer(_) | Value::Boolean(_) | Value::Identifier(_) => 1,
+            Value::Text(_) => 2,
+            Value::Blob(_) | Value::Json(_) => 3,
+        }
+    }
+
+    /// Numeric value for integer-like values
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::Integer(value) => Some(*value as i128),
+            Value::Boolean(value) => Some(*value as i128),
+            Value::Identifier(value) => Some(*value as i128),
+            _ => None,
+        }
+    }
+
+    /// Evaluate a where condition (`self <condition> other`) using SQL semantics
+    ///
+    /// Comparisons against `NULL` are "unknown" and return `None` (SQL's
+    /// three-valued logic), so a row with a `NULL` column never matches
+    /// `col = ?` or `col != ?`. Integer-like values are compared numerically
+    /// regardless of variant, and `LIKE` matches ASCII case-insensitively
+    /// with the `%` and `_` wildcards.
+    ///
+    /// This is intended for in-memory backends which need to filter rows the
+    /// same way the database would.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// assert_eq!(Value::from(1).sql_compare(&QueryCondition::Eq, &Value::from(1)), Some(true));
+    /// assert_eq!(Value::Null.sql_compare(&QueryCondition::Eq, &Value::Null), None);
+    /// assert_eq!(Value::Null.sql_compare(&QueryCondition::Ne, &Value::from(1)), None);
+    /// ```
+    pub fn sql_compare(&self, condition: &QueryCondition, other: &Value) -> Option<bool> {
+        if matches!(self, Value::Null) || matches!(other, Value::Null) {
+            return None;
+        }
+        if let QueryCondition::Like = condition {
+            return Some(sql_like(&self.to_string(), &other.to_string()));
+        }
+
+        let ordering = self
+            .storage_class()
+            .cmp(&other.storage_class())
+            .then_with(|| match (self, other) {
+                (Value::Text(a), Value::Text(b)) => a.cmp(b),
+                (Value::Blob(a) | Value::Json(a), Value::Blob(b) | Value::Json(b)) => a.cmp(b),
+                _ => self.as_i128().cmp(&other.as_i128()),
+            });
+        Some(match condition {
+            QueryCondition::Eq => ordering.is_eq(),
+            QueryCondition::Ne => ordering.is_ne(),
+            QueryCondition::Gt => ordering.is_gt(),
+            QueryCondition::Lt => ordering.is_lt(),
+            QueryCondition::Gte => ordering.is_ge(),
+            QueryCondition::Lte => ordering.is_le(),
+            QueryCondition::Like => unreachable!(),
+        })
+    }
+
+    /// Variant rank used to break ties between equal values of different variants
+    fn variant_rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Integer(_) => 1,
+            Value::Boolean(_) => 2,
+            Value::Identifier(_) => 3,
+            Value::Text(_) => 4,
+            Value::Blob(_) => 5,
+            Value::Json(_) => 6,
+        }
+    }
+}
+
+/// SQL `LIKE` pattern matching (ASCII case-insensitive, `%` and `_` wildcards)
+///
+/// Greedy matching which backtracks only to the last `%`, so it runs in
+/// `O(value * pattern)` time in the worst case.
+fn sql_like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let pattern: Vec<char> = pattern.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    let (mut v, mut p) = (0, 0);
+    // Position of the last `%` in the pattern and the value position it matched up to
+    let mut wildcard: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                wildcard = Some((p, v));
+                p += 1;
+            }
+            Some(c) if *c == '_' || *c == value[v] => {
+                v += 1;
+                p += 1;
+            }
+            _ => match wildcard {
+                // Let the last `%` consume one more character and retry
+                Some((wp, wv)) => {
+                    wildcard = Some((wp, wv + 1));
+                    p = wp + 1;
+                    v = wv + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+/// Values are ordered the same way SQLite sorts them:
+///
+/// 1. `NULL` sorts first
+/// 2. Integer, Boolean and Identifier values are compared numerically
+/// 3. Text values are compared lexically (byte order, like the `BINARY` collation)
+/// 4. Blob and JSON values are compared as bytes
+///
+/// Values of different variants that are numerically equal (e.g. `Integer(1)`
+/// and `Identifier(1)`) are ordered by variant so the ordering stays consistent with `Eq`.
+///
+/// ```rust
+/// use geekorm::prelude::*;
+///
+/// let mut values = vec![
+///     Value::from("b"),
+///     Value::from(42),
+///     Value::Null,
+///     Value::from("a"),
+///     Value::from(-1),
+/// ];
+/// values.sort();
+/// # assert_eq!(values, vec![
+/// #     Value::Null,
+/// #     Value::from(-1),
+/// #     Value::from(42),
+/// #     Value::from("a"),
+/// #     Value::from("b"),
+/// # ]);
+/// ```
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.storage_class()
+            .cmp(&other.storage_class())
+            .then_with(|| match (self, other) {
+                (Value::Text(a), Value::Text(b)) => a.cmp(b),
+                (Value::Blob(a) | Value::Json(a), Value::Blob(b) | Value::Json(b)) => a.cmp(b),
+                _ => self.as_i128().cmp(&other.as_i128()),
+            })
+            .then_with(|| self.variant_rank().cmp(&other.variant_rank()))
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -179,6 +397,26 @@ where
     }
 }
 
+// ForeignKey<String, T> -> Value
+impl<T> From<ForeignKeyString<T>> for Value
+where
+    T: TableBuilder + TablePrimaryKey,
+{
+    fn from(value: ForeignKeyString<T>) -> Self {
+        Value::Text(value.key)
+    }
+}
+
+// &ForeignKey<String, T> -> Value
+impl<T> From<&ForeignKeyString<T>> for Value
+where
+    T: TableBuilder + TablePrimaryKey,
+{
+    fn from(value: &ForeignKeyString<T>) -> Self {
+        Value::Text(value.key.clone())
+    }
+}
+
 impl From<String> for Value {
     fn from(value: String) -> Self {
         Value::Text(value)
@@ -264,12 +502,33 @@ impl From<i64> for Value {
     }
 }
 
+impl From<&i64> for Value {
+    fn from(value: &i64) -> Self {
+        Value::Integer(*value)
+    }
+}
+
 impl From<usize> for Value {
     fn from(value: usize) -> Self {
         Value::Integer(value as i64)
     }
 }
 
+/// Durations are stored as a number of seconds (see `geekorm::utils::duration`)
+///
+/// Durations longer than `i64::MAX` seconds are clamped.
+impl From<std::time::Duration> for Value {
+    fn from(value: std::time::Duration) -> Self {
+        Value::Integer(crate::utils::duration::as_seconds(&value))
+    }
+}
+
+impl From<&std::time::Duration> for Value {
+    fn from(value: &std::time::Duration) -> Self {
+        Value::Integer(crate::utils::duration::as_seconds(value))
+    }
+}
+
 impl From<Vec<String>> for Value {
     fn from(value: Vec<String>) -> Self {
         Value::Blob(serde_json::to_vec(&value).unwrap())
@@ -282,6 +541,92 @@ impl From<&Vec<String>> for Value {
     }
 }
 
+/// Store arbitrary JSON as a JSON document (text)
+///
+/// ```rust
+/// use geekorm::prelude::*;
+///
+/// let json = serde_json::json!({
+///     "name": "geekmasher",
+///     "tags": ["rust", "sqlite"],
+///     "profile": { "age": 42 }
+/// });
+///
+/// let value = Value::from(&json);
+/// # assert!(matches!(value, Value::Text(_)));
+///
+/// let parsed: serde_json::Value = value.try_into().unwrap();
+/// # assert_eq!(parsed, json);
+/// # assert_eq!(parsed["profile"]["age"], 42);
+/// ```
+///
+/// Fields using `serde_json::Value` are stored in `TEXT` columns and read back
+/// with [`crate::utils::json`]:
+///
+/// ```rust
+/// # #[cfg(feature = "rusqlite")] {
+/// use geekorm::prelude::*;
+///
+/// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+/// pub struct Events {
+///     #[geekorm(primary_key, auto_increment)]
+///     pub id: PrimaryKeyInteger,
+///     #[serde(with = "geekorm::utils::json")]
+///     pub payload: serde_json::Value,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let connection = rusqlite::Connection::open_in_memory()?;
+///     Events::create_table(&connection).await?;
+///
+///     let payload = serde_json::json!({ "action": "login", "meta": { "ip": "127.0.0.1" } });
+///     let mut event = Events::new(payload.clone());
+///     event.save(&connection).await?;
+///
+///     let stored: Option<serde_json::Value> = rusqlite::Connection::query_scalar(
+///         &connection,
+///         Query::raw(QueryType::Select, "SELECT payload FROM Events;"),
+///     ).await?;
+///     # assert_eq!(stored, Some(payload));
+///     Ok(())
+/// }
+/// # }
+/// ```
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        Value::from(&value)
+    }
+}
+
+impl From<&serde_json::Value> for Value {
+    fn from(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Null,
+            _ => Value::Text(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<Value> for serde_json::Value {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Json(value) | Value::Blob(value) => {
+                serde_json::from_slice(&value).map_err(|e| crate::Error::SerdeError(e.to_string()))
+            }
+            Value::Text(value) => {
+                serde_json::from_str(&value).map_err(|e| crate::Error::SerdeError(e.to_string()))
+            }
+            Value::Integer(value) => Ok(serde_json::Value::from(value)),
+            Value::Identifier(value) => Ok(serde_json::Value::from(value)),
+            Value::Boolean(value) => Ok(serde_json::Value::Bool(value != 0)),
+            Value::Null => Ok(serde_json::Value::Null),
+        }
+    }
+}
+
 impl From<Vec<u8>> for Value {
     fn from(value: Vec<u8>) -> Self {
         Value::Blob(value)
@@ -294,6 +639,141 @@ impl From<&Vec<u8>> for Value {
     }
 }
 
+impl From<char> for Value {
+    fn from(value: char) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<&char> for Value {
+    fn from(value: &char) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl TryFrom<Value> for char {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        if let Value::Text(text) = &value {
+            let mut chars = text.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                return Ok(c);
+            }
+        }
+        Err(crate::Error::SerdeError(format!(
+            "Unable to convert `{}` to a char",
+            value
+        )))
+    }
+}
+
+impl TryFrom<Value> for i64 {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Integer(value) => Ok(value),
+            Value::Identifier(value) => Ok(value as i64),
+            Value::Boolean(value) => Ok(value as i64),
+            Value::Text(ref text) => text.parse().map_err(|_| {
+                crate::Error::SerdeError(format!("Unable to convert `{}` to an i64", value))
+            }),
+            _ => Err(crate::Error::SerdeError(format!(
+                "Unable to convert `{}` to an i64",
+                value
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = crate::Error;
+
+    /// SQLite `REAL` values (e.g. `AVG()` results) are read back as text
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Integer(value) => Ok(value as f64),
+            Value::Identifier(value) => Ok(value as f64),
+            Value::Boolean(value) => Ok(value as f64),
+            Value::Text(ref text) => text.parse().map_err(|_| {
+                crate::Error::SerdeError(format!("Unable to convert `{}` to an f64", value))
+            }),
+            _ => Err(crate::Error::SerdeError(format!(
+                "Unable to convert `{}` to an f64",
+                value
+            ))),
+        }
+    }
+}
+
+impl TryFrom<Value> for u64 {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let integer = i64::try_from(value)?;
+        u64::try_from(integer).map_err(|_| {
+            crate::Error::SerdeError(format!("Unable to convert `{}` to a u64", integer))
+        })
+    }
+}
+
+impl TryFrom<Value> for bool {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Ok(i64::try_from(value)? != 0)
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Null => Err(crate::Error::SerdeError(String::from(
+                "Unable to convert NULL to a String",
+            ))),
+            Value::Text(value) => Ok(value),
+            _ => Ok(value.to_string()),
+        }
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Value {
+    fn from(value: [u8; N]) -> Self {
+        Value::Blob(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<&[u8; N]> for Value {
+    fn from(value: &[u8; N]) -> Self {
+        Value::Blob(value.to_vec())
+    }
+}
+
+impl<const N: usize> TryFrom<Value> for [u8; N] {
+    type Error = crate::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Blob(bytes) => {
+                let length = bytes.len();
+                bytes.try_into().map_err(|_| {
+                    crate::Error::SerdeError(format!(
+                        "Unable to convert a blob of {} bytes to [u8; {}]",
+                        length, N
+                    ))
+                })
+            }
+            _ => Err(crate::Error::SerdeError(format!(
+                "Unable to convert `{}` to [u8; {}]",
+                value, N
+            ))),
+        }
+    }
+}
+
 /// Serialize a Value
 impl Serialize for Value {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -419,7 +899,119 @@ impl<'de> Deserialize<'de> for Value {
 
 #[cfg(test)]
 mod tests {
-    use super::Values;
+    use super::{sql_like, Value, Values};
+    use crate::builder::models::QueryCondition;
+
+    /// Filter rows the way `WHERE col <condition> ?` would (unknown is excluded)
+    fn filter(rows: &[Value], condition: QueryCondition, value: Value) -> Vec<Value> {
+        rows.iter()
+            .filter(|row| row.sql_compare(&condition, &value) == Some(true))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_values_iter_without_cloning() {
+        let mut values = Values::new();
+        for i in 0..1000 {
+            values.push(format!("column_{}", i), "x".repeat(1024));
+        }
+        let pointers: Vec<*const u8> = values
+            .iter()
+            .map(|value| match value {
+                Value::Text(text) => text.as_ptr(),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        // Borrowing iterates the stored values
+        for (value, pointer) in (&values).into_iter().zip(pointers.iter()) {
+            assert!(matches!(value, Value::Text(text) if text.as_ptr() == *pointer));
+        }
+        // Consuming moves the values out, the text buffers are never copied
+        let mut count = 0;
+        for (value, pointer) in values.into_iter().zip(pointers.iter()) {
+            assert!(matches!(value, Value::Text(ref text) if text.as_ptr() == *pointer));
+            count += 1;
+        }
+        assert_eq!(count, 1000);
+    }
+
+    #[test]
+    fn test_sql_compare_null_equality() {
+        let rows = vec![Value::from("bob"), Value::Null, Value::from("alice")];
+
+        assert_eq!(
+            filter(&rows, QueryCondition::Eq, Value::from("bob")),
+            vec![Value::from("bob")]
+        );
+        // NULL = NULL is unknown, not true
+        assert!(filter(&rows, QueryCondition::Eq, Value::Null).is_empty());
+        assert_eq!(
+            Value::Null.sql_compare(&QueryCondition::Eq, &Value::Null),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sql_compare_null_inequality() {
+        let rows = vec![Value::from(1), Value::Null, Value::from(2)];
+
+        // `col != 1` excludes the NULL row
+        assert_eq!(
+            filter(&rows, QueryCondition::Ne, Value::from(1)),
+            vec![Value::from(2)]
+        );
+        assert!(filter(&rows, QueryCondition::Ne, Value::Null).is_empty());
+        assert_eq!(
+            filter(&rows, QueryCondition::Gte, Value::from(1)),
+            vec![Value::from(1), Value::from(2)]
+        );
+        // Integer-like values compare numerically
+        assert_eq!(
+            Value::Identifier(2).sql_compare(&QueryCondition::Eq, &Value::Integer(2)),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_sql_compare_like() {
+        let name = Value::from("GeekMasher");
+        assert_eq!(
+            name.sql_compare(&QueryCondition::Like, &Value::from("geek%")),
+            Some(true)
+        );
+        assert_eq!(
+            name.sql_compare(&QueryCondition::Like, &Value::from("_eekmasher")),
+            Some(true)
+        );
+        assert_eq!(
+            name.sql_compare(&QueryCondition::Like, &Value::from("%bob%")),
+            Some(false)
+        );
+        assert_eq!(Value::Null.sql_compare(&QueryCondition::Like, &name), None);
+    }
+
+    #[test]
+    fn test_sql_like() {
+        assert!(sql_like("", ""));
+        assert!(sql_like("", "%"));
+        assert!(!sql_like("", "_"));
+        assert!(sql_like("GeekMasher", "%MASHER"));
+        assert!(sql_like("GeekMasher", "g%k%r"));
+        assert!(sql_like("GeekMasher", "%e%e%"));
+        assert!(sql_like("abcabd", "%abd"));
+        assert!(sql_like("mississippi", "m%iss%ppi"));
+        assert!(!sql_like("mississippi", "m%iss%ppx"));
+        assert!(!sql_like("GeekMasher", "geek"));
+        assert!(!sql_like("Geek", "geek_"));
+
+        // Patterns with many wildcards don't backtrack exponentially
+        let value = "a".repeat(5000);
+        let pattern = format!("{}b", "%a".repeat(50));
+        assert!(!sql_like(&value, &pattern));
+        assert!(sql_like(&value, &format!("{}%", "%a".repeat(50))));
+    }
 
     #[test]
     fn test_values() {
@@ -429,4 +1021,159 @@ mod tests {
 
         assert_eq!(values.len(), 2);
     }
+
+    #[test]
+    fn test_values_push_unique() {
+        let mut values = Values::new();
+        values.push_unique("name".to_string(), "Bob");
+        values.push_unique("name".to_string(), "Alice");
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(
+            values.get(&"name".to_string()),
+            Some(&super::Value::Text("Alice".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_value_char() {
+        let value = Value::from('g');
+        assert_eq!(value, Value::Text("g".to_string()));
+        assert_eq!(char::try_from(value).unwrap(), 'g');
+
+        assert!(char::try_from(Value::Text("geek".to_string())).is_err());
+        assert!(char::try_from(Value::Integer(1)).is_err());
+    }
+
+    #[test]
+    fn test_value_byte_array() {
+        let hash: [u8; 16] = [42; 16];
+        let value = Value::from(hash);
+        assert_eq!(value, Value::Blob(vec![42; 16]));
+        assert_eq!(Value::from(&hash), value);
+
+        let back: [u8; 16] = value.try_into().unwrap();
+        assert_eq!(back, hash);
+
+        // Wrong length
+        assert!(<[u8; 8]>::try_from(Value::Blob(vec![42; 16])).is_err());
+    }
+
+    #[test]
+    fn test_value_ordering_integers() {
+        assert!(Value::Integer(-5) < Value::Integer(3));
+        assert!(Value::Integer(10) > Value::Integer(9));
+        assert!(Value::Identifier(2) > Value::Integer(1));
+        assert!(Value::Boolean(1) > Value::Integer(0));
+        // Numerically equal values of different variants are not equal
+        assert_ne!(
+            Value::Integer(1).cmp(&Value::Identifier(1)),
+            std::cmp::Ordering::Equal
+        );
+        assert_eq!(
+            Value::Integer(7).cmp(&Value::Integer(7)),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_value_ordering_text() {
+        assert!(Value::from("apple") < Value::from("banana"));
+        assert!(Value::from("B") < Value::from("a"));
+        assert!(Value::from("") < Value::from("a"));
+        // Text always sorts after numbers
+        assert!(Value::from("1") > Value::Integer(1000));
+    }
+
+    #[test]
+    fn test_value_ordering_null() {
+        let mut values = vec![
+            Value::Blob(vec![0]),
+            Value::from("text"),
+            Value::Integer(i64::MIN),
+            Value::Null,
+        ];
+        values.sort();
+        assert_eq!(values[0], Value::Null);
+        assert_eq!(values[1], Value::Integer(i64::MIN));
+        assert_eq!(values[2], Value::from("text"));
+        assert_eq!(values[3], Value::Blob(vec![0]));
+        assert_eq!(Value::Null.cmp(&Value::Null), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_value_time_years() {
+        use time::{format_description::well_known::Iso8601, Date, Month, OffsetDateTime, Time};
+
+        let datetime = |year: i32| {
+            Date::from_calendar_date(year, Month::January, 31)
+                .unwrap()
+                .with_time(Time::MIDNIGHT)
+                .assume_utc()
+        };
+
+        assert_eq!(
+            Value::from(datetime(2024)),
+            Value::Text("2024-01-31T00:00:00Z".to_string())
+        );
+
+        // Years RFC 3339 can not represent are stored with six digits
+        for (year, text) in [
+            (-1, "-000001-01-31T00:00:00.000000000Z"),
+            (10000, "+010000-01-31T00:00:00.000000000Z"),
+        ] {
+            let value = Value::from(datetime(year));
+            assert_eq!(value, Value::Text(text.to_string()));
+            let parsed = OffsetDateTime::parse(text, &Iso8601::DEFAULT).unwrap();
+            assert_eq!(parsed, datetime(year));
+        }
+    }
+
+    #[test]
+    fn test_value_json() {
+        let json = serde_json::json!({
+            "user": {
+                "name": "geekmasher",
+                "roles": ["admin", "user"],
+                "settings": { "theme": "dark", "notifications": true }
+            }
+        });
+
+        let value = Value::from(json.clone());
+        assert_eq!(value, Value::Text(json.to_string()));
+        assert_eq!(Value::from(serde_json::Value::Null), Value::Null);
+
+        let parsed = serde_json::Value::try_from(value).unwrap();
+        assert_eq!(parsed, json);
+        assert_eq!(parsed["user"]["settings"]["theme"], "dark");
+
+        // JSON stored as a blob or text can be read back
+        let blob = Value::Blob(serde_json::to_vec(&json).unwrap());
+        assert_eq!(serde_json::Value::try_from(blob).unwrap(), json);
+        let text = Value::Text(json.to_string());
+        assert_eq!(serde_json::Value::try_from(text).unwrap(), json);
+
+        assert!(serde_json::Value::try_from(Value::Text("{".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_value_try_into_scalars() {
+        assert_eq!(i64::try_from(Value::Integer(42)).unwrap(), 42);
+        assert_eq!(i64::try_from(Value::Text("42".to_string())).unwrap(), 42);
+        assert_eq!(u64::try_from(Value::Identifier(42)).unwrap(), 42);
+        assert!(u64::try_from(Value::Integer(-1)).is_err());
+        assert!(bool::try_from(Value::Boolean(1)).unwrap());
+        assert_eq!(
+            String::try_from(Value::Text("geek".to_string())).unwrap(),
+            "geek"
+        );
+        assert!(String::try_from(Value::Null).is_err());
+        assert_eq!(
+            f64::try_from(Value::Text("31.5".to_string())).unwrap(),
+            31.5
+        );
+        assert_eq!(f64::try_from(Value::Integer(42)).unwrap(), 42.0);
+        assert!(f64::try_from(Value::Text("geek".to_string())).is_err());
+    }
 }
@@ -10,8 +10,51 @@
 //!     created_at: DateTime<Utc>,
 //! }
 //! ```
+//!
+//! Dates and times without a timezone (`NaiveDate` / `NaiveDateTime` / `NaiveTime`)
+//! are stored as ISO 8601 text (`2024-01-31` / `2024-01-31T12:30:00` / `12:30:00`).
+//! Durations are stored as a number of seconds (see `geekorm::utils::duration::chrono`).
+//!
+//! ```rust
+//! # #[cfg(feature = "rusqlite")] {
+//! use geekorm::prelude::*;
+//! use chrono::{NaiveDate, NaiveDateTime};
+//!
+//! #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//! struct Events {
+//!     #[geekorm(primary_key, auto_increment)]
+//!     id: PrimaryKeyInteger,
+//!     day: NaiveDate,
+//!     starts_at: NaiveDateTime,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let connection = rusqlite::Connection::open_in_memory()?;
+//!     Events::create_table(&connection).await?;
+//!
+//!     let day = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+//!     let starts_at = day.and_hms_opt(12, 30, 0).unwrap();
+//!
+//!     let mut event = Events::new(day, starts_at);
+//!     event.save(&connection).await?;
+//!
+//!     let event = Events::fetch_by_primary_key(&connection, event.id).await?;
+//!     # assert_eq!(event.day, day);
+//!     # assert_eq!(event.starts_at, starts_at);
+//!     # assert_eq!(Value::from(day), Value::Text(String::from("2024-01-31")));
+//!     # assert_eq!(Value::from(starts_at), Value::Text(String::from("2024-01-31T12:30:00")));
+//!     Ok(())
+//! }
+//! # }
+//! ```
 use super::Value;
-use chrono::{DateTime, TimeZone};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+/// Format used by chrono to (de)serialize `NaiveDateTime` values
+const NAIVE_DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+/// Format used by chrono to (de)serialize `NaiveTime` values
+const NAIVE_TIME_FORMAT: &str = "%H:%M:%S%.f";
 
 impl<Tz> From<DateTime<Tz>> for Value
 where
@@ -30,3 +73,51 @@ where
         Value::Text(value.to_rfc3339())
     }
 }
+
+impl From<NaiveDate> for Value {
+    fn from(value: NaiveDate) -> Self {
+        Value::Text(value.format("%Y-%m-%d").to_string())
+    }
+}
+
+impl From<&NaiveDate> for Value {
+    fn from(value: &NaiveDate) -> Self {
+        Value::Text(value.format("%Y-%m-%d").to_string())
+    }
+}
+
+impl From<NaiveDateTime> for Value {
+    fn from(value: NaiveDateTime) -> Self {
+        Value::Text(value.format(NAIVE_DATETIME_FORMAT).to_string())
+    }
+}
+
+impl From<&NaiveDateTime> for Value {
+    fn from(value: &NaiveDateTime) -> Self {
+        Value::Text(value.format(NAIVE_DATETIME_FORMAT).to_string())
+    }
+}
+
+impl From<NaiveTime> for Value {
+    fn from(value: NaiveTime) -> Self {
+        Value::Text(value.format(NAIVE_TIME_FORMAT).to_string())
+    }
+}
+
+impl From<&NaiveTime> for Value {
+    fn from(value: &NaiveTime) -> Self {
+        Value::Text(value.format(NAIVE_TIME_FORMAT).to_string())
+    }
+}
+
+impl From<Duration> for Value {
+    fn from(value: Duration) -> Self {
+        Value::Integer(value.num_seconds())
+    }
+}
+
+impl From<&Duration> for Value {
+    fn from(value: &Duration) -> Self {
+        Value::Integer(value.num_seconds())
+    }
+}
@@ -0,0 +1,66 @@
+//! Implementations of `From` trait for `time` types.
+//!
+//! `OffsetDateTime` values are stored as RFC 3339 text and `Date` values as
+//! ISO 8601 dates (`2024-01-31`). To read them back, the fields need to be
+//! (de)serialized in the same format (e.g. `time::serde::rfc3339`).
+//!
+//! RFC 3339 only supports the years `0..=9999`, other years are stored as
+//! ISO 8601 text with a six digit year (`+010000-01-01T00:00:00.000000000Z`).
+//! Use `time::serde::iso8601` to read both formats back.
+//!
+//! Dates need to be written as `time::Date` so they are not confused with
+//! other types named `Date`.
+//!
+//! ```rust
+//! # #[cfg(feature = "time")] {
+//! use geekorm::prelude::*;
+//! use time::OffsetDateTime;
+//!
+//! #[derive(Table, Clone, serde::Serialize, serde::Deserialize)]
+//! struct User {
+//!     id: PrimaryKeyInteger,
+//!     #[serde(with = "time::serde::rfc3339")]
+//!     created_at: OffsetDateTime,
+//!     birthday: time::Date,
+//! }
+//! # }
+//! ```
+use super::Value;
+use time::format_description::well_known::{
+    iso8601::{Config, EncodedConfig},
+    Iso8601, Rfc3339,
+};
+use time::{Date, OffsetDateTime};
+
+/// ISO 8601 with a six digit year (used for years RFC 3339 can not represent)
+const ISO8601_SIX_DIGIT_YEAR: EncodedConfig = Config::DEFAULT.set_year_is_six_digits(true).encode();
+
+impl From<OffsetDateTime> for Value {
+    fn from(value: OffsetDateTime) -> Self {
+        Value::from(&value)
+    }
+}
+
+impl From<&OffsetDateTime> for Value {
+    fn from(value: &OffsetDateTime) -> Self {
+        let text = match value.format(&Rfc3339) {
+            Ok(text) => text,
+            Err(_) => value
+                .format(&Iso8601::<ISO8601_SIX_DIGIT_YEAR>)
+                .unwrap_or_default(),
+        };
+        Value::Text(text)
+    }
+}
+
+impl From<Date> for Value {
+    fn from(value: Date) -> Self {
+        Value::Text(value.to_string())
+    }
+}
+
+impl From<&Date> for Value {
+    fn from(value: &Date) -> Self {
+        Value::Text(value.to_string())
+    }
+}
@@ -33,6 +33,22 @@ pub enum Error {
         query: String,
     },
 
+    /// The row was changed by another update (optimistic concurrency check failed)
+    #[error("Stale Update: Table({table}) row was modified (expected version {version})")]
+    StaleUpdate {
+        /// Table name
+        table: String,
+        /// Version the update expected to find in the database
+        version: i64,
+    },
+
+    /// Unique constraint violation (e.g. a username which is already taken)
+    #[error("Unique Violation: {}", column.as_deref().unwrap_or("unknown column"))]
+    UniqueViolation {
+        /// The column which violated the constraint (if it could be detected)
+        column: Option<String>,
+    },
+
     /// Pagination Error
     #[cfg(feature = "pagination")]
     #[error("Pagination Error: {0}")]
@@ -50,6 +66,15 @@ pub enum Error {
     #[error("Serde Error: {0}")]
     SerdeError(String),
 
+    /// Error compressing or decompressing a value
+    #[cfg(feature = "compression")]
+    #[error("Compression Error: {0}")]
+    CompressionError(String),
+
+    /// Validation Error (a value does not meet the column constraints)
+    #[error("Validation Error: {0}")]
+    ValidationError(String),
+
     /// Unknown Variant
     #[error("Unknown Variant {0}")]
     UnknownVariant(String),
@@ -80,8 +105,13 @@ pub enum Error {
 
     /// RuSQLite Error
     #[cfg(feature = "rusqlite")]
-    #[error("RuSQLite Error occurred: {0}")]
-    RuSQLiteError(String),
+    #[error("RuSQLite Error occurred: {error}")]
+    RuSQLiteError {
+        /// Error message
+        error: String,
+        /// Query (empty if the error is not caused by a query)
+        query: String,
+    },
 
     /// Query Syntax Error
     #[error(
@@ -95,6 +125,65 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Get the query text of the error (if the error contains one)
+    ///
+    /// This is useful for debugging, see [`Error::redacted`] to display the
+    /// error without the query.
+    pub fn query(&self) -> Option<&str> {
+        match self {
+            Error::NoRowsFound { query } | Error::QuerySyntaxError { query, .. } => Some(query),
+            #[cfg(feature = "libsql")]
+            Error::LibSQLError { query, .. } => Some(query),
+            #[cfg(feature = "rusqlite")]
+            Error::RuSQLiteError { query, .. } if !query.is_empty() => Some(query),
+            _ => None,
+        }
+    }
+
+    /// Display the error without the query text
+    ///
+    /// Queries can contain sensitive data (e.g. interpolated identifiers), so use
+    /// this when the error is written to logs. The query is still available
+    /// with [`Error::query`].
+    ///
+    /// ```rust
+    /// use geekorm::Error;
+    ///
+    /// let error = Error::QuerySyntaxError {
+    ///     error: String::from("near \"FORM\": syntax error"),
+    ///     query: String::from("SELECT * FORM Users;"),
+    /// };
+    ///
+    /// let message = error.redacted().to_string();
+    /// # assert_eq!(message, "Query Syntax Error: near \"FORM\": syntax error");
+    /// # assert_eq!(error.query(), Some("SELECT * FORM Users;"));
+    /// ```
+    pub fn redacted(&self) -> impl std::fmt::Display + '_ {
+        Redacted(self)
+    }
+
+    /// Detect a unique constraint violation from a database error message
+    ///
+    /// SQLite reports these as `UNIQUE constraint failed: Table.column`, the
+    /// column is extracted from the message (the first one for composite keys).
+    #[cfg(any(feature = "libsql", feature = "rusqlite"))]
+    pub(crate) fn unique_violation(message: &str) -> Option<Self> {
+        let (_, columns) = message.split_once("UNIQUE constraint failed")?;
+        let column = columns
+            .trim_start_matches(':')
+            .split(',')
+            .next()
+            .map(|column| column.trim())
+            .filter(|column| !column.is_empty())
+            .map(|column| match column.split_once('.') {
+                Some((_, column)) => column.to_string(),
+                None => column.to_string(),
+            });
+        Some(Error::UniqueViolation { column })
+    }
+}
+
 /// GeekORM Migration Error
 #[cfg(feature = "migrations")]
 #[derive(Debug, thiserror::Error, Clone)]
@@ -110,6 +199,14 @@ pub enum MigrationError {
         /// Column name
         column: String,
     },
+    /// Missing Index (table name, column name)
+    #[error("Missing Index on `{table}.{column}`")]
+    MissingIndex {
+        /// Table name
+        table: String,
+        /// Column name
+        column: String,
+    },
     /// Column Type Mismatch (table name, column name, feature)
     #[error("Column Type Mismatch `{table}.{column}`: {feature}")]
     ColumnTypeMismatch {
@@ -136,6 +233,25 @@ pub enum MigrationError {
         column: String,
     },
 
+    /// Renamed Table (old table name, new table name)
+    #[error("Renamed Table `{from}` -> `{to}`")]
+    RenamedTable {
+        /// Old table name (live database)
+        from: String,
+        /// New table name (migration)
+        to: String,
+    },
+    /// Renamed Column (table name, old column name, new column name)
+    #[error("Renamed Column `{table}.{from}` -> `{table}.{to}`")]
+    RenamedColumn {
+        /// Table name
+        table: String,
+        /// Old column name (live database)
+        from: String,
+        /// New column name (migration)
+        to: String,
+    },
+
     /// Upgrade Error (reason)
     #[error("Upgrade Error: {0}")]
     UpgradeError(String),
@@ -143,3 +259,75 @@ pub enum MigrationError {
     #[error("Missing Migration: {0}")]
     MissingMigration(String),
 }
+
+/// Display an error without the query text (see [`Error::redacted`])
+struct Redacted<'e>(&'e Error);
+
+impl std::fmt::Display for Redacted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Error::NoRowsFound { .. } => write!(f, "No Rows Found"),
+            Error::QuerySyntaxError { error, .. } => write!(f, "Query Syntax Error: {}", error),
+            #[cfg(feature = "libsql")]
+            Error::LibSQLError { error, .. } => write!(f, "LibSQL Error: {}", error),
+            error => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "libsql", feature = "rusqlite"))]
+    fn test_unique_violation() {
+        let error = Error::unique_violation("UNIQUE constraint failed: Users.username");
+        assert!(matches!(
+            error,
+            Some(Error::UniqueViolation { column: Some(ref column) }) if column == "username"
+        ));
+
+        let error = Error::unique_violation("UNIQUE constraint failed: Users.a, Users.b");
+        assert!(matches!(
+            error,
+            Some(Error::UniqueViolation { column: Some(ref column) }) if column == "a"
+        ));
+
+        assert!(Error::unique_violation("NOT NULL constraint failed: Users.email").is_none());
+    }
+
+    #[test]
+    fn test_redacted() {
+        let query = "SELECT * FROM Users WHERE username = 'geekmasher';";
+
+        let error = Error::NoRowsFound {
+            query: query.to_string(),
+        };
+        assert!(error.to_string().contains(query));
+        assert_eq!(error.redacted().to_string(), "No Rows Found");
+        assert_eq!(error.query(), Some(query));
+
+        let error = Error::QuerySyntaxError {
+            error: String::from("syntax error"),
+            query: query.to_string(),
+        };
+        assert!(!error.redacted().to_string().contains("geekmasher"));
+        assert_eq!(error.query(), Some(query));
+
+        #[cfg(feature = "rusqlite")]
+        {
+            // Syntax errors keep the SQL in the query field
+            let connection = rusqlite::Connection::open_in_memory().unwrap();
+            let error = Error::from(connection.prepare("SELECT * FORM Users;").unwrap_err());
+            assert!(matches!(error, Error::RuSQLiteError { .. }));
+            assert!(!error.redacted().to_string().contains("FORM Users"));
+            assert_eq!(error.query(), Some("SELECT * FORM Users;"));
+        }
+
+        // Errors without a query are displayed as usual
+        let error = Error::SerdeError(String::from("invalid type"));
+        assert_eq!(error.redacted().to_string(), error.to_string());
+        assert_eq!(error.query(), None);
+    }
+}
@@ -14,12 +14,15 @@ pub mod migrations;
 pub mod queries;
 pub mod utils;
 
-pub use crate::backends::{GeekConnection, GeekConnector};
+pub use crate::backends::readonly::ReadOnly;
+pub use crate::backends::{FetchOrCreate, GeekConnection, GeekConnector, GeekQueryMap};
 #[cfg(feature = "migrations")]
 pub use crate::builder::alter::AlterQuery;
-pub use crate::builder::columns::{Column, Columns};
+pub use crate::builder::columns::{Column, ColumnChange, ColumnDiff, Columns};
 pub use crate::builder::columntypes::{ColumnType, ColumnTypeOptions};
 pub use crate::builder::database::Database;
+#[doc(hidden)]
+pub use crate::builder::database::TableRegistration;
 pub use crate::builder::keys::{ForeignKey, PrimaryKey};
 pub use crate::builder::table::Table;
 pub use crate::builder::values::{Value, Values};
@@ -29,6 +32,7 @@ pub use crate::queries::pages::Page;
 #[cfg(feature = "pagination")]
 pub use crate::queries::pagination::Pagination;
 pub use crate::queries::{Query, QueryBuilder};
+pub use crate::utils::blob::{FromBlob, ToBlob};
 #[cfg(feature = "two-factor-auth")]
 pub use crate::utils::tfa::TwoFactorAuth;
 #[cfg(feature = "libsql")]
@@ -36,6 +40,10 @@ pub use backends::libsql;
 #[cfg(feature = "migrations")]
 pub use migrations::Migration;
 
+/// Re-export the `inventory` crate (used by the derive to register tables)
+#[doc(hidden)]
+pub use inventory;
+
 /// Trait for basic creation of tables
 ///
 /// This trait is used to define the table structure for the database.
@@ -52,6 +60,44 @@ where
 
     /// Get the name of the table
     fn table_name() -> String;
+
+    /// Validate the column constraints (`min_length` / `max_length`) of the current object
+    ///
+    /// Called by `save`, `update` and `save_batch` before anything is written to the database.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     #[geekorm(min_length = 3, max_length = 16)]
+    ///     pub username: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     let user = Users::new("geekmasher");
+    ///     # assert!(user.validate().is_ok());
+    ///
+    ///     // A single invalid row fails the whole batch before anything is inserted
+    ///     let users = vec![Users::new("geekmasher"), Users::new("me")];
+    ///     # assert!(users[1].validate().is_err());
+    ///     let result = Users::save_batch(&connection, &users).await;
+    ///     # assert!(matches!(result, Err(geekorm::Error::ValidationError(_))));
+    ///     # assert_eq!(Users::total(&connection).await?, 0);
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    fn validate(&self) -> Result<(), crate::Error> {
+        Ok(())
+    }
 }
 
 /// Trait for Building Queries
@@ -78,6 +124,20 @@ where
     /// Insert a row into the table
     fn query_insert(item: &Self) -> Query;
 
+    /// Insert a row into the table, ignoring conflicts (`INSERT OR IGNORE`)
+    ///
+    /// By default this is built from [`QueryBuilderTrait::query_insert`].
+    fn query_insert_or_ignore(item: &Self) -> Query {
+        Self::query_insert(item).or_ignore()
+    }
+
+    /// Insert a row into the table and return the inserted row (`RETURNING`)
+    ///
+    /// By default this is built from [`QueryBuilderTrait::query_insert`].
+    fn query_insert_returning(item: &Self) -> Query {
+        Self::query_insert(item).returning_all()
+    }
+
     /// Update a row in the table
     fn query_update(item: &Self) -> Query;
 
@@ -100,6 +160,18 @@ where
     /// Get the primary key column name
     fn primary_key_value(&self) -> Value;
 
+    /// Check if the primary key has been set (the row has been saved)
+    ///
+    /// The derive uses `PrimaryKey::is_set` for [`PrimaryKey`] fields, other
+    /// key types are unset when they are `0`, empty or `NULL`.
+    fn primary_key_is_set(&self) -> bool {
+        match self.primary_key_value() {
+            Value::Null | Value::Identifier(0) | Value::Integer(0) => false,
+            Value::Text(value) => !value.is_empty(),
+            _ => true,
+        }
+    }
+
     /// Select a row by the primary key
     fn query_select_by_primary_key(pk: impl Into<Value>) -> Query {
         Self::query_select()
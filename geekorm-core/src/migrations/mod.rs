@@ -4,7 +4,7 @@
 
 pub mod validate;
 
-use crate::backends::TableInfo;
+use crate::backends::{IndexInfo, TableInfo};
 use crate::builder::models::QueryType;
 use crate::error::MigrationError;
 use crate::{Database, GeekConnection, Query, Table, Values};
@@ -25,6 +25,7 @@ pub enum MigrationState {
 }
 
 pub(crate) type DatabaseTables = Vec<(String, Vec<TableInfo>)>;
+pub(crate) type DatabaseIndexes = Vec<(String, Vec<IndexInfo>)>;
 
 /// Migration trait
 pub trait Migration
@@ -86,9 +87,12 @@ where
         }
 
         let mut database_table_columns: DatabaseTables = Vec::new();
+        let mut database_indexes: DatabaseIndexes = Vec::new();
         for table in database_tables {
             let dbcolumns = C::pragma_info(connection, table.as_str()).await?;
-            database_table_columns.push((table, dbcolumns));
+            let indexes = C::index_info(connection, table.as_str()).await?;
+            database_table_columns.push((table.clone(), dbcolumns));
+            database_indexes.push((table, indexes));
         }
 
         let mut migrations: Vec<Box<dyn Migration>> = Vec::new();
@@ -97,7 +101,14 @@ where
             log::debug!("Validating database schema");
         }
 
-        let state = Self::validate(&mut migrations, database, &database_table_columns)?;
+        let mut state = Self::validate(&mut migrations, database, &database_table_columns)?;
+        if !matches!(state, MigrationState::OutOfDate(_)) {
+            let mut validator = Validator {
+                errors: Vec::new(),
+                quick: true,
+            };
+            state = validate::validate_indexes(&database_indexes, database, &mut validator)?;
+        }
 
         for migration in migrations {
             #[cfg(feature = "log")]
@@ -1,6 +1,6 @@
 //! # Validate
 use crate::error::MigrationError;
-use crate::{backends::TableInfo, Database};
+use crate::{backends::TableInfo, Columns, Database};
 
 use super::MigrationState;
 
@@ -39,75 +39,62 @@ pub fn validate_database(
         }
     }
 
+    // Detect a renamed table (one table missing on each side with the same columns)
+    if !validator.quick {
+        detect_table_rename(database_tables, migration_database, validator);
+    }
+
     // Validate each table
     for (name, table) in database_tables {
         if let Some(mtable) = migration_database.get_table(name.as_str()) {
-            // Mismatched column count
-            if mtable.columns.len() != table.len() {
-                state = MigrationState::OutOfDate("Column count mismatch".to_string());
+            #[cfg(feature = "log")]
+            {
+                log::debug!("Columns :: {:?}", table);
             }
+            let live = Columns::from(
+                table
+                    .iter()
+                    .map(crate::Column::from)
+                    .collect::<Vec<crate::Column>>(),
+            );
+            let diff = live.diff(&mtable.columns);
 
-            for dbcolumn in table {
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("Columns :: {:?}", dbcolumn);
+            // Columns only in the live database or only in the migration
+            for column in diff.removed.iter().chain(diff.added.iter()) {
+                validator.errors.push(MigrationError::MissingColumn {
+                    table: name.to_string(),
+                    column: column.to_string(),
+                });
+                state = MigrationState::OutOfDate(format!("Column not found: {}.{}", name, column));
+                if validator.quick {
+                    return Ok(state);
                 }
-                if let Some(mcolumn) = mtable.columns.get(dbcolumn.name.as_str()) {
-                    match validate_column(name, dbcolumn, mcolumn, &mut validator.errors) {
-                        MigrationState::UpToDate | MigrationState::Initialized => {}
-                        MigrationState::OutOfDate(reason) => {
-                            state = MigrationState::OutOfDate(reason);
-                            if validator.quick {
-                                return Ok(state);
-                            }
-                        }
-                    }
-                } else {
-                    validator.errors.push(MigrationError::MissingColumn {
-                        table: name.to_string(),
-                        column: dbcolumn.name.to_string(),
-                    });
-
-                    state = MigrationState::OutOfDate(format!(
-                        "Column not found: {}.{}",
-                        name, dbcolumn.name
-                    ));
-                    if validator.quick {
-                        return Ok(state);
-                    }
+            }
+            // Columns in both with a different type or constraints
+            for change in diff.changed {
+                state = MigrationState::OutOfDate(format!(
+                    "Column mismatch: {}.{} ({})",
+                    name, change.column, change.feature
+                ));
+                validator.errors.push(MigrationError::ColumnTypeMismatch {
+                    table: name.to_string(),
+                    column: change.column,
+                    feature: change.feature,
+                });
+                if validator.quick {
+                    return Ok(state);
                 }
             }
 
-            // HACK: This is a little hacky, but we need to validate all columns
-            for mcolumn in mtable.columns.columns.iter() {
-                #[cfg(feature = "log")]
-                {
-                    log::debug!("Migration Columns :: {:?}", mcolumn);
-                }
-                if let Some(dbcolumn) = table.iter().find(|c| c.name == mcolumn.name) {
-                    match validate_column(name, dbcolumn, mcolumn, &mut validator.errors) {
-                        MigrationState::UpToDate | MigrationState::Initialized => {}
-                        MigrationState::OutOfDate(reason) => {
-                            state = MigrationState::OutOfDate(reason);
-                            if validator.quick {
-                                return Ok(state);
-                            }
-                        }
-                    }
-                } else {
-                    validator.errors.push(MigrationError::MissingColumn {
-                        table: name.to_string(),
-                        column: mcolumn.name.to_string(),
-                    });
-                    state = MigrationState::OutOfDate(format!(
-                        "Column not found: {}.{}",
-                        name, mcolumn.name
-                    ));
-                    if validator.quick {
-                        return Ok(state);
-                    }
-                }
+            if !validator.quick {
+                detect_column_rename(name, table, mtable, validator);
             }
+        } else if validator
+            .errors
+            .iter()
+            .any(|e| matches!(e, MigrationError::RenamedTable { from, .. } if from == name))
+        {
+            state = MigrationState::OutOfDate(format!("Table renamed: {}", name));
         } else {
             validator
                 .errors
@@ -119,38 +106,213 @@ pub fn validate_database(
             }
         }
     }
+
+    // Tables in the migration which are not in the database (and not renamed)
+    for mtable in migration_database.tables.iter() {
+        let exists = database_tables.iter().any(|(name, _)| name == &mtable.name);
+        let renamed = validator
+            .errors
+            .iter()
+            .any(|e| matches!(e, MigrationError::RenamedTable { to, .. } if *to == mtable.name));
+        if !exists && !renamed {
+            validator.errors.push(MigrationError::NewTable {
+                table: mtable.name.clone(),
+            });
+            state = MigrationState::OutOfDate(format!("New table: {}", mtable.name));
+            if validator.quick {
+                return Ok(state);
+            }
+        }
+    }
     Ok(state)
 }
 
-/// Validate a column
+/// Validate the indexes of the database schema
 ///
-/// `column` is the source of truth (the migration file)
-fn validate_column(
-    table: &String,
-    dbcolumn: &TableInfo,
-    column: &crate::Column,
-    errors: &mut Vec<MigrationError>,
-) -> MigrationState {
+/// Every indexed column in the migration needs an index on the column in the
+/// live database. Tables which are not in the live database are reported by
+/// [`validate_database`] and skipped here.
+pub fn validate_indexes(
+    database_indexes: &super::DatabaseIndexes,
+    migration_database: &Database,
+    validator: &mut Validator,
+) -> Result<MigrationState, crate::Error> {
     let mut state = MigrationState::UpToDate;
 
-    // Primary key check
-    if column.is_primary_key() && dbcolumn.pk != 1 {
-        errors.push(MigrationError::ColumnTypeMismatch {
-            table: table.to_string(),
-            column: column.name.clone(),
-            feature: "primary-key".to_string(),
-        });
+    for (name, indexes) in database_indexes {
+        let Some(mtable) = migration_database.get_table(name.as_str()) else {
+            continue;
+        };
+
+        for column in mtable.columns.columns.iter().filter(|c| c.is_indexed()) {
+            let column_name = mtable.columns.column_name(&column.name);
+            if indexes
+                .iter()
+                .any(|index| index.columns == [column_name.as_str()])
+            {
+                continue;
+            }
+
+            validator.errors.push(MigrationError::MissingIndex {
+                table: name.to_string(),
+                column: column.name.clone(),
+            });
+            state = MigrationState::OutOfDate(format!("Index not found: {}.{}", name, column_name));
+            if validator.quick {
+                return Ok(state);
+            }
+        }
+    }
+    Ok(state)
+}
+
+/// Detect a renamed table using a simple heuristic
+///
+/// If exactly one live table is missing from the migration, exactly one
+/// migration table is missing from the live database, and both have the same
+/// column names, the table is assumed to have been renamed.
+fn detect_table_rename(
+    database_tables: &super::DatabaseTables,
+    migration_database: &Database,
+    validator: &mut Validator,
+) {
+    let removed: Vec<&(String, Vec<TableInfo>)> = database_tables
+        .iter()
+        .filter(|(name, _)| migration_database.get_table(name).is_none())
+        .collect();
+    let added: Vec<&crate::Table> = migration_database
+        .tables
+        .iter()
+        .filter(|table| !database_tables.iter().any(|(name, _)| *name == table.name))
+        .collect();
+
+    if let ([(from, dbcolumns)], [mtable]) = (removed.as_slice(), added.as_slice()) {
+        let same_columns = dbcolumns.len() == mtable.columns.len()
+            && dbcolumns
+                .iter()
+                .all(|dbcolumn| mtable.columns.get(dbcolumn.name.as_str()).is_some());
 
-        state = MigrationState::OutOfDate("Primary key constraint not set".to_string());
+        if same_columns {
+            validator.errors.push(MigrationError::RenamedTable {
+                from: from.to_string(),
+                to: mtable.name.clone(),
+            });
+        }
     }
-    // Not null check
-    if column.is_not_null() && dbcolumn.notnull == 0 {
-        errors.push(MigrationError::ColumnTypeMismatch {
+}
+
+/// Detect a renamed column using a simple heuristic
+///
+/// If exactly one live column is missing from the migration table and exactly
+/// one migration column is missing from the live table, and both have the same
+/// type, the `MissingColumn` errors are replaced with a `RenamedColumn` error.
+fn detect_column_rename(
+    table: &String,
+    dbcolumns: &[TableInfo],
+    mtable: &crate::Table,
+    validator: &mut Validator,
+) {
+    let live = Columns::from(
+        dbcolumns
+            .iter()
+            .map(crate::Column::from)
+            .collect::<Vec<crate::Column>>(),
+    );
+    let diff = live.diff(&mtable.columns);
+
+    if let ([from], [to]) = (diff.removed.as_slice(), diff.added.as_slice()) {
+        let (Some(from_column), Some(to_column)) = (live.get(from), mtable.columns.get(to)) else {
+            return;
+        };
+        if from_column.column_type.sql_type() != to_column.column_type.sql_type() {
+            return;
+        }
+
+        validator.errors.retain(|e| {
+            !matches!(
+                e,
+                MigrationError::MissingColumn { table: t, column }
+                    if t == table && (column == from || column == to)
+            )
+        });
+        validator.errors.push(MigrationError::RenamedColumn {
             table: table.to_string(),
-            column: column.name.clone(),
-            feature: "not-null".to_string(),
+            from: from.clone(),
+            to: to.clone(),
         });
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::IndexInfo;
+    use crate::builder::alter::AlterMode;
+    use crate::{AlterQuery, Column, ColumnType, ColumnTypeOptions, Table, ToSqlite};
 
-    state
+    fn database() -> Database {
+        Database {
+            tables: vec![Table {
+                name: "Users".to_string(),
+                columns: vec![
+                    Column::new(
+                        "id".to_string(),
+                        ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                    ),
+                    Column::new(
+                        "age".to_string(),
+                        ColumnType::Integer(ColumnTypeOptions {
+                            index: true,
+                            ..Default::default()
+                        }),
+                    ),
+                ]
+                .into(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_missing_index() {
+        let database = database();
+        let mut validator = Validator {
+            errors: Vec::new(),
+            quick: false,
+        };
+
+        let live = vec![("Users".to_string(), Vec::new())];
+        let state = validate_indexes(&live, &database, &mut validator).unwrap();
+        assert!(matches!(state, MigrationState::OutOfDate(_)));
+        assert!(matches!(
+            validator.errors.as_slice(),
+            [MigrationError::MissingIndex { table, column }] if table == "Users" && column == "age"
+        ));
+
+        // The migration creates the missing index
+        let table = database.get_table("Users").unwrap();
+        let column = table.columns.get("age").unwrap();
+        let query = AlterQuery::new(AlterMode::AddIndex, "Users", "age");
+        assert_eq!(
+            column.on_alter(&query).unwrap(),
+            "CREATE INDEX IF NOT EXISTS idx_Users_age ON Users (age);"
+        );
+
+        // Any index on the column is accepted
+        let live = vec![(
+            "Users".to_string(),
+            vec![IndexInfo {
+                name: "idx_Users_age".to_string(),
+                origin: "c".to_string(),
+                columns: vec!["age".to_string()],
+                ..Default::default()
+            }],
+        )];
+        let mut validator = Validator {
+            errors: Vec::new(),
+            quick: false,
+        };
+        let state = validate_indexes(&live, &database, &mut validator).unwrap();
+        assert!(matches!(state, MigrationState::UpToDate));
+        assert!(validator.errors.is_empty());
+    }
 }
@@ -1,8 +1,10 @@
 #[cfg(feature = "pagination")]
 use super::pages::Page;
 use crate::builder::{
-    joins::{TableJoin, TableJoinOptions, TableJoins},
+    case::CaseBuilder,
+    joins::{TableJoin, TableJoins},
     models::{QueryCondition, QueryOrder, QueryType, WhereCondition},
+    quote_identifier,
 };
 use crate::{
     builder::values::{Value, Values},
@@ -10,6 +12,26 @@ use crate::{
     Error, Table, ToSqlite,
 };
 
+/// Generate the `and_where_*` / `or_where_*` methods, which all add a condition
+/// with [`QueryBuilder::add_where_joined`]
+macro_rules! where_joined {
+    ($($(#[$doc:meta])* $name:ident => $connective:ident, $condition:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name(mut self, column: &str, value: impl Into<Value>) -> Self {
+                QueryBuilder::add_where_joined(
+                    &mut self,
+                    WhereCondition::$connective,
+                    column,
+                    QueryCondition::$condition,
+                    value.into(),
+                );
+                self
+            }
+        )*
+    };
+}
+
 /// The QueryBuilder is how you can build dynamically queries using the builder pattern.
 ///
 /// # Features
@@ -26,7 +48,7 @@ use crate::{
 ///   - Order By: Build a query with order by conditions
 ///   - Limit: Build a query with a limit
 /// - Joins: Build a query with joins 2 tables
-///   - Only Inner Joins are supported currently
+///   - Inner Joins and Left (Outer) Joins are supported
 ///
 /// # Example
 /// ```rust
@@ -69,6 +91,10 @@ pub struct QueryBuilder {
     pub(crate) aliases: bool,
 
     pub(crate) columns: Vec<String>,
+    /// Raw expressions to select (expression, alias)
+    pub(crate) expressions: Vec<(String, String)>,
+    /// Parameters bound by the select list expressions (e.g. CASE)
+    pub(crate) expression_values: Values,
 
     /// Count the rows instead of returning them
     pub(crate) count: bool,
@@ -83,11 +109,22 @@ pub struct QueryBuilder {
     pub(crate) where_condition_last: bool,
     /// The order by clause
     pub(crate) order_by: Vec<(String, QueryOrder)>,
+    /// The order used to keep pages stable (column defaults to the primary key)
+    #[cfg(feature = "pagination")]
+    pub(crate) page_order: Option<(Option<String>, QueryOrder)>,
 
     pub(crate) joins: TableJoins,
+    /// Top N rows per group (partition column, order column, order, N)
+    pub(crate) top_n: Option<(String, String, QueryOrder, usize)>,
 
     /// The values are used for data inserted into the database
     pub(crate) values: Values,
+    /// Parameters bound by the where clause of an update (after the `SET` values)
+    pub(crate) where_values: Values,
+    /// Ignore conflicting rows on insert (`INSERT OR IGNORE`)
+    pub(crate) or_ignore: bool,
+    /// Return all the columns of the written rows (`RETURNING`)
+    pub(crate) returning: bool,
 
     pub(crate) error: Option<Error>,
 }
@@ -127,6 +164,18 @@ impl QueryBuilder {
         }
     }
 
+    /// Build an insert query that ignores conflicts (`INSERT OR IGNORE`)
+    ///
+    /// Rows that would violate a constraint (e.g. a unique column) are skipped
+    /// instead of returning an error, and existing rows are never changed.
+    pub fn insert_or_ignore() -> QueryBuilder {
+        QueryBuilder {
+            query_type: QueryType::Insert,
+            or_ignore: true,
+            ..Default::default()
+        }
+    }
+
     /// Build an update query
     pub fn update() -> QueryBuilder {
         QueryBuilder {
@@ -149,18 +198,181 @@ impl QueryBuilder {
         self
     }
 
+    /// Return all the columns of the inserted, updated or deleted rows (`RETURNING`)
+    ///
+    /// The built query returns rows, so use `query` or `query_first` to
+    /// deserialize them (including columns computed by the database).
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// # fn main() -> Result<(), geekorm::Error> {
+    /// let query = QueryBuilder::insert()
+    ///     .table(Users::table())
+    ///     .add_value("username", "geekmasher")
+    ///     .returning_all()
+    ///     .build()?;
+    /// # assert_eq!(
+    /// #     query.to_str(),
+    /// #     "INSERT INTO Users (username) VALUES (?) RETURNING id, username;"
+    /// # );
+    /// # assert!(query.returns_rows());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn returning_all(mut self) -> Self {
+        match self.query_type {
+            QueryType::Insert | QueryType::Update | QueryType::Delete => {
+                self.returning = true;
+            }
+            _ => {
+                self.error = Some(Error::QueryBuilderError(
+                    String::from(
+                        "`RETURNING` is only supported by insert, update and delete queries",
+                    ),
+                    String::from("returning_all"),
+                ));
+            }
+        }
+        self
+    }
+
     /// Set the columns for the query builder
     pub fn columns(mut self, columns: Vec<&str>) -> Self {
         self.columns = columns.iter().map(|c| c.to_string()).collect();
         self
     }
 
+    /// Select a computed expression with an alias (e.g. `price * quantity AS total`)
+    ///
+    /// The expression is appended to the selected columns and is **not** validated
+    /// or escaped, so it must never contain untrusted input (SQL injection).
+    /// Only the alias is validated to be a plain identifier.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Orders {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub price: i32,
+    ///     pub quantity: i32,
+    /// }
+    ///
+    /// # fn main() {
+    /// let query = Orders::query_select()
+    ///     .select_expr("price * quantity", "total")
+    ///     .build()
+    ///     .expect("Failed to build query");
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, price, quantity, price * quantity AS total FROM Orders;"
+    /// # );
+    /// # }
+    /// ```
+    pub fn select_expr(mut self, expr: &str, alias: &str) -> Self {
+        if alias.is_empty() || !alias.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            self.error = Some(Error::QueryBuilderError(
+                format!("Invalid alias `{}` for expression `{}`", alias, expr),
+                String::from("select_expr"),
+            ));
+        } else {
+            self.expressions.push((expr.to_string(), alias.to_string()));
+        }
+        self
+    }
+
+    /// Select a `CASE WHEN` expression with an alias
+    ///
+    /// The values of the expression are bound as parameters (before any
+    /// where clause parameters). The table needs to be set before calling this.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// # fn main() {
+    /// let query = Users::query_select()
+    ///     .columns(vec!["username"])
+    ///     .select_case(
+    ///         CaseBuilder::new()
+    ///             .when("age", QueryCondition::Gte, 18, "adult")
+    ///             .otherwise("minor"),
+    ///         "category",
+    ///     )
+    ///     .where_ne("username", "admin")
+    ///     .build()
+    ///     .expect("Failed to build query");
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT username, CASE WHEN age >= ? THEN ? ELSE ? END AS category FROM Users WHERE username != ?;"
+    /// # );
+    /// # assert_eq!(query.values.len(), 4);
+    /// # }
+    /// ```
+    pub fn select_case(mut self, case: CaseBuilder, alias: &str) -> Self {
+        if alias.is_empty() || !alias.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            self.error = Some(Error::QueryBuilderError(
+                format!("Invalid alias `{}` for CASE expression", alias),
+                String::from("select_case"),
+            ));
+            return self;
+        }
+        match case.build(&self.table, alias) {
+            Ok((expr, values)) => {
+                self.expressions.push((expr, alias.to_string()));
+                self.expression_values.values.extend(values.values);
+            }
+            Err(err) => self.error = Some(err),
+        }
+        self
+    }
+
     /// Add a value to the list of values for parameterized queries
+    ///
+    /// If the column already has a value, it is replaced (last value wins).
+    /// Values for columns which do not exist in the table poison the build.
     pub fn add_value(mut self, column: &str, value: impl Into<Value>) -> Self {
-        self.values.push(column.to_string(), value.into());
+        if self.table.is_valid_column(column) {
+            self.values.push_unique(column.to_string(), value.into());
+        } else {
+            self.error = Some(Error::QueryBuilderError(
+                format!(
+                    "Column `{}` does not exist in table `{}`",
+                    column, self.table.name
+                ),
+                String::from("add_value"),
+            ));
+        }
         self
     }
 
+    /// Add a value which might have failed to convert (e.g. a compressed blob)
+    ///
+    /// An error poisons the build, the same as an invalid column in [`QueryBuilder::add_value`].
+    pub fn try_add_value(mut self, column: &str, value: Result<Value, Error>) -> Self {
+        match value {
+            Ok(value) => self.add_value(column, value),
+            Err(err) => {
+                self.error = Some(err);
+                self
+            }
+        }
+    }
+
     /// Add an AND condition to the where clause
     pub fn and(mut self) -> Self {
         self.where_clause.push(WhereCondition::And.to_sqlite());
@@ -175,23 +387,166 @@ impl QueryBuilder {
         self
     }
 
+    /// Add a where clause joined to the previous condition with an explicit
+    /// connective (`AND` / `OR`)
+    ///
+    /// The connective is only added if there is a previous condition, so the
+    /// first condition never starts with `AND` / `OR`.
+    fn add_where_joined(
+        &mut self,
+        connective: WhereCondition,
+        column: &str,
+        condition: QueryCondition,
+        value: Value,
+    ) {
+        if !self.where_clause.is_empty() && !self.where_condition_last {
+            self.where_clause.push(connective.to_sqlite());
+            self.where_condition_last = true;
+        }
+        self.add_where(column, condition, value);
+    }
+
     /// The underlying function to add a where clause
+    ///
+    /// Columns can be qualified with a table name (`table.column`). Values for
+    /// columns of the current table are keyed by the bare column name, while
+    /// values for joined tables keep the qualified name so they never collide
+    /// with a column of the same name in the current table.
     fn add_where(&mut self, column: &str, condition: QueryCondition, value: Value) {
+        self.add_where_with(column, condition, value, false);
+    }
+
+    /// Add a where clause, optionally casting the column to an integer so the
+    /// comparison is numeric regardless of how the value is stored
+    fn add_where_with(
+        &mut self,
+        column: &str,
+        condition: QueryCondition,
+        value: Value,
+        numeric: bool,
+    ) {
+        if let Some((sql_column, key)) = self.where_column(column, "where_eq") {
+            // Check if the last condition was set
+            if !self.where_clause.is_empty() && !self.where_condition_last {
+                // Use the default where condition
+                self.where_clause
+                    .push(WhereCondition::default().to_sqlite());
+            }
+
+            if numeric {
+                self.where_clause.push(format!(
+                    "CAST({} AS INTEGER) {} ?",
+                    quote_identifier(&sql_column),
+                    condition.to_sqlite()
+                ));
+            } else {
+                self.where_clause.push(format!(
+                    "{} {} ?",
+                    quote_identifier(&sql_column),
+                    condition.to_sqlite()
+                ));
+            }
+
+            self.where_values().push(key, value);
+            self.where_condition_last = false;
+        }
+    }
+
+    /// Add a `column IN (?, ...)` (or `NOT IN`) where clause with one placeholder per value
+    fn add_where_in(&mut self, column: &str, values: Vec<Value>, negate: bool) {
+        let fn_name = if negate { "where_not_in" } else { "where_in" };
+        if values.is_empty() {
+            self.error = Some(Error::QueryBuilderError(
+                format!("No values were provided for column `{}`", column),
+                String::from(fn_name),
+            ));
+            return;
+        }
+
+        if let Some((sql_column, key)) = self.where_column(column, fn_name) {
+            if !self.where_clause.is_empty() && !self.where_condition_last {
+                self.where_clause
+                    .push(WhereCondition::default().to_sqlite());
+            }
+
+            let placeholders = vec!["?"; values.len()].join(", ");
+            self.where_clause.push(format!(
+                "{} {} ({})",
+                quote_identifier(&sql_column),
+                if negate { "NOT IN" } else { "IN" },
+                placeholders
+            ));
+
+            for value in values {
+                self.where_values().push(key.clone(), value);
+            }
+            self.where_condition_last = false;
+        }
+    }
+
+    /// Add a `column BETWEEN ? AND ?` (or `NOT BETWEEN`) where clause
+    fn add_where_between(&mut self, column: &str, low: Value, high: Value, negate: bool) {
+        let fn_name = if negate {
+            "where_not_between"
+        } else {
+            "where_between"
+        };
+        if let Some((sql_column, key)) = self.where_column(column, fn_name) {
+            if !self.where_clause.is_empty() && !self.where_condition_last {
+                self.where_clause
+                    .push(WhereCondition::default().to_sqlite());
+            }
+
+            self.where_clause.push(format!(
+                "{} {} ? AND ?",
+                quote_identifier(&sql_column),
+                if negate { "NOT BETWEEN" } else { "BETWEEN" }
+            ));
+
+            self.where_values().push(key.clone(), low);
+            self.where_values().push(key, high);
+            self.where_condition_last = false;
+        }
+    }
+
+    /// The values bound by the where clause
+    ///
+    /// Updates keep them apart from the `SET` values so a column can be both
+    /// written and compared (e.g. `SET version = 2 WHERE ... AND version = 1`).
+    fn where_values(&mut self) -> &mut Values {
+        match self.query_type {
+            QueryType::Update => &mut self.where_values,
+            _ => &mut self.values,
+        }
+    }
+
+    /// Resolve the column of a where clause
+    ///
+    /// Returns the name of the column in the database and the key used for its
+    /// values, or sets an error if the column (or its table) does not exist.
+    fn where_column(&mut self, column: &str, fn_name: &str) -> Option<(String, String)> {
         let mut column_name: &str = column;
+        let mut joined = false;
 
         // Check if there is a `.` in the column name
         let table: &Table = if let Some((ftable, fcolumn)) = column.split_once('.') {
-            match self.joins.get(ftable) {
-                Some(TableJoin::InnerJoin(TableJoinOptions { child, .. })) => {
-                    column_name = fcolumn;
-                    child
-                }
-                _ => {
-                    self.error = Some(Error::QueryBuilderError(
-                        format!("Table `{}` does not exist", ftable),
-                        String::from("where_eq"),
-                    ));
-                    &self.table
+            if ftable == self.table.name {
+                column_name = fcolumn;
+                &self.table
+            } else {
+                match self.joins.get(ftable) {
+                    Some(join) => {
+                        column_name = fcolumn;
+                        joined = true;
+                        &join.options().child
+                    }
+                    _ => {
+                        self.error = Some(Error::QueryBuilderError(
+                            format!("Table `{}` does not exist", ftable),
+                            String::from(fn_name),
+                        ));
+                        &self.table
+                    }
                 }
             }
         } else {
@@ -199,25 +554,24 @@ impl QueryBuilder {
         };
 
         if table.is_valid_column(column_name) {
-            // Check if the last condition was set
-            if !self.where_clause.is_empty() && !self.where_condition_last {
-                // Use the default where condition
-                self.where_clause
-                    .push(WhereCondition::default().to_sqlite());
-            }
-
-            self.where_clause
-                .push(format!("{} {} ?", column, condition.to_sqlite()));
-            self.values.push(column.to_string(), value);
-            self.where_condition_last = false;
+            // Use the database name of the column (renamed columns use the alias)
+            let sql_column = match column.split_once('.') {
+                Some((ftable, _)) => {
+                    format!("{}.{}", ftable, table.columns.column_name(column_name))
+                }
+                None => table.columns.column_name(column_name),
+            };
+            let key = if joined { column } else { column_name };
+            Some((sql_column, key.to_string()))
         } else {
             self.error = Some(Error::QueryBuilderError(
                 format!(
                     "Column `{}` does not exist in table `{}`",
                     column_name, table.name
                 ),
-                String::from("where_eq"),
+                String::from(fn_name),
             ));
+            None
         }
     }
 
@@ -263,6 +617,277 @@ impl QueryBuilder {
         self
     }
 
+    /// Where clause for equals, joined to the previous condition with `AND`
+    ///
+    /// Unlike [`QueryBuilder::where_eq`], the connective is always explicit.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// let query = Users::query_select()
+    ///     .where_eq("username", "geekmasher")
+    ///     .or_where_eq("username", "bob")
+    ///     .and_where_gt("age", 21)
+    ///     .build()
+    ///     .expect("Failed to build query");
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username, age FROM Users WHERE username = ? OR username = ? AND age > ?;"
+    /// # );
+    /// ```
+    pub fn and_where_eq(mut self, column: &str, value: impl Into<Value>) -> Self {
+        QueryBuilder::add_where_joined(
+            &mut self,
+            WhereCondition::And,
+            column,
+            QueryCondition::Eq,
+            value.into(),
+        );
+        self
+    }
+
+    where_joined! {
+        /// Where clause for not equals, joined to the previous condition with `AND`
+        and_where_ne => And, Ne;
+        /// Where clause for like, joined to the previous condition with `AND`
+        and_where_like => And, Like;
+        /// Where clause for greater than, joined to the previous condition with `AND`
+        and_where_gt => And, Gt;
+        /// Where clause for less than, joined to the previous condition with `AND`
+        and_where_lt => And, Lt;
+        /// Where clause for greater than or equal to, joined to the previous condition with `AND`
+        and_where_gte => And, Gte;
+        /// Where clause for less than or equal to, joined to the previous condition with `AND`
+        and_where_lte => And, Lte;
+        /// Where clause for equals, joined to the previous condition with `OR`
+        or_where_eq => Or, Eq;
+        /// Where clause for not equals, joined to the previous condition with `OR`
+        or_where_ne => Or, Ne;
+        /// Where clause for like, joined to the previous condition with `OR`
+        or_where_like => Or, Like;
+        /// Where clause for greater than, joined to the previous condition with `OR`
+        or_where_gt => Or, Gt;
+        /// Where clause for less than, joined to the previous condition with `OR`
+        or_where_lt => Or, Lt;
+        /// Where clause for greater than or equal to, joined to the previous condition with `OR`
+        or_where_gte => Or, Gte;
+        /// Where clause for less than or equal to, joined to the previous condition with `OR`
+        or_where_lte => Or, Lte;
+    }
+
+    /// Where clause comparing the column as an integer (`CAST(column AS INTEGER)`)
+    ///
+    /// This is useful for numeric values stored as text where SQLite would otherwise
+    /// compare the values lexicographically (`"100" < "20"`).
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Imports {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub age: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Imports::create_table(&connection).await?;
+    ///     for age in ["3", "20", "100"] {
+    ///         Imports::new(age).save(&connection).await?;
+    ///     }
+    ///
+    ///     let query = Imports::query_select()
+    ///         .where_gt_numeric("age", 20)
+    ///         .build()?;
+    ///     # assert_eq!(query.query, "SELECT id, age FROM Imports WHERE CAST(age AS INTEGER) > ?;");
+    ///     let rows: Vec<Imports> = rusqlite::Connection::query(&connection, query).await?;
+    ///     # assert_eq!(rows.len(), 1);
+    ///     # assert_eq!(rows[0].age, "100");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub fn where_numeric(
+        mut self,
+        column: &str,
+        condition: QueryCondition,
+        value: impl Into<Value>,
+    ) -> Self {
+        QueryBuilder::add_where_with(&mut self, column, condition, value.into(), true);
+        self
+    }
+
+    /// Where clause for greater than (compared as integers)
+    pub fn where_gt_numeric(self, column: &str, value: impl Into<Value>) -> Self {
+        self.where_numeric(column, QueryCondition::Gt, value)
+    }
+
+    /// Where clause for less than (compared as integers)
+    pub fn where_lt_numeric(self, column: &str, value: impl Into<Value>) -> Self {
+        self.where_numeric(column, QueryCondition::Lt, value)
+    }
+
+    /// Where clause for greater than or equal to (compared as integers)
+    pub fn where_gte_numeric(self, column: &str, value: impl Into<Value>) -> Self {
+        self.where_numeric(column, QueryCondition::Gte, value)
+    }
+
+    /// Where clause for less than or equal to (compared as integers)
+    pub fn where_lte_numeric(self, column: &str, value: impl Into<Value>) -> Self {
+        self.where_numeric(column, QueryCondition::Lte, value)
+    }
+
+    /// Where clause for a column matching any of the values (`IN`)
+    ///
+    /// Each value is bound as a parameter (in order), an empty list of values
+    /// is an error.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// let query = Users::query_select()
+    ///     .where_in("username", vec!["geekmasher", "bob", "alice"])
+    ///     .build()?;
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username FROM Users WHERE username IN (?, ?, ?);"
+    /// # );
+    /// # Ok::<(), geekorm::Error>(())
+    /// ```
+    pub fn where_in(mut self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        QueryBuilder::add_where_in(&mut self, column, values, false);
+        self
+    }
+
+    /// Where clause for a column not matching any of the values (`NOT IN`)
+    ///
+    /// Each value is bound as a parameter (in order), an empty list of values
+    /// is an error.
+    pub fn where_not_in(mut self, column: &str, values: Vec<impl Into<Value>>) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        QueryBuilder::add_where_in(&mut self, column, values, true);
+        self
+    }
+
+    /// Where clause for a column within a range (`BETWEEN`, both bounds are inclusive)
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// let query = Users::query_select()
+    ///     .where_between("age", 18, 65)
+    ///     .build()?;
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username, age FROM Users WHERE age BETWEEN ? AND ?;"
+    /// # );
+    /// # Ok::<(), geekorm::Error>(())
+    /// ```
+    pub fn where_between(
+        mut self,
+        column: &str,
+        low: impl Into<Value>,
+        high: impl Into<Value>,
+    ) -> Self {
+        QueryBuilder::add_where_between(&mut self, column, low.into(), high.into(), false);
+        self
+    }
+
+    /// Where clause for a column outside of a range (`NOT BETWEEN`)
+    pub fn where_not_between(
+        mut self,
+        column: &str,
+        low: impl Into<Value>,
+        high: impl Into<Value>,
+    ) -> Self {
+        QueryBuilder::add_where_between(&mut self, column, low.into(), high.into(), true);
+        self
+    }
+
+    /// Where clause from a raw SQL fragment with a list of values (`IN (...)`)
+    ///
+    /// The `(...)` marker in the fragment is expanded to one placeholder per
+    /// value and the values are bound in order, so the number of placeholders
+    /// always matches the number of values.
+    ///
+    /// Security: the fragment is added to the query as-is and must never
+    /// contain user input, only the values are bound as parameters.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    /// }
+    ///
+    /// let query = Users::query_select()
+    ///     .where_raw_in("username IN (...)", vec!["geekmasher", "bob", "alice"])
+    ///     .build()
+    ///     .expect("Failed to build query");
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username FROM Users WHERE username IN (?, ?, ?);"
+    /// # );
+    /// # assert_eq!(query.values.len(), 3);
+    /// ```
+    pub fn where_raw_in(mut self, fragment: &str, values: Vec<impl Into<Value>>) -> Self {
+        const MARKER: &str = "(...)";
+
+        if !fragment.contains(MARKER) {
+            self.error = Some(Error::QueryBuilderError(
+                format!("Fragment `{}` is missing the `{}` marker", fragment, MARKER),
+                String::from("where_raw_in"),
+            ));
+            return self;
+        }
+
+        let placeholders = vec!["?"; values.len()].join(", ");
+
+        // Check if the last condition was set
+        if !self.where_clause.is_empty() && !self.where_condition_last {
+            self.where_clause
+                .push(WhereCondition::default().to_sqlite());
+        }
+        self.where_clause
+            .push(fragment.replacen(MARKER, &format!("({})", placeholders), 1));
+
+        for value in values {
+            self.where_values().push(fragment.to_string(), value.into());
+        }
+        self.where_condition_last = false;
+        self
+    }
+
     /// Filter the query by multiple fields
     pub fn filter(mut self, fields: Vec<(&str, impl Into<Value>)>) -> Self {
         for (field, value) in fields {
@@ -283,10 +908,111 @@ impl QueryBuilder {
         self
     }
 
+    /// Add a list of conditions which all have to match (joined with `AND`)
+    ///
+    /// The group is joined to any previous condition with the default connective
+    /// (`AND`) unless `and()` / `or()` was called first.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// let query = Users::query_select()
+    ///     .where_all(vec![
+    ///         ("age", QueryCondition::Gte, Value::from(18)),
+    ///         ("username", QueryCondition::Like, Value::from("geek%")),
+    ///     ])
+    ///     .build()?;
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username, age FROM Users WHERE age >= ? AND username LIKE ?;"
+    /// # );
+    /// # Ok::<(), geekorm::Error>(())
+    /// ```
+    pub fn where_all(mut self, conditions: Vec<(&str, QueryCondition, Value)>) -> Self {
+        self.add_where_group(WhereCondition::And, conditions, false);
+        self
+    }
+
+    /// Add a list of conditions where any of them has to match (joined with `OR`)
+    ///
+    /// The conditions are wrapped in parentheses so they are evaluated as a single
+    /// condition when combined with the rest of the where clause.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// let query = Users::query_select()
+    ///     .where_gt("age", 18)
+    ///     .where_any(vec![
+    ///         ("username", QueryCondition::Eq, Value::from("geekmasher")),
+    ///         ("username", QueryCondition::Eq, Value::from("admin")),
+    ///     ])
+    ///     .build()?;
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username, age FROM Users WHERE age > ? AND (username = ? OR username = ?);"
+    /// # );
+    /// # assert_eq!(query.values.len(), 3);
+    /// # Ok::<(), geekorm::Error>(())
+    /// ```
+    pub fn where_any(mut self, conditions: Vec<(&str, QueryCondition, Value)>) -> Self {
+        self.add_where_group(WhereCondition::Or, conditions, true);
+        self
+    }
+
+    /// Add a group of conditions joined with the same connective
+    fn add_where_group(
+        &mut self,
+        connective: WhereCondition,
+        conditions: Vec<(&str, QueryCondition, Value)>,
+        parenthesize: bool,
+    ) {
+        // Build the group on its own so the connectives are only added between
+        // the conditions of the group
+        let outer = std::mem::take(&mut self.where_clause);
+        let outer_condition_last = std::mem::replace(&mut self.where_condition_last, false);
+
+        for (column, condition, value) in conditions {
+            self.add_where_joined(connective.clone(), column, condition, value);
+        }
+
+        let group = std::mem::replace(&mut self.where_clause, outer);
+        self.where_condition_last = outer_condition_last;
+        if group.is_empty() {
+            return;
+        }
+
+        if !self.where_clause.is_empty() && !self.where_condition_last {
+            // Use the default where condition
+            self.where_clause
+                .push(WhereCondition::default().to_sqlite());
+        }
+        if parenthesize && group.len() > 1 {
+            self.where_clause.push(format!("({})", group.join(" ")));
+        } else {
+            self.where_clause.push(group.join(" "));
+        }
+        self.where_condition_last = false;
+    }
+
     /// Order the query by a particular column
     pub fn order_by(mut self, column: &str, order: QueryOrder) -> Self {
         if self.table.is_valid_column(column) {
-            self.order_by.push((column.to_string(), order));
+            self.order_by.push((self.table.column_name(column), order));
         } else {
             self.error = Some(Error::QueryBuilderError(
                 format!(
@@ -299,57 +1025,306 @@ impl QueryBuilder {
         self
     }
 
-    /// Adds a table to join with the current table
+    /// Order the query by multiple columns in a single call
     ///
-    /// Note: GeekOrm only joins tables with the `INNER JOIN` clause and primary keys
-    pub fn join(mut self, table: Table) -> Self {
-        let key = self.table.get_primary_key();
-        if table.is_valid_column(key.as_str()) || self.table.is_valid_column(key.as_str()) {
-            // TODO(geekmasher): The tables should be references to avoid cloning
-            self.joins
-                .push(TableJoin::new(self.table.clone(), table.clone()));
-        } else {
+    /// Columns are applied in the order given (the first column is the primary sort
+    /// and each following column breaks ties), after any existing `ORDER BY` columns.
+    /// Columns that are already ordered are ignored. All columns are validated
+    /// together and the error lists every invalid column.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    ///     pub postcode: Option<String>,
+    /// }
+    ///
+    /// # fn main() {
+    /// let query = Users::query_select()
+    ///     .order_by_many(&[
+    ///         ("age", QueryOrder::Desc),
+    ///         ("postcode", QueryOrder::AscNullsLast),
+    ///         ("username", QueryOrder::Asc),
+    ///     ])
+    ///     .build()
+    ///     .expect("Failed to build query");
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, username, age, postcode FROM Users ORDER BY age DESC, postcode ASC NULLS LAST, username ASC;"
+    /// # );
+    ///
+    /// // Every invalid column is reported
+    /// let error = Users::query_select()
+    ///     .order_by_many(&[("name", QueryOrder::Asc), ("age", QueryOrder::Asc), ("email", QueryOrder::Desc)])
+    ///     .build()
+    ///     .unwrap_err();
+    /// # assert!(error.to_string().contains("`name`, `email`"));
+    /// # }
+    /// ```
+    pub fn order_by_many(mut self, columns: &[(&str, QueryOrder)]) -> Self {
+        let invalid: Vec<String> = columns
+            .iter()
+            .filter(|(column, _)| !self.table.is_valid_column(column))
+            .map(|(column, _)| format!("`{}`", column))
+            .collect();
+        if !invalid.is_empty() {
             self.error = Some(Error::QueryBuilderError(
-                format!("Column `{}` does not exist in table `{}`", key, table.name),
-                String::from("join"),
+                format!(
+                    "Columns {} do not exist in table `{}`",
+                    invalid.join(", "),
+                    self.table.name
+                ),
+                String::from("order_by_many"),
             ));
+            return self;
         }
-        self
-    }
 
-    /// Count the number of rows in the query
-    pub fn count(mut self) -> Self {
-        self.count = true;
+        for (column, order) in columns {
+            let column = self.table.column_name(column);
+            if !self.order_by.iter().any(|(c, _)| c == &column) {
+                self.order_by.push((column, order.clone()));
+            }
+        }
         self
     }
 
-    /// Add a limit to the query
-    pub fn limit(mut self, limit: usize) -> Self {
-        if limit != 0 {
-            self.limit = Some(limit);
-        } else {
-            self.error = Some(Error::QueryBuilderError(
-                String::from("Limit cannot be 0"),
-                String::from("limit"),
-            ));
+    /// Select the top N rows for each group of rows sharing the same `partition` value
+    ///
+    /// The rows in each group are ranked using `ROW_NUMBER()` ordered by the `order_by`
+    /// column, and only the rows ranked `1..=n` are returned. Where conditions are
+    /// applied before the rows are ranked. Joins are not supported.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Posts {
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub author: String,
+    ///     pub created: i64,
+    /// }
+    ///
+    /// # fn main() {
+    /// // Top 3 newest posts for each author
+    /// let query = Posts::query_select()
+    ///     .top_n_per_group("author", "created", QueryOrder::Desc, 3)
+    ///     .build()
+    ///     .expect("Failed to build query");
+    /// # assert_eq!(
+    /// #     query.query,
+    /// #     "SELECT id, author, created FROM (SELECT *, ROW_NUMBER() OVER (PARTITION BY author ORDER BY created DESC) AS __row_number FROM Posts) WHERE __row_number <= 3;"
+    /// # );
+    /// # }
+    /// ```
+    pub fn top_n_per_group(
+        mut self,
+        partition: &str,
+        order_by: &str,
+        order: QueryOrder,
+        n: usize,
+    ) -> Self {
+        for column in [partition, order_by] {
+            if !self.table.is_valid_column(column) {
+                self.error = Some(Error::QueryBuilderError(
+                    format!(
+                        "Column `{}` does not exist in table `{}`",
+                        column, self.table.name
+                    ),
+                    String::from("top_n_per_group"),
+                ));
+                return self;
+            }
         }
+        self.top_n = Some((partition.to_string(), order_by.to_string(), order, n));
         self
     }
 
-    /// Add an offset to the query
-    pub fn offset(mut self, offset: usize) -> Self {
-        self.offset = Some(offset);
-        self
+    /// Adds a table to join with the current table
+    ///
+    /// Note: GeekOrm joins tables with the `INNER JOIN` clause and primary keys,
+    /// use [`QueryBuilder::left_join`] to keep rows without a joined row
+    pub fn join(self, table: Table) -> Self {
+        self.add_join(table, TableJoin::new, "join")
     }
 
-    /// Add a page to the query
-    #[cfg(feature = "pagination")]
+    /// Adds a table to join with the current table using a `LEFT JOIN`
+    ///
+    /// Rows of the current table are kept even if there is no matching row in
+    /// the joined table (the joined columns are `NULL`).
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Sessions {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub token: String,
+    /// }
+    ///
+    /// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     #[geekorm(foreign_key = "Sessions.id")]
+    ///     pub session: ForeignKey<i32, Sessions>,
+    /// }
+    ///
+    /// # fn main() -> Result<(), geekorm::Error> {
+    /// let query = Users::query_select()
+    ///     .left_join(Sessions::table())
+    ///     .build()?;
+    ///
+    /// assert!(query
+    ///     .to_str()
+    ///     .contains("FROM Users LEFT JOIN Sessions ON Sessions.id = Users.session"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn left_join(self, table: Table) -> Self {
+        self.add_join(table, TableJoin::left, "left_join")
+    }
+
+    fn add_join(
+        mut self,
+        table: Table,
+        join: fn(Table, Table) -> TableJoin,
+        fn_name: &str,
+    ) -> Self {
+        let key = self.table.get_primary_key();
+        if table.is_valid_column(key.as_str()) || self.table.is_valid_column(key.as_str()) {
+            // TODO(geekmasher): The tables should be references to avoid cloning
+            self.joins.push(join(self.table.clone(), table));
+        } else {
+            self.error = Some(Error::QueryBuilderError(
+                format!("Column `{}` does not exist in table `{}`", key, table.name),
+                String::from(fn_name),
+            ));
+        }
+        self
+    }
+
+    /// Alias the selected columns of a join query with their table name
+    ///
+    /// Each column is selected as `Table.column AS Table_column`, so columns with
+    /// the same name in both tables do not clash and the joined row can be
+    /// deserialized into a combined struct. If no columns are set, the columns
+    /// of all the joined tables are selected.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub name: String,
+    /// }
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Posts {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub title: String,
+    ///     #[geekorm(foreign_key = "Users.id")]
+    ///     pub author: ForeignKey<i32, Users>,
+    /// }
+    ///
+    /// /// Combined row of a post and its author
+    /// #[derive(Debug, serde::Deserialize)]
+    /// pub struct PostWithAuthor {
+    ///     #[serde(rename = "Posts_id")]
+    ///     pub id: i32,
+    ///     #[serde(rename = "Posts_title")]
+    ///     pub title: String,
+    ///     #[serde(rename = "Users_name")]
+    ///     pub author: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///     Users::create_table(&connection).await?;
+    ///     Posts::create_table(&connection).await?;
+    ///
+    ///     let mut user = Users::new("geekmasher");
+    ///     user.save(&connection).await?;
+    ///     let mut post = Posts::new("Hello World", user.id);
+    ///     post.save(&connection).await?;
+    ///
+    ///     let query = Posts::query_select()
+    ///         .join(Users::table())
+    ///         .columns(vec!["Posts.id", "Posts.title", "Users.name"])
+    ///         .aliases()
+    ///         .build()?;
+    ///     # assert_eq!(
+    ///     #     query.query,
+    ///     #     "SELECT Posts.id AS Posts_id, Posts.title AS Posts_title, Users.name AS Users_name FROM Posts INNER JOIN Users ON Users.id = Posts.author;"
+    ///     # );
+    ///
+    ///     let rows: Vec<PostWithAuthor> = rusqlite::Connection::query(&connection, query).await?;
+    ///     # assert_eq!(rows.len(), 1);
+    ///     # assert_eq!(rows[0].title, "Hello World");
+    ///     # assert_eq!(rows[0].author, "geekmasher");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub fn aliases(mut self) -> Self {
+        self.aliases = true;
+        self
+    }
+
+    /// Count the number of rows in the query
+    pub fn count(mut self) -> Self {
+        self.count = true;
+        self
+    }
+
+    /// Add a limit to the query
+    ///
+    /// A limit of `0` is valid and generates `LIMIT 0`, which returns no rows.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Add an offset to the query
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Add a page to the query
+    ///
+    /// The query is ordered by the page's order column (or the primary key) after
+    /// any other `ORDER BY` columns so that the pages are deterministic.
+    #[cfg(feature = "pagination")]
     pub fn page(mut self, page: &Page) -> Self {
         self.offset = Some(page.offset() as usize);
         self.limit = Some(page.limit as usize);
+        self.page_order = match page.order() {
+            Some((column, order)) => Some((Some(column.clone()), order.clone())),
+            None => Some((None, QueryOrder::Asc)),
+        };
         self
     }
 
+    /// The columns returned by a write query (only when `RETURNING` is used)
+    fn output_columns(&self) -> Vec<String> {
+        if self.returning {
+            self.table.selectable_columns()
+        } else {
+            self.columns.clone()
+        }
+    }
+
     /// Build a Query from the QueryBuilder and perform some checks
     pub fn build(&mut self) -> Result<Query, crate::Error> {
         if let Some(ref error) = self.error {
@@ -368,6 +1343,31 @@ impl QueryBuilder {
             self.where_clause.pop();
         }
 
+        // Order paged queries by a unique column to keep the pages stable
+        #[cfg(feature = "pagination")]
+        if let Some((column, order)) = self.page_order.clone() {
+            let primary_key = self.table.get_primary_key();
+            if let Some(column) = column {
+                if !self.table.is_valid_column(&column) {
+                    return Err(Error::QueryBuilderError(
+                        format!(
+                            "Column `{}` does not exist in table `{}`",
+                            column, self.table.name
+                        ),
+                        String::from("page"),
+                    ));
+                }
+                let column = self.table.column_name(&column);
+                if !self.order_by.iter().any(|(name, _)| name == &column) {
+                    self.order_by.push((column, order));
+                }
+            }
+            // The primary key is always the last tie-breaker
+            if !self.order_by.iter().any(|(name, _)| name == &primary_key) {
+                self.order_by.push((primary_key, QueryOrder::Asc));
+            }
+        }
+
         match self.query_type {
             QueryType::Create => {
                 let query = self.table.on_create(self)?;
@@ -382,14 +1382,29 @@ impl QueryBuilder {
             }
             QueryType::Select => {
                 let query = self.table.on_select(self)?;
-                Ok(Query::new(
+                // Select list parameters come before the where clause parameters
+                // (count queries do not select the expressions)
+                let mut values = Values::new();
+                if !self.count {
+                    values.values.extend(self.expression_values.values.clone());
+                }
+                values.values.extend(self.values.values.clone());
+                let mut query = Query::new(
                     self.query_type.clone(),
                     query.clone(),
-                    self.values.clone(),
+                    values,
                     Values::new(),
                     self.columns.clone(),
                     self.table.clone(),
-                ))
+                );
+                if !self.count {
+                    query.expression_aliases = self
+                        .expressions
+                        .iter()
+                        .map(|(_, alias)| alias.clone())
+                        .collect();
+                }
+                Ok(query)
             }
             QueryType::Insert => {
                 let (query, parameters) = self.table.on_insert(self)?;
@@ -398,7 +1413,7 @@ impl QueryBuilder {
                     query.clone(),
                     self.values.clone(),
                     parameters,
-                    self.columns.clone(),
+                    self.output_columns(),
                     self.table.clone(),
                 ))
             }
@@ -409,7 +1424,7 @@ impl QueryBuilder {
                     query.clone(),
                     self.values.clone(),
                     parameters,
-                    self.columns.clone(),
+                    self.output_columns(),
                     self.table.clone(),
                 ))
             }
@@ -420,10 +1435,14 @@ impl QueryBuilder {
                     query.clone(),
                     self.values.clone(),
                     parameters,
-                    self.columns.clone(),
+                    self.output_columns(),
                     self.table.clone(),
                 ))
             }
+            QueryType::Raw => Err(Error::QueryBuilderError(
+                String::from("Raw queries can not be built, use `Query::raw` instead"),
+                String::from("build"),
+            )),
         }
     }
 }
@@ -454,6 +1473,264 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "pagination")]
+    fn test_page_order_by() {
+        use crate::{builder::models::QueryOrder, Page};
+
+        let page = Page::from((2, 10));
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .page(&page)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users ORDER BY id ASC LIMIT 10 OFFSET 20;"
+        );
+
+        // Existing ordering is kept and the primary key is used as a tie-breaker
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .page(&page)
+            .order_by("username", QueryOrder::Desc)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users ORDER BY username DESC, id ASC LIMIT 10 OFFSET 20;"
+        );
+
+        // A custom page order still falls back to the primary key
+        let mut page = Page::from((2, 10));
+        page.set_order("username", QueryOrder::Desc);
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .page(&page)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users ORDER BY username DESC, id ASC LIMIT 10 OFFSET 20;"
+        );
+
+        // Unknown page order columns are an error and never reach the SQL
+        let mut page = Page::from((2, 10));
+        page.set_order("id; DROP TABLE users; --", QueryOrder::Asc);
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .page(&page)
+            .build();
+        assert!(matches!(query, Err(crate::Error::QueryBuilderError(_, _))));
+    }
+
+    #[test]
+    fn test_top_n_per_group() {
+        use crate::builder::models::QueryOrder;
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_ne("email", "")
+            .top_n_per_group("username", "id", QueryOrder::Desc, 3)
+            .order_by("username", QueryOrder::Asc)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM (SELECT *, ROW_NUMBER() OVER (PARTITION BY username ORDER BY id DESC) AS __row_number FROM users WHERE email != ?) WHERE __row_number <= 3 ORDER BY username ASC;"
+        );
+        assert_eq!(query.values.len(), 1);
+
+        // Unknown columns are an error
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .top_n_per_group("author", "id", QueryOrder::Desc, 3)
+            .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_where_numeric() {
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_gt_numeric("username", 20)
+            .where_lte_numeric("email", 100)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE CAST(username AS INTEGER) > ? AND CAST(email AS INTEGER) <= ?;"
+        );
+        assert_eq!(query.values.len(), 2);
+    }
+
+    #[test]
+    fn test_select_expr() {
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .columns(vec!["id"])
+            .select_expr("length(username)", "username_length")
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, length(username) AS username_length FROM users;"
+        );
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .select_expr("1", "one; DROP TABLE users")
+            .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_select_case() {
+        use crate::builder::{case::CaseBuilder, models::QueryCondition};
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .columns(vec!["id"])
+            .select_case(
+                CaseBuilder::new()
+                    .when("username", QueryCondition::Eq, "admin", 1)
+                    .when("email", QueryCondition::Like, "%@example.com", 2)
+                    .otherwise(0),
+                "rank",
+            )
+            .where_ne("id", 1)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, CASE WHEN username = ? THEN ? WHEN email LIKE ? THEN ? ELSE ? END AS rank FROM users WHERE id != ?;"
+        );
+        let values: Vec<Value> = query.values.into_iter().collect();
+        assert_eq!(
+            values,
+            vec![
+                Value::from("admin"),
+                Value::from(1),
+                Value::from("%@example.com"),
+                Value::from(2),
+                Value::from(0),
+                Value::from(1),
+            ]
+        );
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .select_case(
+                CaseBuilder::new().when("missing", QueryCondition::Eq, 1, 1),
+                "flag",
+            )
+            .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_join_aliases() {
+        let users = simple_table();
+        let posts = Table {
+            name: "posts".to_string(),
+            columns: crate::Columns::from(vec![
+                Column::new(
+                    "id".to_string(),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    "title".to_string(),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+                Column::new(
+                    "author".to_string(),
+                    ColumnType::ForeignKey(ColumnTypeOptions::foreign_key("users.id".to_string())),
+                ),
+            ]),
+        };
+
+        let query = QueryBuilder::select()
+            .table(posts)
+            .join(users)
+            .aliases()
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT posts.id AS posts_id, posts.title AS posts_title, posts.author AS posts_author, users.id AS users_id, users.username AS users_username, users.email AS users_email FROM posts INNER JOIN users ON users.id = posts.author;"
+        );
+    }
+
+    #[test]
+    fn test_left_join() {
+        let users = simple_table();
+        let posts = Table {
+            name: "posts".to_string(),
+            columns: crate::Columns::from(vec![
+                Column::new(
+                    "id".to_string(),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    "author".to_string(),
+                    ColumnType::ForeignKey(ColumnTypeOptions::foreign_key("users.id".to_string())),
+                ),
+                Column::new(
+                    "image".to_string(),
+                    ColumnType::ForeignKey(ColumnTypeOptions::foreign_key("images.id".to_string())),
+                ),
+            ]),
+        };
+        let images = Table {
+            name: "images".to_string(),
+            columns: crate::Columns::from(vec![Column::new(
+                "id".to_string(),
+                ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+            )]),
+        };
+
+        let query = QueryBuilder::select()
+            .table(posts.clone())
+            .left_join(images.clone())
+            .build()
+            .expect("Failed to build query");
+        assert!(query
+            .query
+            .ends_with("FROM posts LEFT JOIN images ON images.id = posts.image;"));
+
+        let query = QueryBuilder::select()
+            .table(posts)
+            .join(users)
+            .left_join(images)
+            .where_eq("users.username", "geekmasher")
+            .build()
+            .expect("Failed to build query");
+        assert!(query.query.ends_with(
+            "FROM posts INNER JOIN users ON users.id = posts.author LEFT JOIN images ON images.id = posts.image WHERE users.username = ?;"
+        ));
+    }
+
+    #[test]
+    fn test_where_alias() {
+        let mut table = simple_table();
+        table.columns.columns[1].alias = String::from("user_name");
+
+        // Both the field name and the alias reach the SQL as the database column
+        for column in ["username", "user_name"] {
+            let query = QueryBuilder::select()
+                .table(table.clone())
+                .columns(vec!["id"])
+                .where_eq(column, "geekmasher")
+                .order_by(column, crate::builder::models::QueryOrder::Asc)
+                .build()
+                .expect("Failed to build query");
+            assert_eq!(
+                query.query,
+                "SELECT id FROM users WHERE user_name = ? ORDER BY user_name ASC;"
+            );
+        }
+    }
+
     #[test]
     fn test_simple_select() {
         let table = simple_table();
@@ -486,4 +1763,378 @@ mod tests {
         let second = query.values.get(&String::from("email")).unwrap();
         assert_eq!(second, &Value::Text(String::from("%geekmasher%")));
     }
+
+    #[test]
+    fn test_where_joined_column() {
+        let users = simple_table();
+        let posts = Table {
+            name: "posts".to_string(),
+            columns: crate::Columns::from(vec![
+                Column::new(
+                    "id".to_string(),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    "title".to_string(),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+                Column::new(
+                    "author".to_string(),
+                    ColumnType::ForeignKey(ColumnTypeOptions::foreign_key(String::from(
+                        "users.id",
+                    ))),
+                ),
+            ]),
+        };
+
+        let query = QueryBuilder::select()
+            .table(posts)
+            .join(users)
+            .where_eq("users.username", "geekmasher")
+            .and()
+            .where_eq("posts.title", "Hello")
+            .build()
+            .expect("Failed to build query");
+
+        assert!(query
+            .query
+            .ends_with("WHERE users.username = ? AND posts.title = ?;"));
+
+        // The values must be in the same order as the placeholders
+        let values: Vec<&(String, Value)> = query.values.values.iter().collect();
+        assert_eq!(values.len(), 2);
+        assert_eq!(
+            values[0],
+            &(
+                String::from("users.username"),
+                Value::Text(String::from("geekmasher"))
+            )
+        );
+        assert_eq!(
+            values[1],
+            &(String::from("title"), Value::Text(String::from("Hello")))
+        );
+    }
+
+    #[test]
+    fn test_limit_zero() {
+        let table = simple_table();
+        let query = QueryBuilder::select()
+            .table(table)
+            .limit(0)
+            .build()
+            .expect("Limit of 0 should not be an error");
+
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users LIMIT 0;"
+        );
+    }
+
+    #[test]
+    fn test_insert_duplicate_column() {
+        let table = simple_table();
+        let query = QueryBuilder::insert()
+            .table(table)
+            .add_value("username", "geekmasher")
+            .add_value("username", "geek")
+            .build()
+            .expect("Failed to build query");
+
+        assert_eq!(query.query, "INSERT INTO users (username) VALUES (?);");
+        assert_eq!(query.parameters.len(), 1);
+        let username = query.parameters.get(&String::from("username")).unwrap();
+        assert_eq!(username, &Value::Text(String::from("geek")));
+    }
+
+    #[test]
+    fn test_add_value_invalid_column() {
+        let query = QueryBuilder::insert()
+            .table(simple_table())
+            .add_value("username", "geekmasher")
+            .add_value("usrname", "geekmasher")
+            .build();
+
+        match query {
+            Err(crate::Error::QueryBuilderError(msg, func)) => {
+                assert_eq!(msg, "Column `usrname` does not exist in table `users`");
+                assert_eq!(func, "add_value");
+            }
+            _ => panic!("Expected a QueryBuilderError"),
+        }
+    }
+
+    #[test]
+    fn test_build_raw_type() {
+        use crate::builder::models::QueryType;
+
+        let query = QueryBuilder {
+            query_type: QueryType::Raw,
+            ..Default::default()
+        }
+        .table(simple_table())
+        .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_and_or_where() {
+        let explicit = QueryBuilder::select()
+            .table(simple_table())
+            .where_eq("username", "geekmasher")
+            .or_where_eq("username", "bob")
+            .and_where_ne("email", "bob@example.com")
+            .build()
+            .unwrap();
+        let manual = QueryBuilder::select()
+            .table(simple_table())
+            .where_eq("username", "geekmasher")
+            .or()
+            .where_eq("username", "bob")
+            .and()
+            .where_ne("email", "bob@example.com")
+            .build()
+            .unwrap();
+        assert_eq!(explicit.query, manual.query);
+        assert_eq!(
+            explicit.query,
+            "SELECT id, username, email FROM users WHERE username = ? OR username = ? AND email != ?;"
+        );
+        assert_eq!(explicit.values, manual.values);
+
+        // The first condition never starts with a connective
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .or_where_like("username", "%geek%")
+            .and_where_gte("id", 10)
+            .and_where_lt("id", 20)
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE username LIKE ? AND id >= ? AND id < ?;"
+        );
+    }
+
+    #[test]
+    fn test_where_raw_in() {
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_eq("username", "geekmasher")
+            .where_raw_in("id IN (...)", vec![1, 2, 3])
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE username = ? AND id IN (?, ?, ?);"
+        );
+        assert_eq!(query.values.len(), 4);
+        assert_eq!(
+            query.values.values[1..]
+                .iter()
+                .map(|(_, value)| value.clone())
+                .collect::<Vec<Value>>(),
+            vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+        );
+
+        let error = QueryBuilder::select()
+            .table(simple_table())
+            .where_raw_in("id IN (?)", vec![1, 2, 3])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "QueryBuilderError: Fragment `id IN (?)` is missing the `(...)` marker (where_raw_in)"
+        );
+    }
+
+    #[test]
+    fn test_order_by_many() {
+        use crate::builder::models::QueryOrder;
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .order_by("id", QueryOrder::Asc)
+            .order_by_many(&[
+                ("username", QueryOrder::Desc),
+                ("email", QueryOrder::AscNullsFirst),
+                ("id", QueryOrder::Desc),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users ORDER BY id ASC, username DESC, email ASC NULLS FIRST;"
+        );
+
+        let error = QueryBuilder::select()
+            .table(simple_table())
+            .order_by_many(&[
+                ("name", QueryOrder::Asc),
+                ("username", QueryOrder::Asc),
+                ("age", QueryOrder::Desc),
+            ])
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "QueryBuilderError: Columns `name`, `age` do not exist in table `users` (order_by_many)"
+        );
+    }
+
+    #[test]
+    fn test_where_in() {
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_eq("email", "geekmasher@example.com")
+            .where_in("id", vec![3, 1, 2])
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE email = ? AND id IN (?, ?, ?);"
+        );
+        let values: Vec<&Value> = query.values.values.iter().map(|(_, v)| v).collect();
+        assert_eq!(
+            values,
+            vec![
+                &Value::from("geekmasher@example.com"),
+                &Value::from(3),
+                &Value::from(1),
+                &Value::from(2)
+            ]
+        );
+        assert_eq!(query.placeholder_count(), 4);
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_not_in("username", vec!["bob", "alice"])
+            .or()
+            .where_eq("id", 1)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE username NOT IN (?, ?) OR id = ?;"
+        );
+        assert_eq!(query.values.len(), 3);
+
+        // An empty list of values or an invalid column is an error
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_in("id", Vec::<i32>::new())
+            .build();
+        assert!(query.is_err());
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_not_in("unknown", vec![1])
+            .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_where_between() {
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_between("id", 10, 20)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE id BETWEEN ? AND ?;"
+        );
+        let values: Vec<&Value> = query.values.values.iter().map(|(_, v)| v).collect();
+        assert_eq!(values, vec![&Value::from(10), &Value::from(20)]);
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_eq("username", "geekmasher")
+            .or()
+            .where_not_between("id", 1, 5)
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE username = ? OR id NOT BETWEEN ? AND ?;"
+        );
+        assert_eq!(query.values.len(), 3);
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_between("unknown", 1, 5)
+            .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_where_all() {
+        use crate::builder::models::QueryCondition;
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_all(vec![
+                ("username", QueryCondition::Eq, Value::from("geekmasher")),
+                ("email", QueryCondition::Like, Value::from("%@example.com")),
+                ("id", QueryCondition::Gt, Value::from(10)),
+            ])
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE username = ? AND email LIKE ? AND id > ?;"
+        );
+        assert_eq!(query.values.len(), 3);
+
+        // An empty list of conditions doesn't add a where clause
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_all(vec![])
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(query.query, "SELECT id, username, email FROM users;");
+
+        // Invalid columns poison the build
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_all(vec![("unknown", QueryCondition::Eq, Value::from(1))])
+            .build();
+        assert!(query.is_err());
+    }
+
+    #[test]
+    fn test_where_any() {
+        use crate::builder::models::QueryCondition;
+
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_eq("id", 1)
+            .or()
+            .where_any(vec![
+                ("username", QueryCondition::Eq, Value::from("geekmasher")),
+                ("email", QueryCondition::Ne, Value::Null),
+            ])
+            .where_eq("username", "admin")
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE id = ? OR (username = ? OR email != ?) AND username = ?;"
+        );
+        assert_eq!(query.values.len(), 4);
+
+        // A single condition is not wrapped in parentheses
+        let query = QueryBuilder::select()
+            .table(simple_table())
+            .where_any(vec![(
+                "username",
+                QueryCondition::Eq,
+                Value::from("geekmasher"),
+            )])
+            .build()
+            .expect("Failed to build query");
+        assert_eq!(
+            query.query,
+            "SELECT id, username, email FROM users WHERE username = ?;"
+        );
+    }
 }
@@ -1,4 +1,5 @@
 //! # GeekORM Pages
+use crate::builder::models::QueryOrder;
 
 /// Default limit for max page size
 const DEFAULT_LIMIT: u32 = 100;
@@ -7,6 +8,11 @@ const DEFAULT_LIMIT: u32 = 100;
 ///
 /// This is a simple struct to handle pagination for queries.
 ///
+/// Offset pagination is only deterministic when the rows have a total order,
+/// without one the same row can show up on multiple pages (or none) as data changes.
+/// To keep pages stable, paged queries are always ordered by the page's order column
+/// (defaulting to the table's primary key) after any other `ORDER BY` columns.
+///
 /// ```rust
 /// # use geekorm::prelude::*;
 ///
@@ -43,7 +49,19 @@ const DEFAULT_LIMIT: u32 = 100;
 ///     .expect("Failed to build select query");
 /// # assert_eq!(
 /// #     select_query.query,
-/// #     "SELECT id, username, age, postcode FROM Users WHERE username = ? ORDER BY age ASC LIMIT 100 OFFSET 100;"
+/// #     "SELECT id, username, age, postcode FROM Users WHERE username = ? ORDER BY age ASC, id ASC LIMIT 100 OFFSET 100;"
+/// # );
+///
+/// // Order the pages by a specific column
+/// let mut page = Page::new();
+/// page.set_order("username", QueryOrder::Desc);
+/// let select_query = Users::query_select()
+///     .page(&page)
+///     .build()
+///     .expect("Failed to build select query");
+/// # assert_eq!(
+/// #     select_query.query,
+/// #     "SELECT id, username, age, postcode FROM Users ORDER BY username DESC, id ASC LIMIT 100 OFFSET 0;"
 /// # );
 ///
 /// let page_max = Page::from((1, 10_000));
@@ -61,6 +79,8 @@ pub struct Page {
     pub(crate) page: u32,
     pub(crate) limit: u32,
     pub(crate) total: u32,
+    /// Column to order the pages by (defaults to the primary key)
+    pub(crate) order: Option<(String, QueryOrder)>,
 }
 
 impl Page {
@@ -70,6 +90,7 @@ impl Page {
             page: 0,
             limit: DEFAULT_LIMIT,
             total: 0,
+            order: None,
         }
     }
     /// Update current page to the next page
@@ -120,6 +141,18 @@ impl Page {
         self.total = total;
     }
 
+    /// Get the column and order the pages are sorted by (if set)
+    pub fn order(&self) -> Option<&(String, QueryOrder)> {
+        self.order.as_ref()
+    }
+    /// Set the column and order the pages are sorted by
+    ///
+    /// The primary key is still added after this column so rows with the same
+    /// value are always returned in the same order.
+    pub fn set_order(&mut self, column: impl Into<String>, order: QueryOrder) {
+        self.order = Some((column.into(), order));
+    }
+
     /// Get the maximum number of pages based on the total number of rows
     pub fn max(&self) -> u32 {
         if self.total == 0 {
@@ -136,6 +169,7 @@ impl Default for Page {
             page: u32::MAX,
             limit: DEFAULT_LIMIT,
             total: 0,
+            order: None,
         }
     }
 }
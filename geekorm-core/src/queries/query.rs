@@ -1,12 +1,49 @@
 /// The Query struct to hold the query and values to use
 use std::fmt::Display;
 
-use crate::builder::models::QueryType;
+use serde::{Deserialize, Serialize};
+
+use crate::builder::{models::QueryType, push_identifier};
 use crate::queries::QueryBuilder;
-use crate::{builder::values::Values, Table};
+use crate::{
+    builder::values::{Value, Values},
+    Table,
+};
 
 /// The built Query struct with the query and values to use
-#[derive(Debug, Clone, Default)]
+///
+/// Queries can be serialized (SQL, query type and the ordered parameter values),
+/// for example to build a cache key or to store a query to replay later.
+/// The table the query was built from is not part of the serialized query, so a
+/// deserialized query has the default (empty) table.
+///
+/// ```rust
+/// use geekorm::prelude::*;
+///
+/// #[derive(Table, Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+/// pub struct Users {
+///     pub id: PrimaryKeyInteger,
+///     pub username: String,
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let query = Users::query_select()
+///     .where_eq("username", "geekmasher")
+///     .build()?;
+///
+/// let key = serde_json::to_string(&query)?;
+/// # assert!(key.contains(r#""query":"SELECT id, username FROM Users WHERE username = ?;""#));
+/// # assert!(key.contains(r#""values":[["username","geekmasher"]]"#));
+/// # assert!(!key.contains("table"));
+///
+/// let replay: Query = serde_json::from_str(&key)?;
+/// # assert_eq!(replay.query, query.query);
+/// # assert_eq!(replay.values, query.values);
+/// # assert_eq!(serde_json::to_string(&replay)?, key);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Query {
     /// The type of query (select, insert, update, delete)
     pub query_type: QueryType,
@@ -20,7 +57,11 @@ pub struct Query {
     /// The output columns for the query (used in raw queries)
     pub columns: Vec<String>,
 
+    #[serde(skip)]
     pub(crate) table: Table,
+    /// The aliases of the selected expressions (`select_expr` / `select_case`)
+    #[serde(skip)]
+    pub(crate) expression_aliases: Vec<String>,
 }
 
 impl Query {
@@ -40,6 +81,120 @@ impl Query {
             parameters,
             columns,
             table,
+            expression_aliases: Vec::new(),
+        }
+    }
+
+    /// Create a new raw Query from a SQL string with no values
+    ///
+    /// Use [`QueryType::Raw`] for introspection queries (`PRAGMA`, `sqlite_master`, etc.)
+    /// which do not map onto a table operation.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "rusqlite")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let connection = rusqlite::Connection::open_in_memory()?;
+    ///
+    ///     let query = Query::raw(QueryType::Raw, "PRAGMA user_version;");
+    ///     let version: Option<i64> = rusqlite::Connection::query_scalar(&connection, query).await?;
+    ///     # assert_eq!(version, Some(0));
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub fn raw(query_type: QueryType, query: impl Into<String>) -> Self {
+        Query {
+            query_type,
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new raw Query from a SQL string with named parameters
+    ///
+    /// The names are the placeholders used in the query (`:name`, `@name` or `$name`).
+    /// If a name has no prefix, `:` is used.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "libsql")] {
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let database = libsql::Builder::new_local(":memory:").build().await?;
+    ///     let connection = database.connect()?;
+    ///     Users::create_table(&connection).await?;
+    ///
+    ///     for (username, age) in [("geekmasher", 42), ("bob", 21), ("alice", 30)] {
+    ///         Users::new(username, age).save(&connection).await?;
+    ///     }
+    ///
+    ///     let query = Query::raw_named(
+    ///         QueryType::Select,
+    ///         "SELECT id, username, age FROM Users WHERE age >= :min AND username != @name;",
+    ///         vec![(":min", Value::from(30)), ("@name", Value::from("alice"))],
+    ///     );
+    ///     let users: Vec<Users> = <libsql::Connection as GeekConnection>::query(&connection, query).await?;
+    ///     # assert_eq!(users.len(), 1);
+    ///     # assert_eq!(users[0].username, "geekmasher");
+    ///     Ok(())
+    /// }
+    /// # }
+    /// ```
+    pub fn raw_named(
+        query_type: QueryType,
+        query: impl Into<String>,
+        values: Vec<(&str, Value)>,
+    ) -> Self {
+        let mut named = Values::new();
+        for (name, value) in values {
+            let name = if name.starts_with([':', '@', '$']) {
+                name.to_string()
+            } else {
+                format!(":{}", name)
+            };
+            named.push(name, value);
+        }
+
+        Query {
+            query_type,
+            query: query.into(),
+            values: named.clone(),
+            parameters: named,
+            ..Default::default()
+        }
+    }
+
+    /// Check if the query uses named parameters (`:name`, `@name` or `$name`)
+    pub fn is_named(&self) -> bool {
+        let values = match self.query_type {
+            QueryType::Insert | QueryType::Update => &self.parameters,
+            _ => &self.values,
+        };
+        !values.values.is_empty()
+            && values
+                .values
+                .iter()
+                .all(|(name, _)| name.starts_with([':', '@', '$']))
+    }
+
+    /// Check if the query returns rows (select queries and writes with `RETURNING`)
+    pub fn returns_rows(&self) -> bool {
+        match self.query_type {
+            QueryType::Select => true,
+            QueryType::Insert | QueryType::Update | QueryType::Delete => !self.columns.is_empty(),
+            _ => false,
         }
     }
 
@@ -49,9 +204,212 @@ impl Query {
     }
 
     /// Get the query as a &str
+    ///
+    /// This borrows the query string and does not allocate, prefer this over
+    /// `to_string()` when passing the query to a backend.
     pub fn to_str(&self) -> &str {
         &self.query
     }
+
+    /// Get the SQL for the query
+    pub fn sql(&self) -> &str {
+        &self.query
+    }
+
+    /// Get the parameters bound to the query (insert / update)
+    pub fn parameters(&self) -> &Values {
+        &self.parameters
+    }
+
+    /// Get the values bound to the query (where clauses)
+    pub fn values(&self) -> &Values {
+        &self.values
+    }
+
+    /// Get the number of positional placeholders (`?` or `?NNN`) in the query
+    ///
+    /// Placeholders inside string literals, quoted identifiers and comments are
+    /// ignored and numbered placeholders follow SQLite's rules (the highest index
+    /// is the count). Named placeholders (`:name`, `@name`, `$name` and `$NNN`,
+    /// which SQLite treats as a name) are not counted.
+    pub fn placeholder_count(&self) -> usize {
+        let mut count = 0;
+        let mut quote: Option<char> = None;
+        let mut chars = self.query.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if let Some(q) = quote {
+                if c == q {
+                    quote = None;
+                }
+                continue;
+            }
+            match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '[' => quote = Some(']'),
+                // Line comment
+                '-' if chars.peek() == Some(&'-') => {
+                    for c in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+                // Block comment
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    let mut previous = None;
+                    for c in chars.by_ref() {
+                        if previous == Some('*') && c == '/' {
+                            break;
+                        }
+                        previous = Some(c);
+                    }
+                }
+                '?' => {
+                    let mut index: Option<usize> = None;
+                    while let Some(digit) = chars.peek().and_then(|d| d.to_digit(10)) {
+                        index = Some(index.unwrap_or(0) * 10 + digit as usize);
+                        chars.next();
+                    }
+                    match index {
+                        Some(index) => count = count.max(index),
+                        None => count += 1,
+                    }
+                }
+                // Named placeholder
+                ':' | '@' | '$' => {
+                    while chars
+                        .peek()
+                        .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+                    {
+                        chars.next();
+                    }
+                }
+                _ => {}
+            }
+        }
+        count
+    }
+
+    /// Validate that the number of placeholders matches the number of bound values
+    ///
+    /// This catches construction bugs before the query is executed (the backends
+    /// would fail to bind the values). Queries with named parameters are not checked.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// let query = Query::raw(QueryType::Select, "SELECT * FROM Users WHERE id = ?;");
+    /// # assert_eq!(query.placeholder_count(), 1);
+    /// assert!(query.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        if self.is_named() {
+            return Ok(());
+        }
+        // Backends bind the parameters if set, otherwise the values
+        let values = if !self.parameters.values.is_empty() {
+            &self.parameters
+        } else {
+            &self.values
+        };
+        let placeholders = self.placeholder_count();
+        if placeholders != values.len() {
+            return Err(crate::Error::QueryBuilderError(
+                format!(
+                    "Query has {} placeholders but {} values are bound",
+                    placeholders,
+                    values.len()
+                ),
+                String::from("validate"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get the column names projected by a select query (in order)
+    ///
+    /// If the query selects specific columns those are returned, otherwise the
+    /// table's (non-skipped) columns are returned, followed by the aliases of the
+    /// selected expressions. Renamed columns use their name in the database.
+    /// Other query types return the columns set on the query.
+    ///
+    /// ```rust
+    /// use geekorm::prelude::*;
+    ///
+    /// #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+    /// pub struct Users {
+    ///     #[geekorm(primary_key, auto_increment)]
+    ///     pub id: PrimaryKeyInteger,
+    ///     pub username: String,
+    ///     pub age: i32,
+    /// }
+    ///
+    /// let query = Users::query_select().build()?;
+    /// # assert_eq!(query.projected_columns(), vec!["id", "username", "age"]);
+    ///
+    /// let query = Users::query_select().columns(vec!["username", "age"]).build()?;
+    /// # assert_eq!(query.projected_columns(), vec!["username", "age"]);
+    ///
+    /// let query = Users::query_select()
+    ///     .columns(vec!["username"])
+    ///     .select_expr("age * 12", "months")
+    ///     .build()?;
+    /// # assert_eq!(query.projected_columns(), vec!["username", "months"]);
+    /// # Ok::<(), geekorm::Error>(())
+    /// ```
+    pub fn projected_columns(&self) -> Vec<String> {
+        if self.query_type != QueryType::Select {
+            return self.columns.clone();
+        }
+        let columns = if self.columns.is_empty() {
+            self.table.selectable_columns()
+        } else {
+            self.columns.clone()
+        };
+        columns
+            .iter()
+            .map(|column| self.table.column_name(column))
+            .chain(self.expression_aliases.iter().cloned())
+            .collect()
+    }
+
+    /// Turn a built insert query into an `INSERT OR IGNORE` query
+    pub(crate) fn or_ignore(mut self) -> Self {
+        if self.query_type == QueryType::Insert && !self.query.starts_with("INSERT OR IGNORE ") {
+            self.query = self.query.replacen("INSERT ", "INSERT OR IGNORE ", 1);
+        }
+        self
+    }
+
+    /// Add a `RETURNING` clause (all the selectable columns) to a built write query
+    pub(crate) fn returning_all(mut self) -> Self {
+        if self.returns_rows() {
+            return self;
+        }
+        let columns = self.table.selectable_columns();
+
+        let mut query = self.query.trim_end_matches(';').to_string();
+        query.push_str(" RETURNING ");
+        for (index, column) in columns.iter().enumerate() {
+            if index > 0 {
+                query.push_str(", ");
+            }
+            push_identifier(&mut query, column);
+        }
+        query.push(';');
+
+        self.query = query;
+        self.columns = columns;
+        self
+    }
+}
+
+impl AsRef<str> for Query {
+    fn as_ref(&self) -> &str {
+        self.to_str()
+    }
 }
 
 impl Display for Query {
@@ -59,3 +417,278 @@ impl Display for Query {
         write!(f, "{}", self.query)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_raw_type() {
+        let query = Query::raw(QueryType::Raw, "PRAGMA table_info(Users);");
+        assert_eq!(query.query_type, QueryType::Raw);
+        assert_eq!(query.to_str(), "PRAGMA table_info(Users);");
+        assert!(query.values.values.is_empty());
+        assert!(!query.is_named());
+    }
+
+    #[test]
+    fn test_query_to_str_borrows() {
+        let query = Query::raw(QueryType::Select, "SELECT 1;");
+
+        assert_eq!(query.to_str(), "SELECT 1;");
+        // The returned &str must point at the same allocation (no clone)
+        assert!(std::ptr::eq(query.to_str(), query.query.as_str()));
+        assert!(std::ptr::eq(query.as_ref(), query.query.as_str()));
+    }
+
+    #[test]
+    fn test_query_raw_named() {
+        let query = Query::raw_named(
+            QueryType::Select,
+            "SELECT * FROM Users WHERE username = :username AND age > @age;",
+            vec![
+                ("username", crate::Value::from("geekmasher")),
+                ("@age", crate::Value::from(21)),
+            ],
+        );
+        assert!(query.is_named());
+        assert_eq!(
+            query.values().get(&String::from(":username")),
+            Some(&crate::Value::Text(String::from("geekmasher")))
+        );
+        assert_eq!(
+            query.values().get(&String::from("@age")),
+            Some(&crate::Value::Integer(21))
+        );
+
+        let query = Query::raw(QueryType::Select, "SELECT * FROM Users;");
+        assert!(!query.is_named());
+    }
+
+    #[test]
+    fn test_query_projected_columns() {
+        use crate::{Column, ColumnType, ColumnTypeOptions, Table};
+
+        let table = Table {
+            name: String::from("Users"),
+            columns: vec![
+                Column::new(
+                    String::from("id"),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    String::from("username"),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+                Column {
+                    name: String::from("cache"),
+                    column_type: ColumnType::Text(ColumnTypeOptions::default()),
+                    skip: true,
+                    ..Default::default()
+                },
+            ]
+            .into(),
+        };
+
+        let query = QueryBuilder::select().table(table.clone()).build().unwrap();
+        assert_eq!(query.projected_columns(), vec!["id", "username"]);
+
+        let query = QueryBuilder::select()
+            .table(table.clone())
+            .columns(vec!["username"])
+            .build()
+            .unwrap();
+        assert_eq!(query.projected_columns(), vec!["username"]);
+
+        // Expressions follow the columns in select order
+        let query = QueryBuilder::select()
+            .table(table.clone())
+            .select_expr("length(username)", "length")
+            .select_case(
+                crate::builder::case::CaseBuilder::new()
+                    .when("id", crate::builder::models::QueryCondition::Eq, 1, "admin")
+                    .otherwise("user"),
+                "role",
+            )
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.projected_columns(),
+            vec!["id", "username", "length", "role"]
+        );
+
+        // Renamed columns use their name in the database
+        let mut renamed = table;
+        renamed.columns.columns[1].alias = String::from("login");
+        let query = QueryBuilder::select()
+            .table(renamed.clone())
+            .build()
+            .unwrap();
+        assert_eq!(query.projected_columns(), vec!["id", "login"]);
+        let query = QueryBuilder::select()
+            .table(renamed)
+            .columns(vec!["id", "username"])
+            .select_expr("1", "one")
+            .build()
+            .unwrap();
+        assert_eq!(query.projected_columns(), vec!["id", "login", "one"]);
+    }
+
+    #[test]
+    fn test_query_accessors() {
+        use crate::{Column, ColumnType, ColumnTypeOptions, Table};
+
+        let table = Table {
+            name: String::from("Users"),
+            columns: vec![
+                Column::new(
+                    String::from("id"),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    String::from("username"),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+            ]
+            .into(),
+        };
+
+        let query = QueryBuilder::select()
+            .table(table.clone())
+            .where_eq("username", "geekmasher")
+            .build()
+            .unwrap();
+        assert_eq!(
+            query.sql(),
+            "SELECT id, username FROM Users WHERE username = ?;"
+        );
+        assert_eq!(query.values().len(), 1);
+        assert_eq!(
+            query.values().get(&String::from("username")),
+            Some(&crate::Value::Text(String::from("geekmasher")))
+        );
+
+        let query = QueryBuilder::insert()
+            .table(table)
+            .add_value("username", "geekmasher")
+            .build()
+            .unwrap();
+        assert_eq!(query.sql(), "INSERT INTO Users (username) VALUES (?);");
+        assert_eq!(query.parameters().len(), 1);
+    }
+
+    #[test]
+    fn test_query_placeholder_count() {
+        use crate::{Column, ColumnType, ColumnTypeOptions, Table};
+
+        let table = Table {
+            name: String::from("Users"),
+            columns: vec![
+                Column::new(
+                    String::from("id"),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    String::from("username"),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+            ]
+            .into(),
+        };
+
+        let query = QueryBuilder::select()
+            .table(table)
+            .where_eq("username", "geekmasher")
+            .or()
+            .where_gt("id", 10)
+            .build()
+            .unwrap();
+        assert_eq!(query.placeholder_count(), 2);
+        assert_eq!(query.placeholder_count(), query.values().len());
+        assert!(query.validate().is_ok());
+
+        // A value is missing
+        let mut query = Query::raw(
+            QueryType::Select,
+            "SELECT * FROM Users WHERE username = ? AND id > ?;",
+        );
+        query
+            .values
+            .push(String::from("username"), crate::Value::from("geekmasher"));
+        assert_eq!(query.placeholder_count(), 2);
+        assert!(query.validate().is_err());
+
+        // Quoted placeholders are ignored and numbered ones use the highest index
+        let query = Query::raw(
+            QueryType::Select,
+            "SELECT '?', \"a?\" FROM Users WHERE id = ?2 OR id = ?;",
+        );
+        assert_eq!(query.placeholder_count(), 3);
+
+        // `$NNN` is a named placeholder in SQLite
+        let query = Query::raw(
+            QueryType::Select,
+            "SELECT * FROM Users WHERE id = $1 OR id = $2 OR username = :name OR id = ?;",
+        );
+        assert_eq!(query.placeholder_count(), 1);
+        let query = Query::raw(QueryType::Select, "SELECT * FROM Users WHERE id = $10;");
+        assert_eq!(query.placeholder_count(), 0);
+
+        // Placeholders in comments are ignored
+        let query = Query::raw(
+            QueryType::Select,
+            "SELECT * -- where id = ?\nFROM Users /* ?1 ? ?3 */ WHERE id = ? /* ? */;",
+        );
+        assert_eq!(query.placeholder_count(), 1);
+        let query = Query::raw(
+            QueryType::Select,
+            "SELECT 10 - -1, 4 / 2 FROM Users WHERE id = ?;",
+        );
+        assert_eq!(query.placeholder_count(), 1);
+        // Unterminated block comment
+        let query = Query::raw(QueryType::Select, "SELECT * FROM Users WHERE id = ? /* ?");
+        assert_eq!(query.placeholder_count(), 1);
+    }
+
+    #[test]
+    fn test_query_or_ignore_returning() {
+        use crate::{Column, ColumnType, ColumnTypeOptions, Table};
+
+        let table = Table {
+            name: String::from("Users"),
+            columns: vec![
+                Column::new(
+                    String::from("id"),
+                    ColumnType::Identifier(ColumnTypeOptions::primary_key()),
+                ),
+                Column::new(
+                    String::from("username"),
+                    ColumnType::Text(ColumnTypeOptions::default()),
+                ),
+            ]
+            .into(),
+        };
+        let insert = QueryBuilder::insert()
+            .table(table.clone())
+            .add_value("username", "geekmasher")
+            .build()
+            .unwrap();
+
+        // The same query as the query builder would build
+        let query = insert.clone().or_ignore().returning_all();
+        let expected = QueryBuilder::insert_or_ignore()
+            .table(table)
+            .add_value("username", "geekmasher")
+            .returning_all()
+            .build()
+            .unwrap();
+        assert_eq!(query.to_str(), expected.to_str());
+        assert_eq!(query.columns, expected.columns);
+        assert!(query.returns_rows());
+
+        // Applying them twice does not change the query
+        let query = query.or_ignore().returning_all();
+        assert_eq!(query.to_str(), expected.to_str());
+        assert!(!insert.returns_rows());
+    }
+}
@@ -0,0 +1,156 @@
+//! # Typed Blobs
+//!
+//! Fields marked with `#[geekorm(blob)]` are stored in a `BLOB` column using a
+//! custom binary encoding (protobuf, bincode, etc.) instead of JSON.
+//! The field type has to implement [`ToBlob`] and [`FromBlob`], and serde has to
+//! use this module to (de)serialize the field: `#[serde(with = "geekorm::utils::blob")]`.
+//!
+//! ```rust
+//! # #[cfg(feature = "rusqlite")] {
+//! use geekorm::prelude::*;
+//!
+//! /// A point encoded as two little endian integers
+//! #[derive(Debug, Clone, Default, PartialEq)]
+//! pub struct Point {
+//!     pub x: i32,
+//!     pub y: i32,
+//! }
+//!
+//! impl ToBlob for Point {
+//!     fn to_blob(&self) -> Vec<u8> {
+//!         [self.x.to_le_bytes(), self.y.to_le_bytes()].concat()
+//!     }
+//! }
+//!
+//! impl FromBlob for Point {
+//!     fn from_blob(data: &[u8]) -> Result<Self, geekorm::Error> {
+//!         let bytes: [u8; 8] = data.try_into().map_err(|_| {
+//!             geekorm::Error::SerdeError(format!("Invalid point ({} bytes)", data.len()))
+//!         })?;
+//!         Ok(Point {
+//!             x: i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+//!             y: i32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+//!         })
+//!     }
+//! }
+//!
+//! #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//! pub struct Places {
+//!     #[geekorm(primary_key, auto_increment)]
+//!     pub id: PrimaryKeyInteger,
+//!     pub name: String,
+//!     #[geekorm(blob)]
+//!     #[serde(with = "geekorm::utils::blob")]
+//!     pub location: Point,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let connection = rusqlite::Connection::open_in_memory()?;
+//!     Places::create_table(&connection).await?;
+//!
+//!     let mut place = Places::new("Home", Point { x: 42, y: -7 });
+//!     place.save(&connection).await?;
+//!
+//!     // The point is stored as 8 bytes
+//!     let stored: Option<i64> = rusqlite::Connection::query_scalar(
+//!         &connection,
+//!         Query::raw(QueryType::Select, "SELECT length(location) FROM Places;"),
+//!     )
+//!     .await?;
+//!     # assert_eq!(stored, Some(8));
+//!
+//!     let place = Places::fetch_by_primary_key(&connection, place.id).await?;
+//!     # assert_eq!(place.location, Point { x: 42, y: -7 });
+//!     Ok(())
+//! }
+//! # }
+//! ```
+
+use crate::Value;
+
+/// Encode a type into the bytes stored in a `BLOB` column
+pub trait ToBlob {
+    /// Encode the value
+    fn to_blob(&self) -> Vec<u8>;
+}
+
+/// Decode a type from the bytes stored in a `BLOB` column
+pub trait FromBlob: Sized {
+    /// Decode the value
+    fn from_blob(data: &[u8]) -> Result<Self, crate::Error>;
+}
+
+/// Convert an encoded type into a blob value
+pub fn to_value<T: ToBlob>(value: &T) -> Value {
+    Value::Blob(value.to_blob())
+}
+
+/// Serialize a typed blob field
+///
+/// Use with `#[serde(with = "geekorm::utils::blob")]`
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ToBlob,
+    S: serde::Serializer,
+{
+    serializer.serialize_bytes(&value.to_blob())
+}
+
+/// Deserialize a typed blob field
+///
+/// Use with `#[serde(with = "geekorm::utils::blob")]`
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromBlob,
+    D: serde::Deserializer<'de>,
+{
+    let data: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+    T::from_blob(&data).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Version(u8, u8);
+
+    impl ToBlob for Version {
+        fn to_blob(&self) -> Vec<u8> {
+            vec![self.0, self.1]
+        }
+    }
+
+    impl FromBlob for Version {
+        fn from_blob(data: &[u8]) -> Result<Self, crate::Error> {
+            match data {
+                [major, minor] => Ok(Version(*major, *minor)),
+                _ => Err(crate::Error::SerdeError(String::from("Invalid version"))),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Release {
+        #[serde(with = "super")]
+        version: Version,
+    }
+
+    #[test]
+    fn test_blob_value() {
+        assert_eq!(to_value(&Version(1, 2)), Value::Blob(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_blob_serde() {
+        let release = Release {
+            version: Version(4, 2),
+        };
+        let json = serde_json::to_string(&release).unwrap();
+        assert_eq!(json, r#"{"version":[4,2]}"#);
+        assert_eq!(serde_json::from_str::<Release>(&json).unwrap(), release);
+
+        assert!(serde_json::from_str::<Release>(r#"{"version":[1,2,3]}"#).is_err());
+    }
+}
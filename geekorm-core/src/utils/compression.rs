@@ -0,0 +1,159 @@
+//! # Compression
+//!
+//! Blob columns marked with `#[geekorm(compress)]` are compressed (gzip) before
+//! they are written to the database.
+//! To decompress the value when the row is read back, the field needs to use the
+//! [`deserialize`] function.
+//!
+//! Compressed values are prefixed with a marker, values without it (rows written
+//! before the column was compressed) are read back as-is.
+//!
+//! ```rust
+//! # #[cfg(all(feature = "compression", feature = "rusqlite"))] {
+//! use geekorm::prelude::*;
+//!
+//! #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//! pub struct Files {
+//!     #[geekorm(primary_key, auto_increment)]
+//!     pub id: PrimaryKeyInteger,
+//!     #[geekorm(compress)]
+//!     #[serde(deserialize_with = "geekorm::utils::compression::deserialize")]
+//!     pub data: Vec<u8>,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let connection = rusqlite::Connection::open_in_memory()?;
+//!     Files::create_table(&connection).await?;
+//!
+//!     let data = b"GeekORM ".repeat(10_000);
+//!     let mut file = Files::new(data.clone());
+//!     file.save(&connection).await?;
+//!
+//!     // The blob is stored compressed
+//!     let stored: Option<i64> = rusqlite::Connection::query_scalar(
+//!         &connection,
+//!         Query::raw(QueryType::Select, "SELECT length(data) FROM Files;"),
+//!     )
+//!     .await?;
+//!     # assert!(stored.unwrap() < data.len() as i64);
+//!
+//!     // ... and decompressed when it is read back
+//!     let file = Files::fetch_by_primary_key(&connection, file.id).await?;
+//!     # assert_eq!(file.data, data);
+//!     Ok(())
+//! }
+//! # }
+//! ```
+
+use std::io::{Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::Value;
+
+/// Marker written in front of compressed values
+///
+/// Values without the marker (rows written before the column was compressed)
+/// are never decompressed, even if they happen to look like gzip data.
+const MARKER: &[u8] = b"GEEKORM:GZ:";
+
+/// Compress data using gzip
+pub fn compress(data: &[u8]) -> Result<Vec<u8>, crate::Error> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(data)
+        .map_err(|e| crate::Error::CompressionError(e.to_string()))?;
+    encoder
+        .finish()
+        .map_err(|e| crate::Error::CompressionError(e.to_string()))
+}
+
+/// Decompress gzip compressed data
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, crate::Error> {
+    let mut decoder = GzDecoder::new(data);
+    let mut decompressed = Vec::new();
+    decoder
+        .read_to_end(&mut decompressed)
+        .map_err(|e| crate::Error::CompressionError(e.to_string()))?;
+    Ok(decompressed)
+}
+
+/// Check if a stored value was compressed by [`compress_value`]
+pub fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(MARKER)
+}
+
+/// Compress a blob / JSON value before it is written to the database
+///
+/// The compressed data is prefixed with a marker so [`deserialize`] knows to
+/// decompress it. Other values are returned unchanged.
+pub fn compress_value(value: Value) -> Result<Value, crate::Error> {
+    match value {
+        Value::Blob(ref data) | Value::Json(ref data) => {
+            let mut compressed = MARKER.to_vec();
+            compressed.extend(compress(data)?);
+            Ok(Value::Blob(compressed))
+        }
+        _ => Ok(value),
+    }
+}
+
+/// Deserialize a compressed blob column
+///
+/// Use with `#[serde(deserialize_with = "geekorm::utils::compression::deserialize")]`
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let data: Vec<u8> = serde::Deserialize::deserialize(deserializer)?;
+    match data.strip_prefix(MARKER) {
+        Some(compressed) => decompress(compressed).map_err(serde::de::Error::custom),
+        None => Ok(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compress_round_trip() {
+        let data = b"GeekORM ".repeat(1024);
+
+        let compressed = compress(&data).unwrap();
+        assert!(compressed.len() < data.len());
+        assert_eq!(decompress(&compressed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_compress_value() {
+        let data = b"GeekORM ".repeat(1024);
+
+        match compress_value(Value::Blob(data.clone())).unwrap() {
+            Value::Blob(compressed) => {
+                assert!(is_compressed(&compressed));
+                assert!(compressed.len() < data.len());
+                let value = serde_json::to_value(&compressed).unwrap();
+                assert_eq!(deserialize(value).unwrap(), data);
+            }
+            value => panic!("Expected a blob, got {:?}", value),
+        }
+        assert_eq!(
+            compress_value(Value::Integer(42)).unwrap(),
+            Value::Integer(42)
+        );
+    }
+
+    #[test]
+    fn test_deserialize_uncompressed() {
+        let value = serde_json::json!([1, 2, 3]);
+        let data = deserialize(value).unwrap();
+        assert_eq!(data, vec![1, 2, 3]);
+
+        // Raw gzip data (without the marker) is left as-is
+        let gzip = compress(b"GeekORM").unwrap();
+        let value = serde_json::to_value(&gzip).unwrap();
+        assert_eq!(deserialize(value).unwrap(), gzip);
+    }
+}
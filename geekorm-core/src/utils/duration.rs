@@ -0,0 +1,235 @@
+//! # Durations
+//!
+//! Durations (`std::time::Duration` and `chrono::Duration`) are stored in an
+//! `INTEGER` column as a number of **seconds**, sub-second precision is not stored
+//! and durations longer than `i64::MAX` seconds are clamped.
+//! The derive requires serde to use this module (or [`option`], `chrono` and
+//! `chrono::option` for the other types) to (de)serialize the field:
+//! `#[serde(with = "geekorm::utils::duration")]`.
+//!
+//! `time::Duration` is not supported.
+//!
+//! ```rust
+//! # #[cfg(feature = "rusqlite")] {
+//! use geekorm::prelude::*;
+//! use std::time::Duration;
+//!
+//! #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//! pub struct Sessions {
+//!     #[geekorm(primary_key, auto_increment)]
+//!     pub id: PrimaryKeyInteger,
+//!     pub token: String,
+//!     #[serde(with = "geekorm::utils::duration")]
+//!     pub ttl: Duration,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let connection = rusqlite::Connection::open_in_memory()?;
+//!     Sessions::create_table(&connection).await?;
+//!     # assert!(Sessions::query_create().build()?.to_str().contains("ttl INTEGER NOT NULL"));
+//!
+//!     let mut session = Sessions::new("abc", Duration::from_secs(3600));
+//!     session.save(&connection).await?;
+//!
+//!     let session = Sessions::fetch_by_primary_key(&connection, session.id).await?;
+//!     # assert_eq!(session.ttl, Duration::from_secs(3600));
+//!     Ok(())
+//! }
+//! # }
+//! ```
+
+use std::time::Duration;
+
+/// Get the number of seconds of a duration (clamped to `i64::MAX`)
+pub(crate) fn as_seconds(value: &Duration) -> i64 {
+    i64::try_from(value.as_secs()).unwrap_or(i64::MAX)
+}
+
+/// Serialize a duration as a number of seconds
+///
+/// Use with `#[serde(with = "geekorm::utils::duration")]`
+pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_i64(as_seconds(value))
+}
+
+/// Deserialize a duration from a number of seconds
+///
+/// Use with `#[serde(with = "geekorm::utils::duration")]`
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let seconds: u64 = serde::Deserialize::deserialize(deserializer)?;
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Store an optional duration as a number of seconds (or `NULL`)
+///
+/// Use with `#[serde(with = "geekorm::utils::duration::option")]`
+pub mod option {
+    use std::time::Duration;
+
+    /// Serialize an optional duration as a number of seconds
+    pub fn serialize<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match value {
+            Some(value) => serializer.serialize_some(&super::as_seconds(value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an optional duration from a number of seconds
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let seconds: Option<u64> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(seconds.map(Duration::from_secs))
+    }
+}
+
+/// Store a `chrono::Duration` as a number of seconds
+///
+/// Use with `#[serde(with = "geekorm::utils::duration::chrono")]`
+#[cfg(feature = "chrono")]
+pub mod chrono {
+    use ::chrono::Duration;
+
+    /// Serialize a duration as a number of seconds
+    pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_i64(value.num_seconds())
+    }
+
+    /// Deserialize a duration from a number of seconds
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let seconds: i64 = serde::Deserialize::deserialize(deserializer)?;
+        from_seconds(seconds)
+    }
+
+    fn from_seconds<E: serde::de::Error>(seconds: i64) -> Result<Duration, E> {
+        Duration::try_seconds(seconds)
+            .ok_or_else(|| E::custom(format!("Invalid duration: {}s", seconds)))
+    }
+
+    /// Store an optional `chrono::Duration` as a number of seconds (or `NULL`)
+    ///
+    /// Use with `#[serde(with = "geekorm::utils::duration::chrono::option")]`
+    pub mod option {
+        use ::chrono::Duration;
+
+        /// Serialize an optional duration as a number of seconds
+        pub fn serialize<S>(value: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            match value {
+                Some(value) => serializer.serialize_some(&value.num_seconds()),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize an optional duration from a number of seconds
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            let seconds: Option<i64> = serde::Deserialize::deserialize(deserializer)?;
+            seconds.map(super::from_seconds).transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Value;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Session {
+        #[serde(with = "super")]
+        ttl: Duration,
+    }
+
+    #[test]
+    fn test_duration_seconds() {
+        let session = Session {
+            ttl: Duration::from_millis(90_500),
+        };
+        assert_eq!(Value::from(&session.ttl), Value::Integer(90));
+
+        let json = serde_json::to_string(&session).unwrap();
+        assert_eq!(json, r#"{"ttl":90}"#);
+        // Sub-second precision is not stored
+        assert_eq!(
+            serde_json::from_str::<Session>(&json).unwrap(),
+            Session {
+                ttl: Duration::from_secs(90)
+            }
+        );
+    }
+
+    #[test]
+    fn test_duration_clamped() {
+        let session = Session { ttl: Duration::MAX };
+        assert_eq!(Value::from(&session.ttl), Value::Integer(i64::MAX));
+        assert_eq!(
+            serde_json::to_string(&session).unwrap(),
+            format!(r#"{{"ttl":{}}}"#, i64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_optional_duration_seconds() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Session {
+            #[serde(with = "super::option")]
+            grace: Option<Duration>,
+        }
+
+        let session = Session {
+            grace: Some(Duration::from_secs(30)),
+        };
+        let json = serde_json::to_string(&session).unwrap();
+        assert_eq!(json, r#"{"grace":30}"#);
+        assert_eq!(serde_json::from_str::<Session>(&json).unwrap(), session);
+
+        let session = Session { grace: None };
+        let json = serde_json::to_string(&session).unwrap();
+        assert_eq!(json, r#"{"grace":null}"#);
+        assert_eq!(serde_json::from_str::<Session>(&json).unwrap(), session);
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_chrono_duration_seconds() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Timer {
+            #[serde(with = "super::chrono")]
+            offset: ::chrono::Duration,
+        }
+
+        let timer = Timer {
+            offset: ::chrono::Duration::minutes(-5),
+        };
+        assert_eq!(Value::from(&timer.offset), Value::Integer(-300));
+
+        let json = serde_json::to_string(&timer).unwrap();
+        assert_eq!(json, r#"{"offset":-300}"#);
+        assert_eq!(serde_json::from_str::<Timer>(&json).unwrap(), timer);
+
+        let time = ::chrono::NaiveTime::from_hms_opt(12, 30, 5).unwrap();
+        assert_eq!(Value::from(time), Value::Text(String::from("12:30:05")));
+    }
+}
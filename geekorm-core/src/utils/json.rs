@@ -0,0 +1,138 @@
+//! # JSON
+//!
+//! Schemaless JSON (`serde_json::Value`) is stored in a `TEXT` column as the
+//! serialized JSON document. The database returns the column as a string, so the
+//! derive requires serde to use this module (or [`option`] for
+//! `Option<serde_json::Value>`) to deserialize the field:
+//! `#[serde(with = "geekorm::utils::json")]`.
+//!
+//! The value is serialized as-is, and already parsed JSON (for example when the
+//! struct is deserialized from an API request) is accepted as well.
+//!
+//! ```rust
+//! # #[cfg(feature = "rusqlite")] {
+//! use geekorm::prelude::*;
+//!
+//! #[derive(Table, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//! pub struct Events {
+//!     #[geekorm(primary_key, auto_increment)]
+//!     pub id: PrimaryKeyInteger,
+//!     #[serde(with = "geekorm::utils::json")]
+//!     pub payload: serde_json::Value,
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> anyhow::Result<()> {
+//!     let connection = rusqlite::Connection::open_in_memory()?;
+//!     Events::create_table(&connection).await?;
+//!     # assert!(Events::query_create().build()?.to_str().contains("payload TEXT NOT NULL"));
+//!
+//!     let payload = serde_json::json!({ "action": "login", "meta": { "ip": "127.0.0.1" } });
+//!     let mut event = Events::new(payload.clone());
+//!     event.save(&connection).await?;
+//!
+//!     let event = Events::fetch_by_primary_key(&connection, event.id).await?;
+//!     # assert_eq!(event.payload, payload);
+//!     Ok(())
+//! }
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Parse a JSON document stored as text
+///
+/// Values which are not strings were not read from a `TEXT` column and are
+/// returned unchanged. Strings which are not valid JSON are kept as strings.
+fn from_text(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(text) => {
+            serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text))
+        }
+        value => value,
+    }
+}
+
+/// Serialize a JSON value
+///
+/// Use with `#[serde(with = "geekorm::utils::json")]`
+pub fn serialize<S>(value: &serde_json::Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.serialize(serializer)
+}
+
+/// Deserialize a JSON value from the JSON document stored in the column
+///
+/// Use with `#[serde(with = "geekorm::utils::json")]`
+pub fn deserialize<'de, D>(deserializer: D) -> Result<serde_json::Value, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde_json::Value::deserialize(deserializer).map(from_text)
+}
+
+/// Store an optional JSON value as a JSON document (or `NULL`)
+///
+/// Use with `#[serde(with = "geekorm::utils::json::option")]`
+pub mod option {
+    use serde::{Deserialize, Serialize};
+
+    /// Serialize an optional JSON value
+    pub fn serialize<S>(value: &Option<serde_json::Value>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        value.serialize(serializer)
+    }
+
+    /// Deserialize an optional JSON value from the JSON document stored in the column
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<serde_json::Value>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+        Ok(value.map(super::from_text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Event {
+        #[serde(with = "super")]
+        payload: serde_json::Value,
+        #[serde(with = "super::option")]
+        meta: Option<serde_json::Value>,
+    }
+
+    #[test]
+    fn test_json_from_text() {
+        let event: Event =
+            serde_json::from_str(r#"{"payload": "{\"action\": \"login\"}", "meta": "[1, 2]"}"#)
+                .unwrap();
+        assert_eq!(event.payload, serde_json::json!({ "action": "login" }));
+        assert_eq!(event.meta, Some(serde_json::json!([1, 2])));
+
+        let event: Event = serde_json::from_str(r#"{"payload": "login", "meta": null}"#).unwrap();
+        assert_eq!(event.payload, serde_json::json!("login"));
+        assert_eq!(event.meta, None);
+    }
+
+    #[test]
+    fn test_json_values() {
+        let event: Event =
+            serde_json::from_str(r#"{"payload": {"action": "login"}, "meta": [1, 2]}"#).unwrap();
+        assert_eq!(event.payload, serde_json::json!({ "action": "login" }));
+        assert_eq!(event.meta, Some(serde_json::json!([1, 2])));
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "payload": { "action": "login" }, "meta": [1, 2] })
+        );
+    }
+}
@@ -6,12 +6,26 @@
 //! - Cryptography
 //!   - `generate_random_string` - Generate a random string
 //!   - `generate_hash` - Generate a hash
+//! - Compression
+//!   - `compress` / `decompress` - Compress blob columns
+//! - Blobs
+//!   - `ToBlob` / `FromBlob` - Typed blob columns with a custom binary encoding
+//! - Durations
+//!   - `duration` - Store durations as a number of seconds
+//! - JSON
+//!   - `json` - Store schemaless JSON as a JSON document
 //!
 
+pub mod blob;
+#[cfg(feature = "compression")]
+pub mod compression;
 pub mod crypto;
+pub mod duration;
+pub mod json;
 #[cfg(feature = "two-factor-auth")]
 pub mod tfa;
 
+pub use blob::{FromBlob, ToBlob};
 #[cfg(feature = "hash")]
 pub use crypto::hashing::{generate_hash, verify_hash};
 #[cfg(feature = "rand")]
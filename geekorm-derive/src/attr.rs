@@ -16,6 +16,9 @@
 //!     age: i32,
 //!
 //!     occupation: String,
+//!     /// Case-insensitive column
+//!     #[geekorm(unique, collate = "nocase", max_length = 255)]
+//!     email: String,
 //!     /// Random value
 //! #   #[cfg(feature = "rand")]
 //!     #[geekorm(unique, rand, rand_length = "42", rand_prefix = "gorm_")]
@@ -41,6 +44,7 @@
 //!         "geekmasher",
 //!         42,
 //!         "Software Engineer",
+//!         "geekmasher@example.com",
 //!     );
 //!     let post = Posts::new(
 //!         "Why I love Rust",
@@ -78,6 +82,8 @@ pub(crate) enum GeekAttributeKeys {
     Key,
     /// Unique value
     Unique,
+    /// Index the column
+    Index,
     /// New Constructor
     New,
     /// Primary Key
@@ -88,6 +94,8 @@ pub(crate) enum GeekAttributeKeys {
     NotNull,
     /// Foreign Key
     ForeignKey,
+    /// Collating sequence
+    Collate,
     /// Aliases
     Aliases,
     /// Random value
@@ -104,8 +112,25 @@ pub(crate) enum GeekAttributeKeys {
     OnValidate,
     OnUpdate,
     OnSave,
+    /// Async hook called before the row is saved
+    BeforeSave,
+    /// Length constraints for text fields
+    MinLength,
+    MaxLength,
     /// Skip this field
     Skip,
+    /// Transient field (not a column, populated in memory e.g. loaded relations)
+    Transient,
+    /// Read-only field (selected but never inserted or updated)
+    Readonly,
+    /// Compress the (blob) column
+    Compress,
+    /// Typed blob column (encoded with `ToBlob` / `FromBlob`)
+    Blob,
+    /// Optimistic concurrency version (integer) column
+    Version,
+    /// Store a `Data` enum as an integer (requires an integer `#[repr(...)]`)
+    Integer,
     /// Disable features
     Disable,
 }
@@ -119,6 +144,8 @@ pub(crate) enum GeekAttributeValue {
 }
 
 const TO_STRING_KEYS: [&str; 1] = ["lowercase"];
+/// SQLite built-in collating sequences
+const COLLATE_KEYS: [&str; 3] = ["binary", "nocase", "rtrim"];
 
 impl GeekAttribute {
     pub(crate) fn parse_all(all_attrs: &[Attribute]) -> Result<Vec<Self>, syn::Error> {
@@ -173,6 +200,23 @@ impl GeekAttribute {
                     ))
                 }
             }
+            Some(GeekAttributeKeys::BeforeSave) => {
+                if let Some(GeekAttributeValue::String(path)) = &self.value {
+                    if syn::parse_str::<syn::Path>(path).is_ok() {
+                        Ok(())
+                    } else {
+                        Err(syn::Error::new(
+                            self.value_span.unwrap_or_else(|| self.span.span()),
+                            "The `before_save` attribute requires a function path",
+                        ))
+                    }
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "The `before_save` attribute requires a String value",
+                    ))
+                }
+            }
             Some(GeekAttributeKeys::New) => {
                 // Requires: The `new` attribute requires a string or bool value
                 if let Some(value) = &self.value {
@@ -219,6 +263,40 @@ impl GeekAttribute {
                     ))
                 }
             }
+            Some(GeekAttributeKeys::Collate) => {
+                if let Some(GeekAttributeValue::String(content)) = &self.value {
+                    if COLLATE_KEYS.contains(&content.to_lowercase().as_str()) {
+                        Ok(())
+                    } else {
+                        Err(syn::Error::new(
+                            self.value_span.unwrap_or_else(|| self.span.span()),
+                            "The `collate` attribute only supports `binary`, `nocase` or `rtrim`",
+                        ))
+                    }
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "The `collate` attribute requires a string value",
+                    ))
+                }
+            }
+            Some(GeekAttributeKeys::MinLength) | Some(GeekAttributeKeys::MaxLength) => {
+                if let Some(GeekAttributeValue::Int(length)) = &self.value {
+                    if *length >= 0 {
+                        Ok(())
+                    } else {
+                        Err(syn::Error::new(
+                            self.value_span.unwrap_or_else(|| self.span.span()),
+                            "The length attributes require a positive integer value",
+                        ))
+                    }
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "The length attributes require an integer value",
+                    ))
+                }
+            }
             Some(GeekAttributeKeys::HashAlgorithm) => {
                 if let Some(value) = &self.value {
                     if let GeekAttributeValue::String(content) = value {
@@ -244,6 +322,26 @@ impl GeekAttribute {
                     ))
                 }
             }
+            Some(GeekAttributeKeys::Index) => {
+                if self.value.is_some() {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "The `index` attribute does not require a value",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            Some(GeekAttributeKeys::Integer) => {
+                if self.value.is_some() {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "The `integer` attribute does not require a value",
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
             Some(GeekAttributeKeys::Searchable) => {
                 if self.value.is_some() {
                     Err(syn::Error::new(
@@ -319,6 +417,20 @@ impl Parse for GeekAttribute {
 
         let key: Option<GeekAttributeKeys> = match name_str.as_str() {
             "skip" => Some(GeekAttributeKeys::Skip),
+            "transient" => Some(GeekAttributeKeys::Transient),
+            "readonly" | "read_only" => Some(GeekAttributeKeys::Readonly),
+            "compress" => {
+                match cfg!(feature = "compression") {
+                    true => Some(GeekAttributeKeys::Compress),
+                    false => return Err(syn::Error::new(
+                        name.span(),
+                        "The `compress` attribute requires the `compression` feature to be enabled",
+                    )),
+                }
+            }
+            "blob" | "proto" => Some(GeekAttributeKeys::Blob),
+            "version" => Some(GeekAttributeKeys::Version),
+            "integer" => Some(GeekAttributeKeys::Integer),
             "disable" => Some(GeekAttributeKeys::Disable),
             "rename" => Some(GeekAttributeKeys::Rename),
             "to_str" | "to_string" => Some(GeekAttributeKeys::ToString),
@@ -330,12 +442,17 @@ impl Parse for GeekAttribute {
             "auto_increment" => Some(GeekAttributeKeys::AutoIncrement),
             "not_null" => Some(GeekAttributeKeys::NotNull),
             "unique" => Some(GeekAttributeKeys::Unique),
+            "index" => Some(GeekAttributeKeys::Index),
+            "collate" | "collation" => Some(GeekAttributeKeys::Collate),
+            "min_length" => Some(GeekAttributeKeys::MinLength),
+            "max_length" => Some(GeekAttributeKeys::MaxLength),
             // Foreign Key
             "foreign_key" => Some(GeekAttributeKeys::ForeignKey),
             // Functions on action
             "validate" | "on_validate" => Some(GeekAttributeKeys::OnValidate),
             "update" | "on_update" | "on_update_write" => Some(GeekAttributeKeys::OnUpdate),
             "save" | "on_save" | "on_save_write" => Some(GeekAttributeKeys::OnSave),
+            "before_save" => Some(GeekAttributeKeys::BeforeSave),
 
             // New Constructor
             "new" => match cfg!(feature = "new") {
@@ -460,3 +577,84 @@ impl Parse for GeekAttribute {
         })
     }
 }
+
+/// The `#[serde(...)]` attributes of a field that the derive relies on
+///
+/// The derive can't add serde attributes itself, so columns which are stored
+/// differently (compressed, typed blobs, ...) check the user added them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SerdeAttributes {
+    /// `with = "..."`
+    pub(crate) with: Option<String>,
+    /// `deserialize_with = "..."`
+    pub(crate) deserialize_with: Option<String>,
+    /// `skip` or `skip_deserializing`
+    pub(crate) skip: bool,
+    /// `default` or `default = "..."`
+    pub(crate) default: bool,
+}
+
+impl SerdeAttributes {
+    pub(crate) fn parse_all(attrs: &[Attribute]) -> Result<Self, syn::Error> {
+        let mut serde = SerdeAttributes::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("with") {
+                    serde.with = Some(Self::parse_path(&meta)?);
+                } else if meta.path.is_ident("deserialize_with") {
+                    serde.deserialize_with = Some(Self::parse_path(&meta)?);
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    serde.skip = true;
+                } else if meta.path.is_ident("default") {
+                    serde.default = true;
+                    if meta.input.peek(Token![=]) {
+                        meta.value()?.parse::<LitStr>()?;
+                    }
+                } else if meta.input.peek(Token![=]) {
+                    // Other `key = value` attributes (e.g. `rename = "..."`)
+                    meta.value()?.parse::<syn::Expr>()?;
+                } else if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                    // Other `key(...)` attributes (e.g. `rename(serialize = "...")`)
+                    let (_, _, content) = meta.input.parse_any_delimiter()?;
+                    content.parse::<TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(serde)
+    }
+
+    /// Parse a `"path::to::module"` value (a leading `::` is ignored)
+    fn parse_path(meta: &syn::meta::ParseNestedMeta) -> Result<String, syn::Error> {
+        let lit: LitStr = meta.value()?.parse()?;
+        let path: syn::Path = lit.parse()?;
+        Ok(path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<String>>()
+            .join("::"))
+    }
+
+    /// Check if the field is (de)serialized `with` the module
+    pub(crate) fn is_with(&self, module: &str) -> bool {
+        self.with.as_deref() == Some(module)
+    }
+
+    /// Check if the field is deserialized with the function
+    ///
+    /// `with = "module"` uses `module::deserialize`, so both forms are accepted.
+    pub(crate) fn is_deserialized_with(&self, function: &str) -> bool {
+        self.deserialize_with.as_deref() == Some(function)
+            || self
+                .with
+                .as_ref()
+                .is_some_and(|with| format!("{}::deserialize", with) == function)
+    }
+
+    /// Check if serde never reads the field from the row
+    pub(crate) fn is_skipped(&self) -> bool {
+        self.skip || self.default
+    }
+}
@@ -11,8 +11,11 @@ use syn::{
 };
 
 use crate::{
-    attr::{GeekAttribute, GeekAttributeKeys, GeekAttributeValue},
-    derive::{ColumnTypeDerive, ColumnTypeOptionsDerive},
+    attr::{GeekAttribute, GeekAttributeKeys, GeekAttributeValue, SerdeAttributes},
+    derive::{
+        columntypes::{duration_serde_modules, json_serde_module},
+        ColumnTypeDerive, ColumnTypeOptionsDerive,
+    },
     internal::TableState,
 };
 
@@ -156,6 +159,14 @@ pub(crate) struct ColumnDerive {
     pub(crate) coltype: ColumnTypeDerive,
     /// Skip the column
     pub(crate) skip: bool,
+    /// Column is managed by the database and never written
+    pub(crate) readonly: bool,
+    /// Compress the column before it is written
+    pub(crate) compress: bool,
+    /// Typed blob column (encoded with `ToBlob` / `FromBlob`)
+    pub(crate) blob: bool,
+    /// Optimistic concurrency version column
+    pub(crate) version: bool,
     /// Update the column
     pub(crate) update: Option<String>,
     pub(crate) save: Option<String>,
@@ -174,6 +185,43 @@ impl ColumnDerive {
                     GeekAttributeKeys::Skip => {
                         self.skip = true;
                     }
+                    GeekAttributeKeys::Transient => {
+                        // Transient fields are never read from or written to the database
+                        self.skip = true;
+                    }
+                    GeekAttributeKeys::Readonly => {
+                        self.readonly = true;
+                    }
+                    GeekAttributeKeys::Compress => {
+                        if !matches!(self.coltype, ColumnTypeDerive::Blob(_)) {
+                            return Err(syn::Error::new(
+                                attr.span.span(),
+                                "The `compress` attribute is only supported on blob columns (e.g. `Vec<u8>`)",
+                            ));
+                        }
+                        self.compress = true;
+                    }
+                    GeekAttributeKeys::Blob => {
+                        if !matches!(self.coltype, ColumnTypeDerive::Blob(_)) {
+                            return Err(syn::Error::new(
+                                attr.span.span(),
+                                "The `blob` attribute is only supported on blob columns (custom types)",
+                            ));
+                        }
+                        self.blob = true;
+                    }
+                    GeekAttributeKeys::Version => {
+                        if !matches!(self.coltype, ColumnTypeDerive::Integer(_)) {
+                            return Err(syn::Error::new(
+                                attr.span.span(),
+                                "The `version` attribute is only supported on integer columns",
+                            ));
+                        }
+                        self.version = true;
+                    }
+                    GeekAttributeKeys::Index => {
+                        self.coltype.set_index(true);
+                    }
                     GeekAttributeKeys::Unique => {
                         self.coltype.set_unique(true);
                         // If the column is unique, then it should be searchable by default
@@ -224,6 +272,10 @@ impl ColumnDerive {
                     GeekAttributeKeys::PrimaryKey => {
                         if let ColumnTypeDerive::Identifier(_) = self.coltype {
                             // Skip as the column type is already set
+                        } else if let ColumnTypeDerive::Text(_) = self.coltype {
+                            // String keys can't be auto incremented
+                            self.coltype =
+                                ColumnTypeDerive::Text(ColumnTypeOptionsDerive::primary_key_text());
                         } else {
                             self.coltype = ColumnTypeDerive::Identifier(ColumnTypeOptionsDerive {
                                 primary_key: true,
@@ -243,6 +295,27 @@ impl ColumnDerive {
                         }
                     }
                     GeekAttributeKeys::NotNull => self.coltype.set_notnull(true),
+                    GeekAttributeKeys::Collate => {
+                        if let Some(GeekAttributeValue::String(collation)) = &attr.value {
+                            self.coltype.set_collation(collation.to_uppercase());
+                        }
+                    }
+                    GeekAttributeKeys::MinLength | GeekAttributeKeys::MaxLength => {
+                        if !matches!(self.coltype, ColumnTypeDerive::Text(_)) {
+                            return Err(syn::Error::new(
+                                attr.span.span(),
+                                "Length constraints are only supported on text columns",
+                            ));
+                        }
+                        if let Some(GeekAttributeValue::Int(length)) = &attr.value {
+                            let length = Some(*length as usize);
+                            if key == &GeekAttributeKeys::MinLength {
+                                self.coltype.set_length(length, None);
+                            } else {
+                                self.coltype.set_length(None, length);
+                            }
+                        }
+                    }
                     GeekAttributeKeys::ForeignKey => {
                         if let Some(value) = &attr.value {
                             if let GeekAttributeValue::String(name) = value {
@@ -279,6 +352,7 @@ impl ColumnDerive {
                                 self.coltype =
                                     ColumnTypeDerive::ForeignKey(ColumnTypeOptionsDerive {
                                         foreign_key: format!("{}.{}", table, column),
+                                        not_null: self.coltype.options().not_null,
                                         ..Default::default()
                                     });
                             }
@@ -376,6 +450,43 @@ impl ColumnDerive {
         }
     }
 
+    /// Check if the column type is an `Option<T>`
+    pub(crate) fn is_optional(&self) -> bool {
+        match &self.itype {
+            Type::Path(TypePath { path, .. }) => path
+                .segments
+                .first()
+                .map(|segment| segment.ident == "Option")
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// Check if the column type is a `PrimaryKey` with an `is_set()` method
+    pub(crate) fn is_primary_key_type(&self) -> bool {
+        let Type::Path(TypePath { path, .. }) = &self.itype else {
+            return false;
+        };
+        let Some(segment) = path.segments.last() else {
+            return false;
+        };
+        match segment.ident.to_string().as_str() {
+            "PrimaryKeyInteger" | "PrimaryKeyString" | "PrimaryKeyUuid" => true,
+            "PrimaryKey" => match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => args.args.iter().any(|arg| match arg {
+                    syn::GenericArgument::Type(Type::Path(TypePath { path, .. })) => path
+                        .segments
+                        .last()
+                        .map(|s| s.ident == "u64" || s.ident == "String" || s.ident == "Uuid")
+                        .unwrap_or(false),
+                    _ => false,
+                }),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
     /// Check if the column is unique
     pub(crate) fn is_unique(&self) -> bool {
         match &self.coltype {
@@ -398,7 +509,7 @@ impl ColumnDerive {
     /// Convert the column into a list of parameters for a function
     pub(crate) fn to_params(&self) -> Option<TokenStream> {
         // Skip the column if it's marked as such
-        if self.skip {
+        if self.skip || self.readonly || self.version {
             return None;
         }
 
@@ -434,8 +545,8 @@ impl ColumnDerive {
                         return Some(self.to_params_foreign_key_int(identifier, inner_key_type));
                     }
                     "ForeignKeyInteger" => {
-                        // GenericArgument of i32
-                        let inner_key_type = GenericArgument::Type(syn::parse_quote! { i32 });
+                        // ForeignKeyInteger<D> is ForeignKey<u64, D>
+                        let inner_key_type = GenericArgument::Type(syn::parse_quote! { u64 });
                         return Some(self.to_params_foreign_key_int(identifier, &inner_key_type));
                     }
                     _ => {}
@@ -461,8 +572,8 @@ impl ColumnDerive {
     pub(crate) fn to_self(&self) -> TokenStream {
         let identifier = &self.identifier;
 
-        // For Skipped columns, return the identifier
-        if self.skip {
+        // For Skipped, read-only and version columns, return the identifier
+        if self.skip || self.readonly || self.version {
             return quote! { #identifier: Default::default() };
         }
 
@@ -483,16 +594,8 @@ impl ColumnDerive {
                     .unwrap();
                 return quote! { #identifier: #data };
             }
-        } else if let Some(ColumnMode::Rand { len, prefix, env }) = &self.mode {
-            let mut pre = String::new();
-            if let Some(prefix) = prefix {
-                pre.push_str(prefix.as_str());
-                pre.push('_');
-            }
-            if let Some(env) = env {
-                pre.push_str(env.as_str());
-                pre.push('_');
-            }
+        } else if let Some(ColumnMode::Rand { len, .. }) = &self.mode {
+            let pre = self.rand_prefix();
 
             return quote! {
                 #identifier: geekorm::utils::generate_random_string(#len, #pre)
@@ -523,7 +626,7 @@ impl ColumnDerive {
                             #identifier: geekorm::PrimaryKey::default()
                         };
                     }
-                    "ForeignKey" => {
+                    "ForeignKey" | "ForeignKeyInteger" => {
                         // Generate a new foreign key
                         return quote! {
                             #identifier: geekorm::ForeignKey::from(#identifier.into())
@@ -612,12 +715,53 @@ impl ColumnDerive {
             {
                 let q = #foreign_ident::query_select_by_primary_key(self.#identifier.key);
                 let r = C::query_first::<#foreign_ident>(connection, q).await?;
-                self.#identifier.data = r.clone();
+                self.#identifier.set_data(r.clone());
                 Ok(r)
             }
         }
     }
 
+    /// Generate a fetcher function for an optional (`Option<ForeignKey<T, D>>`) column
+    ///
+    /// Returns `None` without querying the database if the foreign key is not set.
+    #[allow(unused_variables)]
+    pub(crate) fn get_fetcher_optional(
+        &self,
+        table_ident: &Ident,
+        foreign_ident: &Ident,
+    ) -> TokenStream {
+        let identifier = &self.identifier;
+
+        if self.skip {
+            return quote! {};
+        }
+
+        let func_name = format!("fetch_{}", identifier);
+        let func = Ident::new(&func_name, Span::call_site());
+
+        quote! {
+            /// Fetch the foreign key data for the column (if set)
+            pub async fn #func<'a, C>(
+                &mut self,
+                connection: &'a C
+            ) -> Result<Option<#foreign_ident>, geekorm::Error>
+            where
+                C: geekorm::GeekConnection<Connection = C> + 'a,
+                Self: geekorm::QueryBuilderTrait + serde::Serialize + serde::de::DeserializeOwned
+            {
+                match self.#identifier.as_mut() {
+                    Some(fk) => {
+                        let q = #foreign_ident::query_select_by_primary_key(fk.key.clone());
+                        let r = C::query_first::<#foreign_ident>(connection, q).await?;
+                        fk.set_data(r.clone());
+                        Ok(Some(r))
+                    }
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
     /// Generate a hash helper functions for the column
     pub(crate) fn get_hash_helpers(&self) -> TokenStream {
         let identifier = &self.identifier;
@@ -664,14 +808,7 @@ impl ColumnDerive {
         } else {
             10
         };
-        let prefix: String = if let Some(mode) = &self.mode {
-            match mode {
-                ColumnMode::Rand { prefix, .. } => prefix.clone().unwrap_or_default(),
-                _ => String::new(),
-            }
-        } else {
-            String::new()
-        };
+        let prefix = self.rand_prefix();
 
         quote! {
             /// Generate a random value for the column
@@ -680,6 +817,24 @@ impl ColumnDerive {
             }
         }
     }
+
+    /// The prefix for randomly generated values (`prefix_env_`)
+    ///
+    /// Used by both `new()` and `regenerate_*` so the values have the same format.
+    pub(crate) fn rand_prefix(&self) -> String {
+        let mut pre = String::new();
+        if let Some(ColumnMode::Rand { prefix, env, .. }) = &self.mode {
+            if let Some(prefix) = prefix {
+                pre.push_str(prefix.as_str());
+                pre.push('_');
+            }
+            if let Some(env) = env {
+                pre.push_str(env.as_str());
+                pre.push('_');
+            }
+        }
+        pre
+    }
 }
 
 impl Default for ColumnDerive {
@@ -689,6 +844,10 @@ impl Default for ColumnDerive {
             coltype: ColumnTypeDerive::Text(ColumnTypeOptionsDerive::default()),
             alias: String::new(),
             skip: false,
+            readonly: false,
+            compress: false,
+            blob: false,
+            version: false,
             update: None,
             save: None,
             attributes: Vec::new(),
@@ -714,6 +873,7 @@ impl ToTokens for ColumnDerive {
         let coltype = &self.coltype;
         let alias = &self.alias;
         let skip = &self.skip;
+        let readonly = &self.readonly;
 
         tokens.extend(quote! {
             geekorm::Column {
@@ -721,6 +881,7 @@ impl ToTokens for ColumnDerive {
                 column_type: #coltype,
                 alias: String::from(#alias),
                 skip: #skip,
+                readonly: #readonly,
             }
         });
     }
@@ -733,6 +894,7 @@ impl From<ColumnDerive> for geekorm_core::Column {
             column_type: ColumnType::from(value.coltype),
             alias: value.alias,
             skip: value.skip,
+            readonly: value.readonly,
         }
     }
 }
@@ -756,9 +918,17 @@ impl TryFrom<&Field> for ColumnDerive {
             Ok(attributes) => attributes,
             Err(e) => return Err(e),
         };
-        let coltype = match ColumnTypeDerive::try_from(&itype) {
-            Ok(coltype) => coltype,
-            Err(e) => return Err(e),
+        let transient = attributes
+            .iter()
+            .any(|attr| matches!(attr.key, Some(GeekAttributeKeys::Transient)));
+        // Transient fields can be any type (e.g. `Vec<Child>`) as they are not columns
+        let coltype = if transient {
+            ColumnTypeDerive::Text(ColumnTypeOptionsDerive::default())
+        } else {
+            match ColumnTypeDerive::try_from(&itype) {
+                Ok(coltype) => coltype,
+                Err(e) => return Err(e),
+            }
         };
 
         let mut col = ColumnDerive {
@@ -769,12 +939,65 @@ impl TryFrom<&Field> for ColumnDerive {
             coltype,
             alias: String::from(""),
             skip: false,
+            readonly: false,
+            compress: false,
+            blob: false,
+            version: false,
             update: None,
             save: None,
             mode: None,
         };
         col.apply_attributes()?;
 
+        let serde = SerdeAttributes::parse_all(&value.attrs)?;
+
+        // Compressed columns need to be decompressed when the row is deserialized
+        if col.compress && !serde.is_deserialized_with("geekorm::utils::compression::deserialize") {
+            return Err(syn::Error::new(
+                value.span(),
+                "Compressed columns require `#[serde(deserialize_with = \"geekorm::utils::compression::deserialize\")]`",
+            ));
+        }
+
+        // Transient fields are not in the row, so serde has to skip (or default) them
+        if transient && !serde.is_skipped() {
+            return Err(syn::Error::new(
+                value.span(),
+                "Transient fields require `#[serde(skip)]` (or `#[serde(default)]`) as they are not columns",
+            ));
+        }
+
+        // Typed blobs have to be (de)serialized with `ToBlob` / `FromBlob`
+        if col.blob && !serde.is_with("geekorm::utils::blob") {
+            return Err(syn::Error::new(
+                value.span(),
+                "Typed blob columns require `#[serde(with = \"geekorm::utils::blob\")]`",
+            ));
+        }
+
+        // Durations are stored as a number of seconds
+        let modules = duration_serde_modules(&col.itype);
+        if !modules.is_empty() && !modules.iter().any(|module| serde.is_with(module)) {
+            let attributes: Vec<String> = modules
+                .iter()
+                .map(|module| format!("`#[serde(with = \"{}\")]`", module))
+                .collect();
+            return Err(syn::Error::new(
+                value.span(),
+                format!("Duration columns require {}", attributes.join(" or ")),
+            ));
+        }
+
+        // JSON is stored as text and has to be parsed when the row is deserialized
+        if let Some(module) = json_serde_module(&col.itype) {
+            if !serde.is_with(&module) {
+                return Err(syn::Error::new(
+                    value.span(),
+                    format!("JSON columns require `#[serde(with = \"{}\")]`", module),
+                ));
+            }
+        }
+
         // TODO(geekmasher): Check if the column is public
         // if let Some(ref mode) = col.mode {
         //     if let ColumnMode::Hash(_) = mode {
@@ -839,4 +1062,269 @@ mod tests {
         };
         assert!(column.is_primary_key());
     }
+
+    #[test]
+    fn test_rand_prefix() {
+        let mut column = ColumnDerive {
+            name: "token".to_string(),
+            identifier: Ident::new("token", Span::call_site()),
+            mode: Some(ColumnMode::Rand {
+                len: 42,
+                prefix: Some(String::from("session")),
+                env: None,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(column.rand_prefix(), "session_");
+
+        // `new()` and `regenerate_token()` use the same prefix
+        let new = column.to_self().to_string();
+        let regenerate = column.get_random_helpers().to_string();
+        assert!(new.contains("\"session_\""));
+        assert!(regenerate.contains("\"session_\""));
+
+        column.mode = Some(ColumnMode::Rand {
+            len: 42,
+            prefix: Some(String::from("session")),
+            env: Some(String::from("prod")),
+        });
+        assert_eq!(column.rand_prefix(), "session_prod_");
+
+        column.mode = None;
+        assert_eq!(column.rand_prefix(), "");
+    }
+
+    #[test]
+    fn test_blob() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Places {
+                #[geekorm(blob)]
+                #[serde(with = "geekorm::utils::blob")]
+                location: Point,
+            }
+        };
+        let column = ColumnDerive::try_from(&fields(input)[0]).unwrap();
+        assert!(column.blob);
+        assert!(matches!(column.coltype, ColumnTypeDerive::Blob(_)));
+
+        // Serde has to decode the blob
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Places {
+                #[geekorm(blob)]
+                location: Point,
+            }
+        };
+        assert!(ColumnDerive::try_from(&fields(input)[0]).is_err());
+
+        // Only blob columns can be typed blobs
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Places {
+                #[geekorm(blob)]
+                #[serde(with = "geekorm::utils::blob")]
+                name: String,
+            }
+        };
+        assert!(ColumnDerive::try_from(&fields(input)[0]).is_err());
+    }
+
+    #[test]
+    fn test_duration_columns() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Sessions {
+                #[serde(with = "geekorm::utils::duration")]
+                ttl: std::time::Duration,
+                #[serde(with = "geekorm::utils::duration::chrono")]
+                offset: chrono::Duration,
+                #[serde(with = "geekorm::utils::duration::option")]
+                grace: Option<Duration>,
+                #[serde(with = "geekorm::utils::duration::chrono::option")]
+                delay: Option<Duration>,
+                starts: NaiveTime,
+            }
+        };
+        let fields = fields(input);
+        for field in &fields[..4] {
+            let column = ColumnDerive::try_from(field).unwrap();
+            assert!(matches!(column.coltype, ColumnTypeDerive::Integer(_)));
+        }
+        #[cfg(feature = "chrono")]
+        {
+            let column = ColumnDerive::try_from(&fields[4]).unwrap();
+            assert!(matches!(column.coltype, ColumnTypeDerive::Text(_)));
+        }
+    }
+
+    #[test]
+    fn test_duration_columns_require_serde() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Sessions {
+                ttl: Duration,
+                #[serde(with = "geekorm::utils::duration")]
+                offset: chrono::Duration,
+                #[serde(with = "geekorm::utils::duration")]
+                grace: Option<std::time::Duration>,
+                #[serde(with = "geekorm::utils::duration")]
+                timeout: time::Duration,
+            }
+        };
+        let fields = fields(input);
+        for field in &fields {
+            assert!(ColumnDerive::try_from(field).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_time_columns() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Events {
+                created_at: OffsetDateTime,
+                updated_at: time::OffsetDateTime,
+                day: time::Date,
+                // Not a `time` date, so it is stored as a blob
+                other: Date,
+            }
+        };
+        let fields = fields(input);
+        for field in &fields[..3] {
+            let column = ColumnDerive::try_from(field).unwrap();
+            assert!(matches!(column.coltype, ColumnTypeDerive::Text(_)));
+        }
+        let column = ColumnDerive::try_from(&fields[3]).unwrap();
+        assert!(matches!(column.coltype, ColumnTypeDerive::Blob(_)));
+    }
+
+    #[test]
+    fn test_json_columns() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Events {
+                #[serde(with = "geekorm::utils::json")]
+                payload: serde_json::Value,
+                // `use serde_json::Value;`
+                #[serde(with = "geekorm::utils::json")]
+                data: Value,
+                #[serde(with = "geekorm::utils::json::option")]
+                meta: Option<serde_json::Value>,
+            }
+        };
+        for field in &fields(input) {
+            let column = ColumnDerive::try_from(field).unwrap();
+            assert!(matches!(column.coltype, ColumnTypeDerive::Text(_)));
+        }
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Events {
+                payload: serde_json::Value,
+                data: Value,
+                #[serde(with = "geekorm::utils::json")]
+                meta: Option<serde_json::Value>,
+            }
+        };
+        for field in &fields(input) {
+            assert!(ColumnDerive::try_from(field).is_err());
+        }
+    }
+
+    #[test]
+    fn test_array_columns() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Tokens {
+                token: [u8; 16],
+                hash: Option<[u8; 32]>,
+            }
+        };
+        for field in &fields(input) {
+            let column = ColumnDerive::try_from(field).unwrap();
+            assert!(matches!(column.coltype, ColumnTypeDerive::Blob(_)));
+        }
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Tokens {
+                scores: [i32; 4],
+                names: [String; 2],
+                nested: [[u8; 4]; 4],
+            }
+        };
+        for field in &fields(input) {
+            assert!(ColumnDerive::try_from(field).is_err());
+        }
+    }
+
+    fn fields(input: syn::DeriveInput) -> Vec<Field> {
+        match input.data {
+            syn::Data::Struct(data) => data.fields.into_iter().collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_transient() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Users {
+                #[geekorm(transient)]
+                #[serde(skip)]
+                posts: Vec<Posts>,
+            }
+        };
+        let column = ColumnDerive::try_from(&fields(input)[0]).unwrap();
+        assert!(column.skip);
+        assert!(column.to_params().is_none());
+        assert_eq!(
+            column.to_self().to_string(),
+            "posts : Default :: default ()"
+        );
+
+        // Serde has to skip the field as it is not in the row
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Users {
+                #[geekorm(transient)]
+                posts: Vec<Posts>,
+            }
+        };
+        assert!(ColumnDerive::try_from(&fields(input)[0]).is_err());
+
+        // Only `skip`, `skip_deserializing` or `default` are accepted
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Users {
+                #[geekorm(transient)]
+                #[serde(skip_serializing, rename = "default")]
+                posts: Vec<Posts>,
+            }
+        };
+        assert!(ColumnDerive::try_from(&fields(input)[0]).is_err());
+
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Users {
+                #[geekorm(transient)]
+                #[serde(rename(serialize = "posts"), skip_deserializing)]
+                posts: Vec<Posts>,
+            }
+        };
+        assert!(ColumnDerive::try_from(&fields(input)[0]).is_ok());
+    }
+
+    #[test]
+    fn test_serde_with_paths() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            struct Users {
+                #[geekorm(blob)]
+                #[serde(with = "geekorm::utils::blob")]
+                a: Vec<u8>,
+                #[geekorm(blob)]
+                #[serde(with = "::geekorm::utils::blob")]
+                b: Vec<u8>,
+                #[geekorm(blob)]
+                #[serde(with = "my_blob")]
+                c: Vec<u8>,
+                #[geekorm(blob)]
+                #[serde(deserialize_with = "geekorm::utils::blob::deserialize")]
+                d: Vec<u8>,
+            }
+        };
+        let fields = fields(input);
+        assert!(ColumnDerive::try_from(&fields[0]).is_ok());
+        assert!(ColumnDerive::try_from(&fields[1]).is_ok());
+        assert!(ColumnDerive::try_from(&fields[2]).is_err());
+        assert!(ColumnDerive::try_from(&fields[3]).is_err());
+    }
 }
@@ -64,6 +64,39 @@ impl ColumnTypeDerive {
             }
         }
     }
+    pub fn set_collation(&mut self, collation: String) {
+        match self {
+            ColumnTypeDerive::Identifier(options)
+            | ColumnTypeDerive::Text(options)
+            | ColumnTypeDerive::Integer(options)
+            | ColumnTypeDerive::Boolean(options)
+            | ColumnTypeDerive::Blob(options)
+            | ColumnTypeDerive::ForeignKey(options) => {
+                options.set_collation(collation);
+            }
+        }
+    }
+    /// Get the options for the column type
+    pub fn options(&self) -> &ColumnTypeOptionsDerive {
+        match self {
+            ColumnTypeDerive::Identifier(options)
+            | ColumnTypeDerive::Text(options)
+            | ColumnTypeDerive::Integer(options)
+            | ColumnTypeDerive::Boolean(options)
+            | ColumnTypeDerive::Blob(options)
+            | ColumnTypeDerive::ForeignKey(options) => options,
+        }
+    }
+    pub fn set_length(&mut self, min: Option<usize>, max: Option<usize>) {
+        if let ColumnTypeDerive::Text(options) = self {
+            if min.is_some() {
+                options.min_length = min;
+            }
+            if max.is_some() {
+                options.max_length = max;
+            }
+        }
+    }
     pub fn set_unique(&mut self, unique: bool) {
         match self {
             ColumnTypeDerive::Identifier(options)
@@ -76,6 +109,18 @@ impl ColumnTypeDerive {
             }
         }
     }
+    pub fn set_index(&mut self, index: bool) {
+        match self {
+            ColumnTypeDerive::Identifier(options)
+            | ColumnTypeDerive::Text(options)
+            | ColumnTypeDerive::Integer(options)
+            | ColumnTypeDerive::Boolean(options)
+            | ColumnTypeDerive::Blob(options)
+            | ColumnTypeDerive::ForeignKey(options) => {
+                options.set_index(index);
+            }
+        }
+    }
     pub fn set_auto_increment(&mut self, auto_increment: bool) {
         match self {
             ColumnTypeDerive::Identifier(options) | ColumnTypeDerive::Integer(options) => {
@@ -111,15 +156,124 @@ impl TryFrom<&Type> for ColumnTypeDerive {
     }
 }
 
+/// Get the `geekorm::utils::duration` serde modules which can (de)serialize the type
+///
+/// Empty if the type is not a duration. The derive can't resolve imports, so a
+/// bare `Duration` can either be a `std::time::Duration` or a `chrono::Duration`.
+pub(crate) fn duration_serde_modules(typ: &Type) -> Vec<String> {
+    let Type::Path(path) = typ else {
+        return Vec::new();
+    };
+    let (Some(first), Some(last)) = (path.path.segments.first(), path.path.segments.last()) else {
+        return Vec::new();
+    };
+
+    if first.ident == "Option" {
+        return match first.arguments {
+            syn::PathArguments::AngleBracketed(ref args) => match args.args.first() {
+                Some(GenericArgument::Type(inner)) => duration_serde_modules(inner)
+                    .into_iter()
+                    .map(|module| format!("{}::option", module))
+                    .collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+    }
+
+    let std = String::from("geekorm::utils::duration");
+    let chrono = String::from("geekorm::utils::duration::chrono");
+    match last.ident.to_string().as_str() {
+        "TimeDelta" => vec![chrono],
+        "Duration" if path.path.segments.len() == 1 => vec![std, chrono],
+        "Duration" if first.ident == "chrono" => vec![chrono],
+        "Duration" if first.ident != "time" => vec![std],
+        _ => Vec::new(),
+    }
+}
+
+/// Check if the type is a `serde_json::Value` (or a bare `Value` imported from `serde_json`)
+fn is_json_value(path: &syn::Path) -> bool {
+    match (path.segments.first(), path.segments.last()) {
+        (Some(first), Some(last)) => last.ident == "Value" && first.ident != "geekorm",
+        _ => false,
+    }
+}
+
+/// Get the `geekorm::utils::json` serde module which can deserialize the type
+///
+/// `None` if the type is not a `serde_json::Value`.
+pub(crate) fn json_serde_module(typ: &Type) -> Option<String> {
+    let Type::Path(path) = typ else {
+        return None;
+    };
+    let first = path.path.segments.first()?;
+    if first.ident == "Option" {
+        return match first.arguments {
+            syn::PathArguments::AngleBracketed(ref args) => match args.args.first() {
+                Some(GenericArgument::Type(inner)) => {
+                    json_serde_module(inner).map(|module| format!("{}::option", module))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+    }
+    is_json_value(&path.path).then(|| String::from("geekorm::utils::json"))
+}
+
 #[allow(unreachable_patterns, unused_variables, non_snake_case)]
 fn parse_path(typ: &Type, opts: ColumnTypeOptionsDerive) -> Result<ColumnTypeDerive, syn::Error> {
     match typ {
         Type::Slice(_) => Ok(ColumnTypeDerive::Text(ColumnTypeOptionsDerive::default())),
+        // Fixed-size byte arrays (e.g. `[u8; 16]`)
+        Type::Array(array) => match array.elem.as_ref() {
+            Type::Path(elem) if elem.path.is_ident("u8") => Ok(ColumnTypeDerive::Blob(opts)),
+            _ => Err(syn::Error::new_spanned(
+                array,
+                "Only byte arrays (`[u8; N]`) are supported as fixed-size array columns",
+            )),
+        },
         Type::Path(path) => {
             let ident = path.path.segments.first().unwrap().ident.clone();
 
             let ident_name = ident.to_string();
 
+            // Durations (`std::time::Duration` / `chrono::Duration`) are stored as seconds
+            let last_name = path.path.segments.last().unwrap().ident.to_string();
+            if matches!(last_name.as_str(), "Duration" | "TimeDelta") {
+                if ident_name == "time" {
+                    return Err(syn::Error::new_spanned(
+                        typ,
+                        "`time::Duration` columns are not supported, use `std::time::Duration`",
+                    ));
+                }
+                return Ok(ColumnTypeDerive::Integer(opts));
+            }
+
+            // Schemaless JSON is stored as a JSON document
+            if is_json_value(&path.path) {
+                return Ok(ColumnTypeDerive::Text(opts));
+            }
+
+            // `time` dates are matched on the full path as `Date` is a common name
+            #[cfg(feature = "time")]
+            {
+                let path_name = path
+                    .path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                if matches!(
+                    path_name.as_str(),
+                    "OffsetDateTime" | "time::OffsetDateTime" | "time::Date"
+                ) {
+                    return Ok(ColumnTypeDerive::Text(opts));
+                }
+            }
+
             match ident_name.as_str() {
                 // GeekORM types
                 "PrimaryKey" => {
@@ -134,6 +288,13 @@ fn parse_path(typ: &Type, opts: ColumnTypeOptionsDerive) -> Result<ColumnTypeDer
                         _ => panic!("Unsupported PrimaryKey type"),
                     };
 
+                    // String and Uuid keys are stored as `TEXT PRIMARY KEY`
+                    if matches!(inner_type_name.as_str(), "String" | "Uuid") {
+                        return Ok(ColumnTypeDerive::Text(
+                            ColumnTypeOptionsDerive::primary_key_text(),
+                        ));
+                    }
+
                     Ok(ColumnTypeDerive::Identifier(ColumnTypeOptionsDerive {
                         primary_key: true,
                         foreign_key: String::new(),
@@ -141,36 +302,34 @@ fn parse_path(typ: &Type, opts: ColumnTypeOptionsDerive) -> Result<ColumnTypeDer
                         not_null: false,
                         // If the inner type is an integer, auto increment
                         auto_increment: inner_type_name == "Integer",
+                        ..Default::default()
                     }))
                 }
-                "PrimaryKeyString" | "PrimaryKeyUuid" => {
-                    Ok(ColumnTypeDerive::Identifier(ColumnTypeOptionsDerive {
-                        primary_key: true,
-                        foreign_key: String::new(),
-                        unique: false,
-                        not_null: false,
-                        auto_increment: false,
-                    }))
-                }
+                "PrimaryKeyString" | "PrimaryKeyUuid" => Ok(ColumnTypeDerive::Text(
+                    ColumnTypeOptionsDerive::primary_key_text(),
+                )),
                 "PrimaryKeyInteger" => Ok(ColumnTypeDerive::Identifier(ColumnTypeOptionsDerive {
                     primary_key: true,
                     foreign_key: String::new(),
                     unique: false,
                     not_null: false,
                     auto_increment: true,
+                    ..Default::default()
                 })),
                 "ForeignKey" => {
                     let options = ColumnTypeOptionsDerive {
                         primary_key: false,
                         foreign_key: String::from("GeekOrmForeignKey"),
                         unique: false,
-                        not_null: true,
+                        // `Option<ForeignKey<T, D>>` is nullable
+                        not_null: opts.not_null,
                         auto_increment: false,
+                        ..Default::default()
                     };
                     Ok(ColumnTypeDerive::ForeignKey(options))
                 }
                 // Data types
-                "String" => Ok(ColumnTypeDerive::Text(opts)),
+                "String" | "char" => Ok(ColumnTypeDerive::Text(opts)),
                 "i32" | "i64" | "u32" | "u64" => Ok(ColumnTypeDerive::Integer(opts)),
                 "bool" => Ok(ColumnTypeDerive::Boolean(opts)),
                 "Option" => {
@@ -195,7 +354,9 @@ fn parse_path(typ: &Type, opts: ColumnTypeOptionsDerive) -> Result<ColumnTypeDer
                 #[cfg(feature = "uuid")]
                 "Uuid" => Ok(ColumnTypeDerive::Text(opts)),
                 #[cfg(feature = "chrono")]
-                "chrono" | "DateTime" => Ok(ColumnTypeDerive::Text(opts)),
+                "chrono" | "DateTime" | "NaiveDate" | "NaiveDateTime" | "NaiveTime" => {
+                    Ok(ColumnTypeDerive::Text(opts))
+                }
                 _ => Ok(ColumnTypeDerive::Blob(opts)),
             }
         }
@@ -215,6 +376,14 @@ pub(crate) struct ColumnTypeOptionsDerive {
     pub(crate) not_null: bool,
     /// Column is auto increment
     pub(crate) auto_increment: bool,
+    /// Column collating sequence
+    pub(crate) collation: Option<String>,
+    /// Minimum length of the column value
+    pub(crate) min_length: Option<usize>,
+    /// Maximum length of the column value
+    pub(crate) max_length: Option<usize>,
+    /// Create an index on the column
+    pub(crate) index: bool,
 }
 
 impl ColumnTypeOptionsDerive {
@@ -230,6 +399,25 @@ impl ColumnTypeOptionsDerive {
     pub fn set_auto_increment(&mut self, auto_increment: bool) {
         self.auto_increment = auto_increment;
     }
+    /// Options for a `TEXT PRIMARY KEY` (String / Uuid keys)
+    ///
+    /// `AUTOINCREMENT` is only supported on integer primary keys.
+    pub fn primary_key_text() -> Self {
+        ColumnTypeOptionsDerive {
+            primary_key: true,
+            not_null: false,
+            auto_increment: false,
+            ..Default::default()
+        }
+    }
+    /// Set Collation
+    pub fn set_collation(&mut self, collation: String) {
+        self.collation = Some(collation);
+    }
+    /// Set Index
+    pub fn set_index(&mut self, index: bool) {
+        self.index = index;
+    }
 }
 
 impl Default for ColumnTypeOptionsDerive {
@@ -240,6 +428,10 @@ impl Default for ColumnTypeOptionsDerive {
             not_null: true,
             foreign_key: String::new(),
             auto_increment: false,
+            collation: None,
+            min_length: None,
+            max_length: None,
+            index: false,
         }
     }
 }
@@ -251,6 +443,19 @@ impl ToTokens for ColumnTypeOptionsDerive {
         let unique = &self.unique;
         let not_null = &self.not_null;
         let auto_increment = &self.auto_increment;
+        let index = &self.index;
+        let collation = match &self.collation {
+            Some(collation) => quote! { Some(String::from(#collation)) },
+            None => quote! { None },
+        };
+        let min_length = match &self.min_length {
+            Some(min) => quote! { Some(#min) },
+            None => quote! { None },
+        };
+        let max_length = match &self.max_length {
+            Some(max) => quote! { Some(#max) },
+            None => quote! { None },
+        };
 
         tokens.extend(quote! {
             geekorm::ColumnTypeOptions {
@@ -259,6 +464,10 @@ impl ToTokens for ColumnTypeOptionsDerive {
                 not_null: #not_null,
                 foreign_key: String::from(#foreign_key),
                 auto_increment: #auto_increment,
+                collation: #collation,
+                min_length: #min_length,
+                max_length: #max_length,
+                index: #index,
             }
         });
     }
@@ -272,6 +481,10 @@ impl From<ColumnTypeOptionsDerive> for geekorm_core::ColumnTypeOptions {
             unique: opts.unique,
             not_null: opts.not_null,
             auto_increment: opts.auto_increment,
+            collation: opts.collation,
+            min_length: opts.min_length,
+            max_length: opts.max_length,
+            index: opts.index,
         }
     }
 }
@@ -15,6 +15,8 @@ use geekorm_core::{PrimaryKey, Table};
 pub(crate) struct TableDerive {
     pub name: String,
     pub columns: ColumnsDerive,
+    /// Async hook called before the row is saved (function path)
+    pub before_save: Option<String>,
 }
 
 impl TableDerive {
@@ -30,6 +32,11 @@ impl TableDerive {
                             }
                         }
                     }
+                    GeekAttributeKeys::BeforeSave => {
+                        if let Some(GeekAttributeValue::String(path)) = &attr.value {
+                            self.before_save = Some(path.to_string());
+                        }
+                    }
                     _ => {}
                 }
             } else {
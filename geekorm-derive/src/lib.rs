@@ -115,3 +115,75 @@ pub fn data_derive(input: TokenStream) -> TokenStream {
 pub fn depricated_value_derive(input: TokenStream) -> TokenStream {
     data_derive(input)
 }
+
+/// Collect the tables derived with `#[derive(Table)]` into a `geekorm::Database`.
+///
+/// Without arguments every table registered by the derive is returned, so tables
+/// from any module (and any crate linked into the binary) are included and sorted
+/// by name. Generic tables are not registered. Tables can also be passed as type
+/// paths to only collect those tables.
+///
+/// ```rust
+/// mod users {
+///     use geekorm::prelude::*;
+///
+///     #[derive(Table, Clone, Default, serde::Serialize, serde::Deserialize)]
+///     pub struct Users {
+///         #[geekorm(primary_key, auto_increment)]
+///         pub id: PrimaryKeyInteger,
+///         pub username: String,
+///     }
+/// }
+///
+/// mod posts {
+///     use geekorm::prelude::*;
+///     use super::users::Users;
+///
+///     #[derive(Table, Clone, Default, serde::Serialize, serde::Deserialize)]
+///     pub struct Posts {
+///         #[geekorm(primary_key, auto_increment)]
+///         pub id: PrimaryKeyInteger,
+///         pub title: String,
+///         #[geekorm(foreign_key = "Users.id")]
+///         pub author: ForeignKey<i32, Users>,
+///     }
+/// }
+///
+/// # fn main() {
+/// // Every derived table
+/// let database = geekorm::tables!();
+/// # assert_eq!(database.tables.len(), 2);
+/// assert!(database.get_table("Users").is_some());
+/// assert!(database.get_table("Posts").is_some());
+///
+/// // Only the given tables
+/// let database = geekorm::tables!(users::Users);
+/// # assert_eq!(database.tables.len(), 1);
+/// # }
+/// ```
+#[proc_macro]
+pub fn tables(input: TokenStream) -> TokenStream {
+    let paths = match syn::parse::Parser::parse(
+        syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+        input,
+    ) {
+        Ok(paths) => paths,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    if paths.is_empty() {
+        return quote! {
+            geekorm::Database::registered()
+        }
+        .into();
+    }
+    let tables = paths.iter();
+
+    quote! {
+        geekorm::Database {
+            tables: vec![
+                #(<#tables as geekorm::TableBuilder>::table()),*
+            ],
+        }
+    }
+    .into()
+}
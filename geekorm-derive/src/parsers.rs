@@ -8,7 +8,9 @@ use syn::{
 #[cfg(feature = "rand")]
 use geekorm_core::utils::generate_random_string;
 use geekorm_core::{Columns, Table};
-use values::{generate_from_value, generate_serde, generate_strings};
+use values::{
+    enum_int_repr, generate_from_value, generate_serde, generate_strings, generate_variants,
+};
 
 mod helpers;
 mod tablebuilder;
@@ -48,6 +50,7 @@ pub(crate) fn derive_parser(ast: &DeriveInput) -> Result<TokenStream, syn::Error
             let mut table = TableDerive {
                 name: name.to_string(),
                 columns: ColumnsDerive::from(columns),
+                before_save: None,
             };
             table.apply_attributes(&attributes);
 
@@ -80,14 +83,23 @@ pub(crate) fn enum_parser(ast: &DeriveInput) -> Result<TokenStream, syn::Error>
         Data::Enum(DataEnum { variants, .. }) => {
             let mut tokens = TokenStream::new();
 
-            tokens.extend(generate_from_value(name, variants, &ast.generics)?);
-            tokens.extend(generate_serde(name, variants, &ast.generics)?);
+            let int_repr = enum_int_repr(name, &ast.attrs, &attributes)?;
+
+            tokens.extend(generate_from_value(
+                name,
+                variants,
+                &ast.generics,
+                int_repr,
+            )?);
+            tokens.extend(generate_serde(name, variants, &ast.generics, int_repr)?);
             tokens.extend(generate_strings(
                 name,
                 variants,
                 &ast.generics,
                 &attributes,
+                int_repr,
             )?);
+            tokens.extend(generate_variants(name, variants)?);
 
             Ok(tokens)
         }
@@ -8,7 +8,10 @@ use syn::{
     GenericArgument, Type, TypePath,
 };
 
-use crate::{derive::TableDerive, internal::TableState};
+use crate::{
+    derive::{ColumnDerive, TableDerive},
+    internal::TableState,
+};
 
 /// Generate implementation of `TableBuilder` trait for the struct.
 ///
@@ -36,6 +39,51 @@ pub fn generate_table_builder(
 ) -> Result<TokenStream, syn::Error> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    // Register the table for `tables!()` (generic tables can't be registered)
+    let registration = if generics.params.is_empty() {
+        quote! {
+            geekorm::inventory::submit! {
+                geekorm::TableRegistration::new(<#ident as geekorm::TableBuilder>::table)
+            }
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    // Validate the column constraints before writing
+    let mut validate_values = TokenStream::new();
+    for column in table.columns.columns.iter() {
+        if column.skip {
+            continue;
+        }
+        let name = &column.name;
+        let ident = syn::Ident::new(name.as_str(), name.span());
+
+        let options = column.coltype.options();
+        if let Some(min) = options.min_length {
+            validate_values.extend(quote! {
+                if let geekorm::Value::Text(value) = geekorm::Value::from(&self.#ident) {
+                    if value.chars().count() < #min {
+                        return Err(geekorm::Error::ValidationError(format!(
+                            "`{}` must be at least {} characters", #name, #min
+                        )));
+                    }
+                }
+            });
+        }
+        if let Some(max) = options.max_length {
+            validate_values.extend(quote! {
+                if let geekorm::Value::Text(value) = geekorm::Value::from(&self.#ident) {
+                    if value.chars().count() > #max {
+                        return Err(geekorm::Error::ValidationError(format!(
+                            "`{}` must be at most {} characters", #name, #max
+                        )));
+                    }
+                }
+            });
+        }
+    }
+
     Ok(quote! {
         impl #impl_generics geekorm::prelude::TableBuilder for #ident #ty_generics #where_clause {
             /// Get the table instance.
@@ -50,7 +98,14 @@ pub fn generate_table_builder(
             fn table_name() -> String {
                 stringify!(#ident).to_string()
             }
+            /// Validate the column constraints of the item.
+            fn validate(&self) -> Result<(), geekorm::Error> {
+                #validate_values
+                Ok(())
+            }
         }
+
+        #registration
     })
 }
 
@@ -95,17 +150,7 @@ pub fn generate_query_builder(
 ) -> Result<TokenStream, syn::Error> {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let mut insert_values = TokenStream::new();
-    for column in table.columns.columns.iter() {
-        if column.skip {
-            continue;
-        }
-        let name = &column.name;
-        let ident = syn::Ident::new(name.as_str(), name.span());
-        insert_values.extend(quote! {
-            .add_value(#name, &item.#ident)
-        });
-    }
+    let insert_values = write_values(table, &quote! { item });
 
     Ok(quote! {
         impl #impl_generics geekorm::prelude::QueryBuilderTrait for #ident #ty_generics #where_clause {
@@ -127,6 +172,23 @@ pub fn generate_query_builder(
                     .build()
                     .expect("Failed to build insert query")
             }
+            /// Insert (or ignore) query.
+            fn query_insert_or_ignore(item: &Self) -> geekorm::Query {
+                geekorm::QueryBuilder::insert_or_ignore()
+                    .table(#ident::table())
+                    #insert_values
+                    .build()
+                    .expect("Failed to build insert or ignore query")
+            }
+            /// Insert query returning the inserted row.
+            fn query_insert_returning(item: &Self) -> geekorm::Query {
+                geekorm::QueryBuilder::insert()
+                    .table(#ident::table())
+                    #insert_values
+                    .returning_all()
+                    .build()
+                    .expect("Failed to build insert returning query")
+            }
             /// Update query.
             fn query_update(item: &Self) -> geekorm::Query {
                 geekorm::QueryBuilder::update()
@@ -153,6 +215,41 @@ pub fn generate_query_builder(
     })
 }
 
+/// Generate the `add_value` calls for the written (not skipped or read-only) columns
+///
+/// `item` is the expression of the struct the values are read from.
+fn write_values(table: &TableDerive, item: &TokenStream) -> TokenStream {
+    let mut values = TokenStream::new();
+    for column in table.columns.columns.iter() {
+        // Skipped and read-only columns are never written
+        if column.skip || column.readonly {
+            continue;
+        }
+        let name = &column.name;
+        let ident = syn::Ident::new(name.as_str(), name.span());
+        // Typed blobs are encoded using `ToBlob`
+        let value = if column.blob {
+            quote! { geekorm::utils::blob::to_value(&#item.#ident) }
+        } else {
+            quote! { geekorm::Value::from(&#item.#ident) }
+        };
+        if column.compress {
+            values.extend(quote! {
+                .try_add_value(#name, geekorm::utils::compression::compress_value(#value))
+            });
+        } else if column.blob {
+            values.extend(quote! {
+                .add_value(#name, #value)
+            });
+        } else {
+            values.extend(quote! {
+                .add_value(#name, &#item.#ident)
+            });
+        }
+    }
+    values
+}
+
 /// Generate implementation of `TablePrimaryKey` for the struct.
 ///
 /// ```rust
@@ -169,6 +266,7 @@ pub fn generate_query_builder(
 ///
 /// # assert_eq!(Users::primary_key(), "id");
 /// # assert_eq!(Users::primary_key_value(&user), Value::Identifier(0));
+/// # assert!(!user.primary_key_is_set());
 /// ```
 pub fn generate_table_primary_key(
     ident: &syn::Ident,
@@ -182,6 +280,19 @@ pub fn generate_table_primary_key(
 
         let identifier = syn::Ident::new(name.as_str(), name.span());
 
+        // `PrimaryKey` fields know if they have been set, other key types
+        // use the default (value based) check
+        let is_set = if key.is_primary_key_type() {
+            quote! {
+                /// Check if the primary key has been set.
+                fn primary_key_is_set(&self) -> bool {
+                    self.#identifier.is_set()
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         Ok(quote! {
             impl #impl_generics geekorm::prelude::TablePrimaryKey for #ident #ty_generics #where_clause {
                 /// Get the primary key of the table.
@@ -193,6 +304,8 @@ pub fn generate_table_primary_key(
                 fn primary_key_value(&self) -> geekorm::Value {
                     geekorm::Value::from(&self.#identifier)
                 }
+
+                #is_set
             }
         })
     } else {
@@ -206,7 +319,7 @@ pub fn generate_table_primary_key(
 /// Generate the backend implementation for the struct and fetch methods.
 ///
 /// - `fetch_by_primary_key()` - Gets an item by the primary key.
-/// - `fetch_by_{field}()` - Gets an item by the field.
+/// - `fetch_by_{field}()` - Gets an item by the field (foreign keys and `Option<T>` use the inner value).
 /// - `fetch_{field}()` - Fetch foreign key items.
 #[allow(dead_code)]
 pub fn generate_backend(
@@ -225,6 +338,13 @@ pub fn generate_backend(
     let mut fetch_functions = TokenStream::new();
     // Auto Update fields
     let mut auto_update = TokenStream::new();
+    // Values of the auto update fields before the update (restored on a failed versioned update)
+    let mut auto_update_snapshot = TokenStream::new();
+    let mut auto_update_restore = TokenStream::new();
+    // Auto Save fields
+    let mut auto_save = TokenStream::new();
+    // Values for the auto update fields (used by `touch`)
+    let mut touch_values = TokenStream::new();
     // Stream of where clauses
     let mut where_previous = false;
     let mut where_clauses = TokenStream::new();
@@ -242,23 +362,47 @@ pub fn generate_backend(
         let ident = syn::Ident::new(name.as_str(), name.span());
 
         // TODO(geekmasher): This clone isn't ideal, but it's the only way to get this to work.
-        insert_values.extend(quote! {
-            self.#ident = item.#ident.clone();
-        });
+        if column.is_foreign_key() {
+            // Keep the foreign key data already loaded in memory, only the key is
+            // read back from the database
+            if column.is_optional() {
+                insert_values.extend(quote! {
+                    self.#ident = match (self.#ident.take(), item.#ident.clone()) {
+                        (Some(mut fk), Some(saved)) => {
+                            fk.key = saved.key;
+                            Some(fk)
+                        }
+                        (_, saved) => saved,
+                    };
+                });
+            } else {
+                insert_values.extend(quote! {
+                    self.#ident.key = item.#ident.key.clone();
+                });
+            }
+        } else {
+            insert_values.extend(quote! {
+                self.#ident = item.#ident.clone();
+            });
+        }
 
         if let Some(update) = &column.update {
             // self.updated = chrono::Utc::now();
-
-            let auto = syn::parse_str::<TokenStream>(update).map_err(|err| {
-                syn::Error::new(
-                    column.span(),
-                    format!("Failed to parse data for New mode: {}", err),
-                )
-            })?;
-
-            auto_update.extend(quote! {
-                self.#ident = #auto;
+            auto_update.extend(on_write(column, &ident, update)?);
+            let target = on_write_target(column, &ident, update)?;
+            let previous = Ident::new(&format!("previous_{}", ident), Span::call_site());
+            auto_update_snapshot.extend(quote! {
+                let #previous = (#target).clone();
+            });
+            auto_update_restore.extend(quote! {
+                #target = #previous;
             });
+            touch_values.extend(quote! {
+                .add_value(#name, &self.#ident)
+            });
+        }
+        if let Some(save) = &column.save {
+            auto_save.extend(on_write(column, &ident, save)?);
         }
 
         if column.is_searchable() {
@@ -274,7 +418,11 @@ pub fn generate_backend(
             where_previous = true;
         }
 
-        if column.is_unique() {
+        if column.is_unique() && column.blob {
+            unique_where.extend(quote! {
+                .where_eq(stringify!(#ident), geekorm::utils::blob::to_value(&self.#ident))
+            });
+        } else if column.is_unique() {
             unique_where.extend(quote! {
                 .where_eq(stringify!(#ident), &self.#ident)
             });
@@ -289,7 +437,7 @@ pub fn generate_backend(
 
             // Inner type of the field
             // ForeignKey<i32, Users>,
-            let field_type = match &field.ty {
+            let mut field_type = match &field.ty {
                 syn::Type::Path(path) => path.path.segments.first().unwrap(),
                 _ => {
                     return Err(syn::Error::new(
@@ -299,6 +447,30 @@ pub fn generate_backend(
                 }
             };
 
+            // Option<ForeignKey<i32, Users>>
+            let optional = field_type.ident == "Option";
+            if optional {
+                field_type = match &field_type.arguments {
+                    syn::PathArguments::AngleBracketed(args) => match args.args.first() {
+                        Some(syn::GenericArgument::Type(Type::Path(path))) => {
+                            path.path.segments.first().unwrap()
+                        }
+                        _ => {
+                            return Err(syn::Error::new(
+                                field.ty.span(),
+                                "Only path types are supported for optional foreign keys",
+                            ))
+                        }
+                    },
+                    _ => {
+                        return Err(syn::Error::new(
+                            field.ty.span(),
+                            "Only angle bracketed arguments are supported for foreign keys",
+                        ))
+                    }
+                };
+            }
+
             let inner_type = match &field_type.arguments {
                 syn::PathArguments::AngleBracketed(args) => args.args.last().unwrap(),
                 _ => {
@@ -312,7 +484,11 @@ pub fn generate_backend(
             match inner_type {
                 syn::GenericArgument::Type(Type::Path(path)) => {
                     let fident = path.path.segments.first().unwrap().ident.clone();
-                    fetch_impl.extend(column.get_fetcher(&ident, &fident));
+                    if optional {
+                        fetch_impl.extend(column.get_fetcher_optional(&ident, &fident));
+                    } else {
+                        fetch_impl.extend(column.get_fetcher(&ident, &fident));
+                    }
 
                     // Add fetch function to the list of fetch functions
                     let func_name = format!("fetch_{}", column.identifier);
@@ -373,6 +549,138 @@ pub fn generate_backend(
         }
     }
 
+    // Async hook called before the row is saved
+    let before_save = match &table.before_save {
+        Some(path) => {
+            let path = syn::parse_str::<syn::Path>(path)?;
+            quote! { #path(self, connection).await?; }
+        }
+        None => TokenStream::new(),
+    };
+
+    // Optimistic concurrency: bump the version column and only update the row
+    // if the version in the database is still the one we loaded.
+    let version = table.columns.columns.iter().find(|c| c.version && !c.skip);
+    let (version_bump, version_restore, version_where) = match version {
+        Some(version) => {
+            let vname = &version.name;
+            let vident = syn::Ident::new(vname.as_str(), vname.span());
+            (
+                quote! {
+                    let expected = self.#vident;
+                    self.#vident = expected + 1;
+                },
+                quote! {
+                    self.#vident = expected;
+                    #auto_update_restore
+                },
+                quote! { .where_eq(#vname, expected) },
+            )
+        }
+        None => (TokenStream::new(), TokenStream::new(), TokenStream::new()),
+    };
+    // The auto update fields are only restored if the version check can fail
+    let (auto_update_snapshot, validate) = match version {
+        Some(_) => (
+            auto_update_snapshot,
+            quote! {
+                if let Err(err) = <Self as geekorm::TableBuilder>::validate(self) {
+                    #auto_update_restore
+                    return Err(err);
+                }
+            },
+        ),
+        None => (
+            TokenStream::new(),
+            quote! { <Self as geekorm::TableBuilder>::validate(self)?; },
+        ),
+    };
+    // Run the update query built from `builder` (checking the version of the row)
+    let run_update = |builder: TokenStream| match version {
+        Some(_) => quote! {
+            #version_bump
+            let result = match #builder #version_where .build() {
+                Ok(query) => T::execute_affected(connection, query).await,
+                Err(err) => Err(err),
+            };
+            let affected = match result {
+                Ok(affected) => affected,
+                Err(err) => {
+                    #version_restore
+                    return Err(err);
+                }
+            };
+            if affected == 0 {
+                #version_restore
+                return Err(geekorm::Error::StaleUpdate {
+                    table: String::from(stringify!(#ident)),
+                    version: expected as i64,
+                });
+            }
+        },
+        None => quote! {
+            T::execute(connection, #builder.build()?).await?;
+        },
+    };
+
+    let touch = if touch_values.is_empty() {
+        quote! {
+            Err(geekorm::Error::QueryBuilderError(
+                format!("Table `{}` has no `on_update` columns to touch", stringify!(#ident)),
+                String::from("touch"),
+            ))
+        }
+    } else {
+        if let Some(version) = version {
+            let vname = &version.name;
+            let vident = syn::Ident::new(vname.as_str(), vname.span());
+            touch_values.extend(quote! {
+                .add_value(#vname, &self.#vident)
+            });
+        }
+        let touched = run_update(quote! {
+            geekorm::QueryBuilder::update()
+                .table(#ident::table())
+                .add_value(#ident::primary_key().as_str(), self.primary_key_value())
+                #touch_values
+        });
+        quote! {
+            #auto_update_snapshot
+            #auto_update
+            #touched
+            Ok(())
+        }
+    };
+
+    let self_values = write_values(table, &quote! { self });
+
+    let (update, update_affected) = if version.is_some() {
+        let updated = run_update(quote! {
+            geekorm::QueryBuilder::update()
+                .table(#ident::table())
+                #self_values
+        });
+        (
+            quote! {
+                #updated
+                Ok(())
+            },
+            quote! {
+                #updated
+                Ok(affected)
+            },
+        )
+    } else {
+        (
+            quote! {
+                T::execute(connection, Self::query_update(self)).await
+            },
+            quote! {
+                T::execute_affected(connection, Self::query_update(self)).await
+            },
+        )
+    };
+
     // GeekConnector implementation
     stream.extend(quote! {
         #[automatically_derived]
@@ -384,23 +692,61 @@ pub fn generate_backend(
             #[allow(async_fn_in_trait, unused_variables)]
             async fn save(&mut self, connection: &'a T) -> Result<(), geekorm::Error>
             {
-                T::execute(connection, Self::query_insert(self)).await?;
-                let select_query = #ident::query_select()
-                    .order_by(#ident::primary_key().as_str(), geekorm::QueryOrder::Desc)
-                    .limit(1)
-                    .build()?;
-
-                let item: #ident = T::query_first::<Self>(connection, select_query).await?;
+                #before_save
+                #auto_save
+                <Self as geekorm::TableBuilder>::validate(self)?;
+                // The inserted row (including the columns set by the database)
+                let item: #ident =
+                    T::query_first::<Self>(connection, Self::query_insert_returning(self)).await?;
 
                 #insert_values
                 Ok(())
             }
 
+            /// Save a new item to the database unless it conflicts with an existing row.
+            #[allow(async_fn_in_trait, unused_variables)]
+            async fn save_ignore(&mut self, connection: &'a T) -> Result<bool, geekorm::Error>
+            {
+                #before_save
+                #auto_save
+                <Self as geekorm::TableBuilder>::validate(self)?;
+                let query = geekorm::QueryBuilder::insert_or_ignore()
+                    .table(#ident::table())
+                    #self_values
+                    .returning_all()
+                    .build()?;
+                // An ignored insert returns no rows
+                let item: #ident = match T::query::<Self>(connection, query).await?.into_iter().next() {
+                    Some(item) => item,
+                    None => return Ok(false),
+                };
+
+                #insert_values
+                Ok(true)
+            }
+
             /// Update the item in the database.
             #[allow(async_fn_in_trait, unused_variables)]
             async fn update(&mut self, connection: &'a T) -> Result<(), geekorm::Error> {
+                #auto_update_snapshot
                 #auto_update
-                T::execute(connection, Self::query_update(self)).await
+                #validate
+                #update
+            }
+
+            /// Update only the `on_update` columns of the item in the database.
+            #[allow(async_fn_in_trait, unused_variables)]
+            async fn touch(&mut self, connection: &'a T) -> Result<(), geekorm::Error> {
+                #touch
+            }
+
+            /// Update the item in the database and return the number of rows affected.
+            #[allow(async_fn_in_trait, unused_variables)]
+            async fn update_affected(&mut self, connection: &'a T) -> Result<u64, geekorm::Error> {
+                #auto_update_snapshot
+                #auto_update
+                #validate
+                #update_affected
             }
 
             /// Fetch all the data from foreign tables and store them in the struct.
@@ -417,6 +763,17 @@ pub fn generate_backend(
                 &mut self,
                 connection: &'a T,
             ) -> Result<(), geekorm::Error>
+            {
+                self.refresh_or_insert(connection).await?;
+                Ok(())
+            }
+
+            /// Fetch or create a row in the database and return if it was created
+            #[allow(async_fn_in_trait, unused_variables)]
+            async fn refresh_or_insert(
+                &mut self,
+                connection: &'a T,
+            ) -> Result<geekorm::FetchOrCreate, geekorm::Error>
             {
                 let query = Self::query_select()
                     #unique_where
@@ -425,12 +782,13 @@ pub fn generate_backend(
                 match T::query_first::<Self>(connection, query).await {
                     Ok(item) => {
                         *self = item;
+                        Ok(geekorm::FetchOrCreate::Existing)
                     },
                     Err(_) => {
                         self.save(connection).await?;
+                        Ok(geekorm::FetchOrCreate::Created)
                     }
                 }
-                Ok(())
             }
 
             #[allow(async_fn_in_trait, unused_variables)]
@@ -440,13 +798,13 @@ pub fn generate_backend(
             ) -> Result<Vec<Self>, geekorm::Error>
             {
                 let search = search.into();
-                Ok(T::query::<Self>(
+                T::query::<Self>(
                     connection,
                     geekorm::QueryBuilder::select()
                         .table(Self::table())
                         #where_clauses
                         .build()?
-                ).await?)
+                ).await
             }
         }
     });
@@ -468,3 +826,66 @@ pub fn generate_backend(
 
     Ok(stream)
 }
+
+/// The expression written by an `on_save` / `on_update` attribute
+fn on_write_target(
+    column: &ColumnDerive,
+    ident: &syn::Ident,
+    value: &str,
+) -> Result<TokenStream, syn::Error> {
+    Ok(match syn::parse_str::<syn::Expr>(value) {
+        // The left hand side of the assignment (everything before the `=`)
+        Ok(syn::Expr::Assign(assign)) => {
+            let mut previous_joint = false;
+            assign
+                .to_token_stream()
+                .into_iter()
+                .take_while(|token| match token {
+                    proc_macro2::TokenTree::Punct(punct) => {
+                        let assign_token = punct.as_char() == '='
+                            && punct.spacing() == proc_macro2::Spacing::Alone
+                            && !previous_joint;
+                        previous_joint = punct.spacing() == proc_macro2::Spacing::Joint;
+                        !assign_token
+                    }
+                    _ => {
+                        previous_joint = false;
+                        true
+                    }
+                })
+                .collect()
+        }
+        Ok(_) => quote! { self.#ident },
+        Err(err) => {
+            return Err(syn::Error::new(
+                column.span(),
+                format!("Failed to parse on_save / on_update expression: {}", err),
+            ))
+        }
+    })
+}
+
+/// Generate the statement for an `on_save` / `on_update` attribute
+///
+/// The value is either an expression assigned to the field
+/// (`on_update = "chrono::Utc::now()"`) or a full assignment
+/// (`on_save = "self.slug = slugify(&self.title)?"`).
+/// Both are run inside the generated async function, so `?` can be used
+/// for fallible expressions.
+fn on_write(
+    column: &ColumnDerive,
+    ident: &syn::Ident,
+    value: &str,
+) -> Result<TokenStream, syn::Error> {
+    let expr = syn::parse_str::<syn::Expr>(value).map_err(|err| {
+        syn::Error::new(
+            column.span(),
+            format!("Failed to parse on_save / on_update expression: {}", err),
+        )
+    })?;
+
+    Ok(match expr {
+        syn::Expr::Assign(assign) => quote! { #assign; },
+        expr => quote! { self.#ident = #expr; },
+    })
+}
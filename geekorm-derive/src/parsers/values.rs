@@ -3,37 +3,163 @@ use quote::quote;
 use syn::spanned::Spanned;
 
 use super::GeekAttribute;
-use crate::attr::GeekAttributeValue;
-
-// impl From<&UserRole> for geekorm::Value {
-//     fn from(value: &UserRole) -> Self {
-//         match value {
-//             UserRole::Admin => geekorm::Value::Integer(0),
-//             UserRole::User => geekorm::Value::Integer(1),
-//         }
-//     }
-// }
+use crate::attr::{GeekAttributeKeys, GeekAttributeValue};
+
+/// Check if the enum is stored as an integer
+///
+/// Integer storage is opt-in with `#[geekorm(integer)]` and requires an
+/// integer `#[repr(...)]`. These enums are stored as `Value::Integer` using
+/// the discriminant of each variant instead of the variant name.
+pub(crate) fn enum_int_repr(
+    ident: &syn::Ident,
+    attrs: &[syn::Attribute],
+    attributes: &[GeekAttribute],
+) -> Result<bool, syn::Error> {
+    const INT_TYPES: [&str; 12] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+
+    let Some(attr) = attributes
+        .iter()
+        .find(|attr| attr.key == Some(GeekAttributeKeys::Integer))
+    else {
+        return Ok(false);
+    };
+
+    let mut int_repr = false;
+    for repr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let _ = repr.parse_nested_meta(|meta| {
+            if INT_TYPES.iter().any(|typ| meta.path.is_ident(typ)) {
+                int_repr = true;
+            }
+            Ok(())
+        });
+    }
+    if !int_repr {
+        return Err(syn::Error::new(
+            attr.span.span(),
+            format!(
+                "The `integer` attribute requires an integer `#[repr(...)]` on `{}`",
+                ident
+            ),
+        ));
+    }
+    Ok(true)
+}
+
+/// Get the discriminant for each variant of an integer backed enum
+///
+/// Variants without an explicit discriminant follow on from the previous
+/// variant (starting at `0`), the same way Rust assigns them.
+pub(crate) fn enum_discriminants(
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> Result<Vec<i64>, syn::Error> {
+    let mut discriminants = Vec::new();
+    let mut next: i64 = 0;
+
+    for variant in variants {
+        if let Some((_, expr)) = &variant.discriminant {
+            next = match expr {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(lit),
+                    ..
+                }) => lit.base10_parse::<i64>()?,
+                syn::Expr::Unary(syn::ExprUnary {
+                    op: syn::UnOp::Neg(_),
+                    expr,
+                    ..
+                }) => match expr.as_ref() {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(lit),
+                        ..
+                    }) => -lit.base10_parse::<i64>()?,
+                    _ => {
+                        return Err(syn::Error::new(
+                            expr.span(),
+                            "Only integer literal discriminants are supported",
+                        ))
+                    }
+                },
+                _ => {
+                    return Err(syn::Error::new(
+                        expr.span(),
+                        "Only integer literal discriminants are supported",
+                    ))
+                }
+            };
+        }
+        discriminants.push(next);
+        next += 1;
+    }
+    Ok(discriminants)
+}
+
+/// Generate the conversions between the enum and `geekorm::Value`
+///
+/// By default the variants are stored as `Value::Text`. If the enum has
+/// `#[geekorm(integer)]` (and an integer `#[repr(...)]`), the variants are
+/// stored as `Value::Integer` using their discriminant.
+///
+/// ```rust
+/// # use geekorm::prelude::*;
+///
+/// # #[derive(Eq, PartialEq, Debug)]
+/// #[derive(Data, Default, Clone)]
+/// #[geekorm(integer)]
+/// #[repr(u8)]
+/// enum Priority {
+///     #[default]
+///     Low,
+///     Medium,
+///     High = 10,
+///     Critical,
+/// }
+///
+/// let value = Value::from(Priority::High);
+/// # assert_eq!(value, Value::Integer(10));
+/// # assert_eq!(Value::from(Priority::Low), Value::Integer(0));
+/// # assert_eq!(Value::from(Priority::Critical), Value::Integer(11));
+///
+/// // Integers read back from the database map to the variant
+/// let priority = Priority::from(value);
+/// # assert_eq!(priority, Priority::High);
+/// # assert_eq!(Priority::from(Value::Integer(1)), Priority::Medium);
+/// # assert_eq!(Priority::try_from(11_i64).unwrap(), Priority::Critical);
+/// # assert!(Priority::try_from(42_i64).is_err());
+///
+/// // The variant names are still used for strings
+/// # assert_eq!(Priority::High.to_string(), "High");
+/// # assert_eq!(Priority::from("Medium"), Priority::Medium);
+/// ```
 pub(crate) fn generate_from_value(
     ident: &syn::Ident,
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
     generics: &syn::Generics,
+    int_repr: bool,
 ) -> Result<TokenStream, syn::Error> {
     let (impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
 
     let mut stream = TokenStream::new();
     let mut from_value_stream = TokenStream::new();
+    let mut from_int_stream = TokenStream::new();
 
-    for variant in variants {
+    let discriminants = if int_repr {
+        enum_discriminants(variants)?
+    } else {
+        Vec::new()
+    };
+
+    for (index, variant) in variants.iter().enumerate() {
         if !matches!(variant.fields, syn::Fields::Unit) {
             return Err(syn::Error::new(
                 variant.span(),
                 "Only unit variants are supported",
             ));
         }
-        if variant.discriminant.is_some() {
+        if variant.discriminant.is_some() && !int_repr {
             return Err(syn::Error::new(
                 variant.span(),
-                "Discriminant values are not supported",
+                "Discriminant values are only supported with `#[geekorm(integer)]`",
             ));
         }
 
@@ -62,15 +188,47 @@ pub(crate) fn generate_from_value(
             syn::LitStr::new(&variant_string, variant.span())
         };
 
-        stream.extend(quote! {
-            #ident::#variant_ident => ::geekorm::Value::Text(value.to_string()),
-        });
+        if let Some(discriminant) = discriminants.get(index) {
+            stream.extend(quote! {
+                #ident::#variant_ident => ::geekorm::Value::Integer(#discriminant),
+            });
+            from_value_stream.extend(quote! {
+                ::geekorm::Value::Integer(#discriminant) => #ident::#variant_ident,
+            });
+            from_int_stream.extend(quote! {
+                #discriminant => Ok(#ident::#variant_ident),
+            });
+        } else {
+            stream.extend(quote! {
+                #ident::#variant_ident => ::geekorm::Value::Text(value.to_string()),
+            });
+        }
         from_value_stream.extend(quote! {
             ::geekorm::Value::Text(ref s) if s == #variant_str => #ident::#variant_ident,
         });
     }
 
+    let from_int = if int_repr {
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics TryFrom<i64> for #ident #ty_generics {
+                type Error = ::geekorm::Error;
+
+                fn try_from(value: i64) -> Result<Self, Self::Error> {
+                    match value {
+                        #from_int_stream
+                        _ => Err(::geekorm::Error::UnknownVariant(value.to_string())),
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     Ok(quote! {
+        #from_int
+
         #[automatically_derived]
         impl #impl_generics From<#ident #ty_generics> for ::geekorm::Value {
             fn from(value: #ident #ty_generics) -> Self {
@@ -103,6 +261,49 @@ pub(crate) fn generate_from_value(
     })
 }
 
+/// Generate the list of all the variants of the enum
+///
+/// `ALL` lists every variant in declaration order, for example to build
+/// dropdowns or validate input.
+///
+/// ```rust
+/// # use geekorm::prelude::*;
+///
+/// # #[derive(Eq, PartialEq, Debug)]
+/// #[derive(Data, Default, Clone)]
+/// enum Role {
+///     Admin,
+///     Moderator,
+///     #[geekorm(key = "UserAccounts")]
+///     User,
+///     #[default]
+///     Guest,
+/// }
+///
+/// # assert_eq!(Role::ALL, &[Role::Admin, Role::Moderator, Role::User, Role::Guest]);
+/// let names: Vec<String> = Role::iter().map(|role| role.to_string()).collect();
+/// # assert_eq!(names, vec!["Admin", "Moderator", "UserAccounts", "Guest"]);
+/// ```
+pub(crate) fn generate_variants(
+    ident: &syn::Ident,
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> Result<TokenStream, syn::Error> {
+    let names = variants.iter().map(|variant| &variant.ident);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #ident {
+            /// All the variants of the enum (in declaration order)
+            pub const ALL: &'static [Self] = &[ #( #ident::#names ),* ];
+
+            /// Iterate over all the variants of the enum (in declaration order)
+            pub fn iter() -> impl Iterator<Item = &'static Self> {
+                Self::ALL.iter()
+            }
+        }
+    })
+}
+
 /// Generating ToString / Display implementations for the enum
 ///
 /// ```rust
@@ -133,15 +334,23 @@ pub(crate) fn generate_from_value(
 /// let unknown = UserRole::from("unknown");
 /// // This will use the default value
 /// assert_eq!(unknown, UserRole::Guest);
+///
+/// // Case-insensitive parsing can be picked per call site
+/// let admin = UserRole::from_str_ci("aDMINistrator").unwrap();
+/// # assert_eq!(admin, UserRole::Admin);
+/// # assert_eq!(UserRole::from_str_ci("USER").unwrap(), UserRole::User);
+/// # assert!(UserRole::from_str_ci("unknown").is_err());
 /// ```
 pub(crate) fn generate_strings(
     ident: &syn::Ident,
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
     _generics: &syn::Generics,
     attributes: &[GeekAttribute],
+    int_repr: bool,
 ) -> Result<TokenStream, syn::Error> {
     let mut stream = TokenStream::new();
     let mut str_to = TokenStream::new();
+    let mut str_to_ci = TokenStream::new();
 
     let from_lowercase: bool = attributes.iter().any(|attr| {
         attr.key == Some(crate::attr::GeekAttributeKeys::FromString)
@@ -164,10 +373,10 @@ pub(crate) fn generate_strings(
                 "Only unit variants are supported",
             ));
         }
-        if variant.discriminant.is_some() {
+        if variant.discriminant.is_some() && !int_repr {
             return Err(syn::Error::new(
                 variant.span(),
-                "Discriminant values are not supported",
+                "Discriminant values are only supported with `#[geekorm(integer)]`",
             ));
         }
 
@@ -227,6 +436,12 @@ pub(crate) fn generate_strings(
         str_to.extend(quote! {
             #(#variants)|* => #ident::#variant_ident,
         });
+        // Case-insensitive key | aliases*
+        str_to_ci.extend(quote! {
+            if #(s.eq_ignore_ascii_case(#variants) ||)* false {
+                return Ok(#ident::#variant_ident);
+            }
+        });
     }
 
     // The parsing is case-sensitive
@@ -248,6 +463,15 @@ pub(crate) fn generate_strings(
 
     let strings_tokens = if !disabled_from_strings {
         quote! {
+            #[automatically_derived]
+            impl #ident {
+                /// Parse the value from a string ignoring the (ASCII) case of the
+                /// keys and aliases
+                pub fn from_str_ci(s: &str) -> Result<Self, ::geekorm::Error> {
+                    #str_to_ci
+                    Err(::geekorm::Error::UnknownVariant(s.to_string()))
+                }
+            }
             #[automatically_derived]
             impl ::std::str::FromStr for #ident {
                 type Err = ::geekorm::Error;
@@ -313,6 +537,7 @@ pub(crate) fn generate_serde(
     ident: &syn::Ident,
     variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
     generics: &syn::Generics,
+    int_repr: bool,
 ) -> Result<TokenStream, syn::Error> {
     let (_impl_generics, _ty_generics, _where_clause) = generics.split_for_impl();
 
@@ -339,10 +564,10 @@ pub(crate) fn generate_serde(
                 "Only unit variants are supported",
             ));
         }
-        if variant.discriminant.is_some() {
+        if variant.discriminant.is_some() && !int_repr {
             return Err(syn::Error::new(
                 variant.span(),
-                "Discriminant values are not supported",
+                "Discriminant values are only supported with `#[geekorm(integer)]`",
             ));
         }
 
@@ -355,19 +580,40 @@ pub(crate) fn generate_serde(
         });
     }
 
-    stream.extend(quote! {
-        #[automatically_derived]
-        impl<'de> ::serde::Deserialize<'de> for #ident {
-            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-            where
-                D: ::serde::Deserializer<'de>,
-            {
-                use ::std::str::FromStr;
-                Self::from_str(String::deserialize(deserializer)?.as_str())
+    if int_repr {
+        // Integer backed enums are stored as integers but can still be parsed from strings
+        stream.extend(quote! {
+            #[automatically_derived]
+            impl<'de> ::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    use ::std::str::FromStr;
+                    match <::geekorm::Value as ::serde::Deserialize>::deserialize(deserializer)? {
+                        ::geekorm::Value::Integer(value) => Self::try_from(value),
+                        ::geekorm::Value::Text(value) => Self::from_str(value.as_str()),
+                        value => Err(::geekorm::Error::UnknownVariant(value.to_string())),
+                    }
                     .map_err(::serde::de::Error::custom)
+                }
             }
-        }
-    });
+        });
+    } else {
+        stream.extend(quote! {
+            #[automatically_derived]
+            impl<'de> ::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: ::serde::Deserializer<'de>,
+                {
+                    use ::std::str::FromStr;
+                    Self::from_str(String::deserialize(deserializer)?.as_str())
+                        .map_err(::serde::de::Error::custom)
+                }
+            }
+        });
+    }
 
     Ok(stream)
 }
@@ -5,7 +5,7 @@
 #![deny(missing_docs)]
 
 // Builder Modules
-pub use geekorm_core::builder::columns::{Column, Columns};
+pub use geekorm_core::builder::columns::{Column, ColumnChange, ColumnDiff, Columns};
 pub use geekorm_core::builder::columntypes::{ColumnType, ColumnTypeOptions};
 pub use geekorm_core::builder::database::Database;
 pub use geekorm_core::builder::table::Table;
@@ -17,7 +17,9 @@ pub use geekorm_core::builder::keys::primary::PrimaryKeyUuid;
 pub use geekorm_core::builder::keys::primary::{PrimaryKey, PrimaryKeyInteger, PrimaryKeyString};
 
 // Query Builder Modules
+pub use geekorm_core::builder::case::CaseBuilder;
 pub use geekorm_core::builder::models::{QueryCondition, QueryOrder, QueryType};
+pub use geekorm_core::builder::quote_identifier;
 pub use geekorm_core::builder::values::{Value, Values};
 
 // Query Modules
@@ -35,6 +37,7 @@ pub mod utils {
 pub use geekorm_core::migrations::{Migration, MigrationState};
 
 // Derive Crate
+pub use geekorm_derive::tables;
 pub use geekorm_derive::Data;
 pub use geekorm_derive::Table;
 
@@ -45,7 +48,14 @@ pub use geekorm_derive::GeekValue;
 // Traits
 pub use geekorm_core::QueryBuilderTrait;
 pub use geekorm_core::TableBuilder;
-pub use geekorm_core::{GeekConnection, GeekConnector};
+pub use geekorm_core::{FetchOrCreate, GeekConnection, GeekConnector, GeekQueryMap, ReadOnly};
+pub use geekorm_core::{FromBlob, ToBlob};
+
+/// Re-export the `inventory` crate (used by the derive to register tables)
+#[doc(hidden)]
+pub use geekorm_core::inventory;
+#[doc(hidden)]
+pub use geekorm_core::TableRegistration;
 
 /// Re-export the `lazy_static` crate
 #[cfg(feature = "migrations")]
@@ -93,10 +103,10 @@ pub mod prelude {
     /// SQLite Trait
     pub use geekorm_core::ToSqlite;
     // Backends Module
-    pub use geekorm_core::{GeekConnection, GeekConnector};
+    pub use geekorm_core::{FetchOrCreate, GeekConnection, GeekConnector, GeekQueryMap, ReadOnly};
 
     // Builder Modules
-    pub use geekorm_core::builder::columns::{Column, Columns};
+    pub use geekorm_core::builder::columns::{Column, ColumnChange, ColumnDiff, Columns};
     pub use geekorm_core::builder::columntypes::{ColumnType, ColumnTypeOptions};
     pub use geekorm_core::builder::table::Table as BuilderTable;
     #[cfg(feature = "pagination")]
@@ -121,7 +131,9 @@ pub mod prelude {
     pub use geekorm_core::TwoFactorAuth;
 
     pub use geekorm_core::builder::values::{Value, Values};
+    pub use geekorm_core::{FromBlob, ToBlob};
     // Query Builder Modules
+    pub use geekorm_core::builder::case::CaseBuilder;
     pub use geekorm_core::builder::models::{QueryCondition, QueryOrder, QueryType};
     // Query Modules
     pub use geekorm_core::queries::Query;